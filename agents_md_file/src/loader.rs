@@ -1,15 +1,31 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Cached AGENTS.md contents keyed by path, invalidated by modification time
+static AGENTS_MD_CACHE: Mutex<Option<HashMap<PathBuf, (SystemTime, String)>>> = Mutex::new(None);
 
 /// Load existing AGENTS.md file from project root
 pub fn load_agents_md(working_dir: &Path) -> Result<Option<String>> {
     let agents_path = working_dir.join("AGENTS.md");
 
-    if agents_path.exists() {
-        let content = fs::read_to_string(&agents_path)?;
-        Ok(Some(content))
-    } else {
-        Ok(None)
+    let modified = match fs::metadata(&agents_path) {
+        Ok(metadata) => metadata.modified()?,
+        Err(_) => return Ok(None),
+    };
+
+    let mut cache = AGENTS_MD_CACHE.lock().unwrap();
+    let cache = cache.get_or_insert_with(HashMap::new);
+    if let Some((cached_at, content)) = cache.get(&agents_path) {
+        if *cached_at == modified {
+            return Ok(Some(content.clone()));
+        }
     }
+
+    let content = fs::read_to_string(&agents_path)?;
+    cache.insert(agents_path, (modified, content.clone()));
+    Ok(Some(content))
 }
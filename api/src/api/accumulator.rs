@@ -1,13 +1,37 @@
+use std::collections::{BTreeMap, HashMap};
+
 use history::ToolCall;
 use ui::get_i18n;
 use ui::ToolCallDisplay;
 
 use super::parser::is_json_semantically_complete;
 
+/// Tool call being streamed
+#[derive(Default)]
+struct PendingCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+impl PendingCall {
+    /// Call id, synthesized from the stream index if the server sent none
+    fn call_id(&self, index: usize) -> String {
+        if self.id.is_empty() {
+            format!("call_{}", index)
+        } else {
+            self.id.clone()
+        }
+    }
+}
+
 pub struct ToolCallAccumulator {
-    calls: std::collections::HashMap<String, (String, String)>,
-    last_id: Option<String>,
-    displays: std::collections::HashMap<String, ToolCallDisplay>,
+    /// Calls keyed by stream index, which also keeps the model's ordering
+    calls: BTreeMap<usize, PendingCall>,
+    /// Slot of the call most recently started on each stream index, where fragments
+    /// without an id belong
+    latest_slot: HashMap<usize, usize>,
+    displays: HashMap<String, ToolCallDisplay>,
     has_tool_calls: bool,
     has_finish_reason: bool,
     finish_reason: Option<String>,
@@ -16,9 +40,9 @@ pub struct ToolCallAccumulator {
 impl ToolCallAccumulator {
     pub fn new() -> Self {
         Self {
-            calls: std::collections::HashMap::new(),
-            last_id: None,
-            displays: std::collections::HashMap::new(),
+            calls: BTreeMap::new(),
+            latest_slot: HashMap::new(),
+            displays: HashMap::new(),
             has_tool_calls: false,
             has_finish_reason: false,
             finish_reason: None,
@@ -31,43 +55,57 @@ impl ToolCallAccumulator {
         self.finish_reason = Some(reason);
     }
 
-    pub fn add_chunk(&mut self, id: String, name: String, arguments: String) {
+    /// Resolve the slot for a chunk: servers that omit `index` send every call as 0,
+    /// so a new id on an occupied index starts a new call, and later fragments without
+    /// an id continue the call most recently started on that index
+    fn slot_for(&self, index: usize, id: &str) -> usize {
+        if id.is_empty() {
+            return self.latest_slot.get(&index).copied().unwrap_or(index);
+        }
+        if let Some((&existing, _)) = self.calls.iter().find(|(_, c)| c.id == id) {
+            return existing;
+        }
+        match self.calls.get(&index) {
+            Some(call) if !call.id.is_empty() => {
+                self.calls.keys().next_back().map_or(index, |last| last + 1)
+            }
+            _ => index,
+        }
+    }
+
+    pub fn add_chunk(&mut self, index: usize, id: String, name: String, arguments: String) {
         // Mark that tool calls were detected
         if !id.is_empty() || !name.is_empty() || !arguments.is_empty() {
             self.has_tool_calls = true;
         }
 
-        // Use last valid ID if current ID is empty
-        let key = if id.is_empty() {
-            self.last_id.clone().unwrap_or_else(|| "temp".to_string())
-        } else {
-            self.last_id = Some(id.clone());
-            id.clone()
-        };
-
-        let entry = self
-            .calls
-            .entry(key.clone())
-            .or_insert((String::new(), String::new()));
+        let stream_index = index;
+        let index = self.slot_for(stream_index, &id);
+        if !id.is_empty() {
+            self.latest_slot.insert(stream_index, index);
+        }
+        let entry = self.calls.entry(index).or_default();
+        if entry.id.is_empty() && !id.is_empty() {
+            entry.id = id;
+        }
+        let key = entry.call_id(index);
 
         // Update name if provided
         if !name.is_empty() {
-            entry.0 = name.clone();
+            entry.name = name.clone();
             // Create UI display component
-            if !self.displays.contains_key(&key) {
-                self.displays
-                    .insert(key.clone(), ToolCallDisplay::new(name.clone()));
-            }
+            self.displays
+                .entry(key.clone())
+                .or_insert_with(|| ToolCallDisplay::new(name));
         }
 
         // Append arguments
         if !arguments.is_empty() {
-            entry.1.push_str(&arguments);
+            entry.arguments.push_str(&arguments);
 
             // Try to extract key argument and update UI
             if let Some(display) = self.displays.get_mut(&key) {
-                let tool_name = &entry.0;
-                if let Some(arg) = ui::extract_key_argument(tool_name, &entry.1) {
+                if let Some(arg) = ui::extract_key_argument(&entry.name, &entry.arguments) {
                     display.update_argument(arg);
                 }
                 display.render_streaming();
@@ -76,7 +114,7 @@ impl ToolCallAccumulator {
     }
 
     /// Get all UI display components
-    pub fn get_displays(&self) -> &std::collections::HashMap<String, ToolCallDisplay> {
+    pub fn get_displays(&self) -> &HashMap<String, ToolCallDisplay> {
         &self.displays
     }
 
@@ -85,7 +123,12 @@ impl ToolCallAccumulator {
 
         self.calls
             .into_iter()
-            .filter_map(|(id, (name, arguments))| {
+            .filter_map(|(index, call)| {
+                let id = call.call_id(index);
+                let PendingCall {
+                    name, arguments, ..
+                } = call;
+
                 // Filter out empty tool calls
                 if name.is_empty() || arguments.is_empty() {
                     let i18n = get_i18n();
@@ -184,3 +227,53 @@ impl Default for ToolCallAccumulator {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleaved_calls_keep_model_order() {
+        let mut acc = ToolCallAccumulator::new();
+        acc.add_chunk(1, "call_b".into(), "file_list".into(), String::new());
+        acc.add_chunk(0, "call_a".into(), "file_read".into(), String::new());
+        acc.add_chunk(1, String::new(), String::new(), r#"{"path":"#.into());
+        acc.add_chunk(0, String::new(), String::new(), r#"{"path":"a.rs"}"#.into());
+        acc.add_chunk(1, String::new(), String::new(), r#""src"}"#.into());
+
+        let calls = acc.into_tool_calls();
+        let ids: Vec<&str> = calls.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["call_a", "call_b"]);
+        assert_eq!(calls[1].function.arguments, r#"{"path":"src"}"#);
+    }
+
+    #[test]
+    fn test_new_id_on_same_index_starts_new_call() {
+        let mut acc = ToolCallAccumulator::new();
+        acc.add_chunk(0, "call_a".into(), "file_read".into(), "{}".into());
+        acc.add_chunk(0, "call_b".into(), "file_list".into(), "{}".into());
+
+        let calls = acc.into_tool_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].function.name, "file_list");
+    }
+
+    #[test]
+    fn test_fragments_without_index_follow_latest_call() {
+        // Servers that omit `index` send every chunk as 0, with arguments in separate chunks
+        let mut acc = ToolCallAccumulator::new();
+        acc.add_chunk(0, "call_a".into(), "file_read".into(), String::new());
+        acc.add_chunk(0, String::new(), String::new(), r#"{"path":"#.into());
+        acc.add_chunk(0, String::new(), String::new(), r#""a.rs"}"#.into());
+        acc.add_chunk(0, "call_b".into(), "file_list".into(), String::new());
+        acc.add_chunk(0, String::new(), String::new(), r#"{"path":"#.into());
+        acc.add_chunk(0, String::new(), String::new(), r#""src"}"#.into());
+
+        let calls = acc.into_tool_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_a");
+        assert_eq!(calls[0].function.arguments, r#"{"path":"a.rs"}"#);
+        assert_eq!(calls[1].id, "call_b");
+        assert_eq!(calls[1].function.arguments, r#"{"path":"src"}"#);
+    }
+}
@@ -1,21 +1,35 @@
 use anyhow::Result;
 use futures::StreamExt;
 use reqwest::Client;
+use std::sync::Arc;
 use tokio_stream::Stream;
 
-use config::Config;
+use config::{Config, ModelProfile};
 use history::Message;
 use tools;
 use ui::get_i18n;
 
-use super::parser::parse_sse_line;
-use super::stream::SseLineStream;
-use super::types::{ChatRequest, ModelsResponse, StreamChunk};
+use super::error::{backoff_delay, jitter, parse_retry_after, ApiError};
+use super::providers::{create_provider, Provider};
+use super::recorder::{write_stream_bytes, Exchange, Recorder};
+use super::side_usage::record_side_usage;
+use super::stream::decode_stream;
+use super::types::{ChatRequest, StreamChunk, StreamOptions};
+
+/// Instruction appended after the partial answer when a broken stream is resumed
+const RESUME_PROMPT: &str = "[Your previous response was cut off by a connection error. \
+Continue exactly where it stopped, without repeating any text already written.]";
+
+type ChunkStream = Box<dyn Stream<Item = Result<StreamChunk>> + Unpin + Send>;
 
 #[derive(Clone)]
 pub struct ApiClient {
     client: Client,
     config: Config,
+    provider: Arc<dyn Provider>,
+    recorder: Option<Arc<Recorder>>,
+    /// Clients for the configured fallback models, tried in order
+    fallbacks: Vec<ApiClient>,
 }
 
 impl ApiClient {
@@ -26,7 +40,83 @@ impl ApiClient {
             .build()
             .unwrap_or_else(|_| Client::new());
 
-        Self { client, config }
+        let recorder = config
+            .record_dir
+            .as_deref()
+            .and_then(|dir| match Recorder::new(dir) {
+                Ok(recorder) => Some(Arc::new(recorder)),
+                Err(e) => {
+                    let i18n = get_i18n();
+                    eprintln!(
+                        "\x1b[33m[!] {}:\x1b[0m {}",
+                        i18n.get("api_record_failed"),
+                        e
+                    );
+                    None
+                }
+            });
+
+        Self::with_connection(config, client, recorder)
+    }
+
+    /// Build a client sharing the HTTP connection pool and recorder
+    fn with_connection(config: Config, client: Client, recorder: Option<Arc<Recorder>>) -> Self {
+        let provider = Arc::from(create_provider(&config.provider));
+        let fallbacks = config
+            .fallback_configs()
+            .into_iter()
+            .map(|c| Self::with_connection(c, client.clone(), recorder.clone()))
+            .collect();
+
+        Self {
+            client,
+            config,
+            provider,
+            recorder,
+            fallbacks,
+        }
+    }
+
+    /// The configured (primary) model
+    pub fn model(&self) -> &str {
+        &self.config.current_model
+    }
+
+    /// Request parameters configured for the current model
+    fn profile(&self) -> ModelProfile {
+        self.config
+            .model_profile(&self.config.current_model)
+            .map(|(_, profile)| profile.clone())
+            .unwrap_or_default()
+    }
+
+    /// Refuse to send images to a model whose profile says it cannot see them
+    fn check_vision(&self, messages: &[Message]) -> Result<()> {
+        let has_images = messages.iter().any(|m| m.content.has_images());
+        if has_images && !self.profile().supports_vision() {
+            return Err(ApiError::NoVision {
+                model: self.config.current_model.clone(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Start recording an exchange when `--record` is active
+    fn record_request(&self, body: &serde_json::Value) -> Option<Exchange> {
+        let recorder = self.recorder.as_ref()?;
+        match recorder.record_request(body) {
+            Ok(exchange) => Some(exchange),
+            Err(e) => {
+                let i18n = get_i18n();
+                eprintln!(
+                    "\x1b[33m[!] {}:\x1b[0m {}",
+                    i18n.get("api_record_failed"),
+                    e
+                );
+                None
+            }
+        }
     }
 
     /// Clean message history: remove orphaned tool calls without responses
@@ -37,9 +127,7 @@ impl ApiClient {
         while i < messages.len() {
             let msg = &messages[i];
 
-            if msg.role == "assistant" && msg.tool_calls.is_some() {
-                let tool_calls = msg.tool_calls.as_ref().unwrap();
-
+            if let Some(tool_calls) = msg.tool_calls.as_ref().filter(|_| msg.role == "assistant") {
                 let tool_call_ids: std::collections::HashSet<_> =
                     tool_calls.iter().map(|tc| tc.id.clone()).collect();
 
@@ -79,151 +167,310 @@ impl ApiClient {
             i += 1;
         }
 
+        // Usage and model are local bookkeeping and must not be sent back to the API
+        for msg in &mut cleaned {
+            msg.usage = None;
+            msg.model = None;
+        }
+
         cleaned
     }
 
-    /// Stream chat with retry logic
-    pub async fn chat_stream_with_retry(
-        &self,
-        messages: Vec<Message>,
-    ) -> Result<Box<dyn Stream<Item = Result<StreamChunk>> + Unpin + Send>> {
-        let cleaned_messages = Self::clean_messages(&messages);
+    /// Stream chat with retry logic.
+    /// Only retryable failures are retried, honoring `Retry-After`. A stream that breaks
+    /// before any tool call was emitted is resumed transparently, keeping the partial content.
+    pub async fn chat_stream_with_retry(&self, messages: Vec<Message>) -> Result<ChunkStream> {
+        let messages = Self::clean_messages(&messages);
 
-        let max_retries = self.config.max_retries;
-        let base_delay = self.config.retry_delay_ms;
+        let (client, stream, attempt) = self.open_with_fallback(&messages).await?;
+        let model = client.config.current_model.clone();
+
+        let state = ResumeState {
+            client: client.clone(),
+            messages,
+            stream,
+            attempt,
+            partial: String::new(),
+            saw_tool_call: false,
+            failed: false,
+        };
+        let resumable = futures::stream::unfold(state, |mut state| async move {
+            if state.failed {
+                return None;
+            }
+            loop {
+                let error = match state.stream.next().await? {
+                    Ok(chunk) => {
+                        match &chunk {
+                            StreamChunk::Content(text) => state.partial.push_str(text),
+                            StreamChunk::ToolCall { .. } => state.saw_tool_call = true,
+                            _ => {}
+                        }
+                        return Some((Ok(chunk), state));
+                    }
+                    Err(e) => ApiError::classify(&e),
+                };
+
+                // Tool call fragments can't be stitched across responses, so those fail
+                let max_retries = state.client.config.max_retries;
+                if state.saw_tool_call || !error.is_retryable() || state.attempt >= max_retries {
+                    state.failed = true;
+                    return Some((Err(error.into()), state));
+                }
 
-        for attempt in 0..=max_retries {
-            if attempt > 0 {
-                let delay = base_delay * (1 << (attempt - 1)); // exponential backoff
                 let i18n = get_i18n();
-                println!(
-                    "\n\x1b[33m[!] {} {}/{}...{} {}ms\x1b[0m",
-                    i18n.get("api_retry_label"),
-                    attempt,
-                    max_retries,
-                    i18n.get("api_retry_waiting"),
-                    delay
+                eprintln!(
+                    "\n\x1b[33m[!] {}: {}\x1b[0m",
+                    i18n.get("api_stream_interrupted"),
+                    error
                 );
-                tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+                state.attempt += 1;
+                state.client.wait_before_retry(state.attempt, &error).await;
+
+                let messages = resume_messages(&state.messages, &state.partial);
+                match state
+                    .client
+                    .open_stream(&messages, &mut state.attempt)
+                    .await
+                {
+                    Ok(stream) => state.stream = stream,
+                    Err(e) => {
+                        state.failed = true;
+                        return Some((Err(e), state));
+                    }
+                }
             }
+        });
+
+        let stream =
+            futures::stream::once(async move { Ok(StreamChunk::Model(model)) }).chain(resumable);
+        Ok(Box::new(Box::pin(stream)))
+    }
 
-            match self.chat_stream(cleaned_messages.clone()).await {
+    /// Open a stream on this model, moving down the fallback list while models
+    /// are overloaded, rate limited or failing. Returns the answering client and
+    /// the retries it used.
+    async fn open_with_fallback(
+        &self,
+        messages: &[Message],
+    ) -> Result<(&ApiClient, ChunkStream, u32)> {
+        let mut clients = std::iter::once(self)
+            .chain(self.fallbacks.iter())
+            .peekable();
+
+        loop {
+            let client = clients.next().expect("at least the primary client");
+            let mut attempt = 0;
+            let error = match client.open_stream(messages, &mut attempt).await {
+                Ok(stream) => return Ok((client, stream, attempt)),
+                Err(e) => e,
+            };
+
+            let next = match clients.peek() {
+                Some(next) if ApiError::classify(&error).should_fall_back() => next,
+                _ => return Err(error),
+            };
+            print_fallback(client.model(), next.model());
+        }
+    }
+
+    /// Open a stream, retrying retryable failures until `max_retries` attempts are used up
+    async fn open_stream(&self, messages: &[Message], attempt: &mut u32) -> Result<ChunkStream> {
+        let max_retries = self.config.max_retries;
+
+        loop {
+            let error = match self.chat_stream(messages.to_vec()).await {
                 Ok(stream) => return Ok(stream),
-                Err(e) => {
-                    if attempt == max_retries {
-                        let i18n = get_i18n();
-                        eprintln!("\n\x1b[31m[X] {}\x1b[0m", i18n.get("api_retries_failed"));
-                        return Err(e);
-                    }
-                    let i18n = get_i18n();
-                    eprintln!(
-                        "\n\x1b[33m[!] {}: {}\x1b[0m",
-                        i18n.get("api_request_failed"),
-                        e
-                    );
-                }
+                Err(e) => ApiError::classify(&e),
+            };
+
+            if !error.is_retryable() {
+                return Err(error.into());
+            }
+            if *attempt >= max_retries {
+                let i18n = get_i18n();
+                eprintln!("\n\x1b[31m[X] {}\x1b[0m", i18n.get("api_retries_failed"));
+                return Err(error.into());
             }
+
+            let i18n = get_i18n();
+            eprintln!(
+                "\n\x1b[33m[!] {}: {}\x1b[0m",
+                i18n.get("api_request_failed"),
+                error
+            );
+            *attempt += 1;
+            self.wait_before_retry(*attempt, &error).await;
         }
+    }
 
+    /// Sleep before retry number `attempt`
+    async fn wait_before_retry(&self, attempt: u32, error: &ApiError) {
+        let delay = backoff_delay(
+            attempt,
+            self.config.retry_delay_ms,
+            error.retry_after(),
+            jitter(),
+        );
         let i18n = get_i18n();
-        Err(anyhow::anyhow!(i18n.get("api_retries_failed")))
+        println!(
+            "\n\x1b[33m[!] {} {}/{}...{} {}ms\x1b[0m",
+            i18n.get("api_retry_label"),
+            attempt,
+            self.config.max_retries,
+            i18n.get("api_retry_waiting"),
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
     }
 
     /// Stream chat completions
-    pub async fn chat_stream(
-        &self,
-        messages: Vec<Message>,
-    ) -> Result<Box<dyn Stream<Item = Result<StreamChunk>> + Unpin + Send>> {
-        let url = format!("{}/chat/completions", self.config.api_url);
+    pub async fn chat_stream(&self, messages: Vec<Message>) -> Result<ChunkStream> {
+        self.check_vision(&messages)?;
+        let url = self.provider.chat_url(&self.config.api_url);
 
+        let profile = self.profile();
         let request = ChatRequest {
             model: self.config.current_model.clone(),
             messages,
             tools: tools::get_available_tools(),
             stream: true,
-            max_tokens: None,
+            max_tokens: profile.max_tokens,
+            stream_options: profile.requests_stream_usage().then_some(StreamOptions {
+                include_usage: true,
+            }),
+            profile,
         };
 
+        let body = self.provider.build_body(&request);
+        let exchange = self.record_request(&body);
+
         let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .provider
+            .authorize(self.client.post(&url), &self.config.api_key)
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(&body)
             .send()
-            .await?;
+            .await
+            .map_err(|e| ApiError::from_reqwest(&e))?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await?;
-            anyhow::bail!("API error {}: {}", status, text);
+            return Err(error_from_response(response, exchange.as_ref())
+                .await
+                .into());
         }
 
-        let stream = response.bytes_stream();
-        let sse_stream = SseLineStream::new(stream);
+        let mut recording = exchange.and_then(|e| e.stream_file().ok());
+        let bytes = response.bytes_stream().inspect(move |chunk| {
+            if let Ok(bytes) = chunk {
+                write_stream_bytes(&mut recording, bytes);
+            }
+        });
 
-        let mapped_stream = sse_stream.filter_map(|line_result| async move {
-            match line_result {
-                Ok(line) => parse_sse_line(&line),
-                Err(e) => Some(Err(e)),
+        let model = request.model.clone();
+        let mapped_stream = decode_stream(
+            bytes,
+            self.provider.stream_format(),
+            self.provider.stream_decoder(),
+        )
+        .map(move |chunk| match chunk {
+            Ok(StreamChunk::Usage(mut usage)) => {
+                usage.model = model.clone();
+                Ok(StreamChunk::Usage(usage))
             }
+            other => other,
         });
 
         Ok(Box::new(Box::pin(mapped_stream)))
     }
 
-    /// Non-streaming chat completion (for simple requests like prompt optimization)
+    /// Non-streaming chat completion (for simple requests like prompt optimization).
+    /// Falls back to the next model on overload, rate limit or server errors.
+    /// Its usage is queued as side usage, see `take_side_usage`.
     pub async fn chat_complete(&self, messages: Vec<Message>) -> Result<Message> {
-        let url = format!("{}/chat/completions", self.config.api_url);
-        
+        let mut clients = std::iter::once(self)
+            .chain(self.fallbacks.iter())
+            .peekable();
+
+        loop {
+            let client = clients.next().expect("at least the primary client");
+            let error = match client.complete_once(&messages).await {
+                Ok(mut message) => {
+                    message.model = Some(client.model().to_string());
+                    if let Some(usage) = &message.usage {
+                        record_side_usage(usage.clone());
+                    }
+                    return Ok(message);
+                }
+                Err(e) => e,
+            };
+
+            let next = match clients.peek() {
+                Some(next) if ApiError::classify(&error).should_fall_back() => next,
+                _ => return Err(error),
+            };
+            print_fallback(client.model(), next.model());
+        }
+    }
+
+    async fn complete_once(&self, messages: &[Message]) -> Result<Message> {
+        self.check_vision(messages)?;
+        let url = self.provider.chat_url(&self.config.api_url);
+
         let request = ChatRequest {
             model: self.config.current_model.clone(),
-            messages,
-            tools: vec![],  // No tools for simple completion
+            messages: Self::clean_messages(messages),
+            tools: vec![], // No tools for simple completion
             stream: false,
-            max_tokens: Some(1000),  // Limit tokens for optimization
+            max_tokens: Some(1000), // Limit tokens for optimization
+            stream_options: None,
+            profile: self.profile(),
         };
-        
+
+        let body = self.provider.build_body(&request);
+        let exchange = self.record_request(&body);
+
         let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .provider
+            .authorize(self.client.post(&url), &self.config.api_key)
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(&body)
             .send()
-            .await?;
-        
+            .await
+            .map_err(|e| ApiError::from_reqwest(&e))?;
+
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await?;
-            anyhow::bail!("API error {}: {}", status, text);
+            return Err(error_from_response(response, exchange.as_ref())
+                .await
+                .into());
         }
-        
+
         // Parse response
-        let response_json: serde_json::Value = response.json().await?;
-        
-        let content = response_json["choices"][0]["message"]["content"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
-        
-        Ok(Message {
-            role: "assistant".to_string(),
-            content,
-            tool_calls: None,
-            tool_call_id: None,
-            name: None,
-        })
+        let text = response
+            .text()
+            .await
+            .map_err(|e| ApiError::from_reqwest(&e))?;
+        if let Some(exchange) = &exchange {
+            let _ = exchange.record_response(&text);
+        }
+        let response_json: serde_json::Value =
+            serde_json::from_str(&text).map_err(|e| ApiError::MalformedStream {
+                message: e.to_string(),
+            })?;
+        let mut message = self.provider.parse_response(&response_json)?;
+        if let Some(usage) = message.usage.as_mut() {
+            usage.model = request.model.clone();
+        }
+        Ok(message)
     }
 
     /// List available models
     pub async fn list_models(&self) -> Result<Vec<String>> {
-        let url = format!("{}/models", self.config.api_url);
+        let url = self.provider.models_url(&self.config.api_url);
 
         let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .provider
+            .authorize(self.client.get(&url), &self.config.api_key)
             .send()
             .await?;
 
@@ -232,7 +479,382 @@ impl ApiClient {
             anyhow::bail!(i18n.get("api_models_failed"));
         }
 
-        let models_response: ModelsResponse = response.json().await?;
-        Ok(models_response.data.into_iter().map(|m| m.id).collect())
+        let models_json: serde_json::Value = response.json().await?;
+        Ok(self.provider.parse_models(&models_json))
+    }
+}
+
+/// State of a stream that resumes after a connection failure
+struct ResumeState {
+    client: ApiClient,
+    messages: Vec<Message>,
+    stream: ChunkStream,
+    /// Retries used so far, shared with reconnect attempts
+    attempt: u32,
+    /// Content already delivered to the caller
+    partial: String,
+    saw_tool_call: bool,
+    failed: bool,
+}
+
+fn print_fallback(failed: &str, next: &str) {
+    let i18n = get_i18n();
+    eprintln!(
+        "\n\x1b[33m[!] {}\x1b[0m",
+        i18n.get("api_fallback_model")
+            .replacen("{}", failed, 1)
+            .replacen("{}", next, 1)
+    );
+}
+
+/// Messages for resuming a broken stream: the partial answer plus a request to continue it
+fn resume_messages(messages: &[Message], partial: &str) -> Vec<Message> {
+    let mut messages = messages.to_vec();
+    if partial.is_empty() {
+        return messages;
+    }
+    for (role, content) in [("assistant", partial), ("user", RESUME_PROMPT)] {
+        messages.push(Message {
+            role: role.to_string(),
+            content: content.into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        });
+    }
+    messages
+}
+
+/// Classify a non-success response, keeping the server's `Retry-After`
+async fn error_from_response(response: reqwest::Response, exchange: Option<&Exchange>) -> ApiError {
+    let status = response.status().as_u16();
+    let retry_after = parse_retry_after(response.headers());
+    match response.text().await {
+        Ok(body) => {
+            if let Some(exchange) = exchange {
+                let _ = exchange.record_error(status, retry_after.map(|d| d.as_secs()), &body);
+            }
+            ApiError::from_status(status, retry_after, &body)
+        }
+        Err(e) => ApiError::from_reqwest(&e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{execute_tool_calls, ToolCallAccumulator};
+    use config::FallbackModel;
+    use mock_llm::{load_transcript, MockResponse, MockServer, MockToolCall};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn test_config(server: &MockServer) -> Config {
+        Config {
+            api_key: "test-key".to_string(),
+            api_url: server.url(),
+            current_model: "mock-model".to_string(),
+            provider: "openai".to_string(),
+            ui_language: "en".to_string(),
+            ai_language: "en".to_string(),
+            max_retries: 2,
+            retry_delay_ms: 1,
+            model_prices: Default::default(),
+            context_limits: Default::default(),
+            default_context_limit: 128_000,
+            model_profiles: Default::default(),
+            fallback_models: Default::default(),
+            mcp_servers: Default::default(),
+            allowed_roots: Default::default(),
+            sensitive_paths: Default::default(),
+            auto_commit: false,
+            command_timeout_secs: config::DEFAULT_COMMAND_TIMEOUT_SECS,
+            record_dir: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("friendev_api_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Drain a response stream: content, accumulated tool calls and the first error
+    async fn drain(
+        mut stream: ChunkStream,
+    ) -> (String, ToolCallAccumulator, Option<anyhow::Error>) {
+        let mut content = String::new();
+        let mut accumulator = ToolCallAccumulator::new();
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(StreamChunk::Content(text)) => content.push_str(&text),
+                Ok(StreamChunk::ToolCall {
+                    index,
+                    id,
+                    name,
+                    arguments,
+                }) => accumulator.add_chunk(index, id, name, arguments),
+                Ok(StreamChunk::FinishReason(reason)) => accumulator.set_finish_reason(reason),
+                Ok(StreamChunk::Done) => break,
+                Ok(_) => {}
+                Err(e) => return (content, accumulator, Some(e)),
+            }
+        }
+        (content, accumulator, None)
+    }
+
+    fn msg(role: &str, content: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: content.into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_resume_messages_carry_partial_content() {
+        let messages = vec![msg("user", "explain")];
+        assert_eq!(resume_messages(&messages, "").len(), 1);
+
+        let resumed = resume_messages(&messages, "Rust's borrow checker");
+        assert_eq!(resumed.len(), 3);
+        assert_eq!(resumed[1].role, "assistant");
+        assert_eq!(resumed[1].content, "Rust's borrow checker");
+        assert_eq!(resumed[2].content, RESUME_PROMPT);
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_end_to_end_with_recording() {
+        let workdir = temp_dir("tool_loop");
+        std::fs::write(workdir.join("hello.txt"), "hello from disk").unwrap();
+        let record_dir = workdir.join("recording");
+
+        let server = MockServer::start(vec![
+            MockResponse::tool_calls(vec![
+                MockToolCall::new(
+                    "call_read",
+                    "file_read",
+                    json!({ "path": workdir.join("hello.txt") }),
+                ),
+                MockToolCall::new("call_list", "file_list", json!({ "path": workdir })),
+            ]),
+            MockResponse::text("The file says hello."),
+        ])
+        .await
+        .unwrap();
+        let mut config = test_config(&server);
+        config.record_dir = Some(record_dir.clone());
+        let client = ApiClient::new(config);
+
+        let mut messages = vec![msg("user", "read hello.txt")];
+        let stream = client
+            .chat_stream_with_retry(messages.clone())
+            .await
+            .unwrap();
+        let (_, accumulator, error) = drain(stream).await;
+        assert!(error.is_none());
+
+        let mut displays = accumulator.get_displays().clone();
+        let calls = accumulator.into_tool_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_read");
+        assert_eq!(calls[1].function.name, "file_list");
+
+        let mut assistant = msg("assistant", "");
+        assistant.tool_calls = Some(calls.clone());
+        messages.push(assistant);
+        messages.extend(execute_tool_calls(&calls, &workdir, &mut displays, false).await);
+
+        let stream = client.chat_stream_with_retry(messages).await.unwrap();
+        let (content, _, error) = drain(stream).await;
+        assert!(error.is_none());
+        assert_eq!(content, "The file says hello.");
+
+        // The tool results were sent back, answering each call id
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        let sent = requests[1].body["messages"].as_array().unwrap();
+        let tool_result = sent
+            .iter()
+            .find(|m| m["tool_call_id"] == "call_read")
+            .unwrap();
+        assert!(tool_result["content"]
+            .as_str()
+            .unwrap()
+            .contains("hello from disk"));
+
+        // The recording replays to the same answers
+        assert!(record_dir.join("0001-request.json").exists());
+        let replay = MockServer::start(load_transcript(&record_dir).unwrap())
+            .await
+            .unwrap();
+        let client = ApiClient::new(test_config(&replay));
+        let stream = client
+            .chat_stream_with_retry(vec![msg("user", "again")])
+            .await
+            .unwrap();
+        let (_, accumulator, _) = drain(stream).await;
+        assert_eq!(accumulator.into_tool_calls().len(), 2);
+        let stream = client
+            .chat_stream_with_retry(vec![msg("user", "again")])
+            .await
+            .unwrap();
+        assert_eq!(drain(stream).await.0, "The file says hello.");
+
+        std::fs::remove_dir_all(&workdir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_retried() {
+        let server = MockServer::start(vec![
+            MockResponse::Error {
+                status: 429,
+                body: r#"{"error":{"message":"slow down"}}"#.to_string(),
+                retry_after: Some(0),
+            },
+            MockResponse::text("ok"),
+        ])
+        .await
+        .unwrap();
+        let client = ApiClient::new(test_config(&server));
+
+        let stream = client
+            .chat_stream_with_retry(vec![msg("user", "hi")])
+            .await
+            .unwrap();
+        assert_eq!(drain(stream).await.0, "ok");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_is_not_retried() {
+        let server = MockServer::start(vec![
+            MockResponse::error(401, r#"{"error":{"message":"Invalid API key"}}"#),
+            MockResponse::text("unreachable"),
+        ])
+        .await
+        .unwrap();
+        let client = ApiClient::new(test_config(&server));
+
+        let error = client
+            .chat_stream_with_retry(vec![msg("user", "hi")])
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error.downcast_ref::<ApiError>(),
+            Some(ApiError::Auth { .. })
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_broken_stream_resumes_with_partial_content() {
+        let server = MockServer::start(vec![
+            MockResponse::sse("data: {\"choices\":[{\"delta\":{\"content\":\"Hello, \"}}]}\n\n"),
+            MockResponse::text("world."),
+        ])
+        .await
+        .unwrap();
+        let client = ApiClient::new(test_config(&server));
+
+        let stream = client
+            .chat_stream_with_retry(vec![msg("user", "greet")])
+            .await
+            .unwrap();
+        let (content, _, error) = drain(stream).await;
+        assert!(error.is_none());
+        assert_eq!(content, "Hello, world.");
+
+        let resumed = server.requests()[1].body["messages"].clone();
+        let resumed = resumed.as_array().unwrap();
+        assert_eq!(resumed[resumed.len() - 2]["content"], "Hello, ");
+        assert_eq!(resumed[resumed.len() - 1]["content"], RESUME_PROMPT);
+    }
+
+    #[tokio::test]
+    async fn test_broken_stream_after_tool_call_fails() {
+        let server = MockServer::start(vec![
+            MockResponse::sse(
+                "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"function\":{\"name\":\"file_read\",\"arguments\":\"{\\\"pa\"}}]}}]}\n\n",
+            ),
+            MockResponse::text("unreachable"),
+        ])
+        .await
+        .unwrap();
+        let client = ApiClient::new(test_config(&server));
+
+        let stream = client
+            .chat_stream_with_retry(vec![msg("user", "read")])
+            .await
+            .unwrap();
+        let (_, _, error) = drain(stream).await;
+        assert!(matches!(
+            error.as_ref().and_then(|e| e.downcast_ref::<ApiError>()),
+            Some(ApiError::MalformedStream { .. })
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_next_model_on_server_errors() {
+        let primary = MockServer::start(vec![MockResponse::error(503, "overloaded"); 3])
+            .await
+            .unwrap();
+        let local = MockServer::start(vec![MockResponse::text("from the fallback")])
+            .await
+            .unwrap();
+        let mut config = test_config(&primary);
+        config.fallback_models = vec![FallbackModel {
+            model: "local-model".to_string(),
+            api_url: Some(local.url()),
+            ..Default::default()
+        }];
+        let client = ApiClient::new(config);
+
+        let mut stream = client
+            .chat_stream_with_retry(vec![msg("user", "hi")])
+            .await
+            .unwrap();
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(StreamChunk::Model(ref m))) if m == "local-model"
+        ));
+        assert_eq!(drain(stream).await.0, "from the fallback");
+        assert_eq!(primary.requests().len(), 3);
+        assert_eq!(local.requests()[0].body["model"], "local-model");
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_does_not_fall_back() {
+        let primary = MockServer::start(vec![MockResponse::error(401, "bad key")])
+            .await
+            .unwrap();
+        let fallback = MockServer::start(vec![MockResponse::text("unreachable")])
+            .await
+            .unwrap();
+        let mut config = test_config(&primary);
+        config.fallback_models = vec![FallbackModel {
+            model: "other".to_string(),
+            api_url: Some(fallback.url()),
+            ..Default::default()
+        }];
+        let client = ApiClient::new(config);
+
+        assert!(client
+            .chat_stream_with_retry(vec![msg("user", "hi")])
+            .await
+            .is_err());
+        assert!(fallback.requests().is_empty());
     }
 }
@@ -0,0 +1,461 @@
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+use ui::get_i18n;
+
+use super::sse::SseEvent;
+
+/// Error reported by the provider inside a streaming response
+/// (e.g. `overloaded_error` from Anthropic, `server_error` from OpenAI)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamError {
+    /// Provider error type or code
+    pub kind: String,
+    pub message: String,
+}
+
+impl StreamError {
+    pub fn new(kind: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Detect an error payload in a stream event:
+    /// an `error` event, a `{"type":"error"}` object or an `{"error": ...}` field
+    pub fn from_event(event: &SseEvent) -> Option<Self> {
+        let value = match serde_json::from_str::<Value>(&event.data) {
+            Ok(value) => value,
+            Err(_) if event.is("error") => return Some(Self::new("error", event.data.trim())),
+            Err(_) => return None,
+        };
+
+        match &value["error"] {
+            Value::Object(_) => return Some(Self::from_error_object(&value["error"])),
+            Value::String(message) => return Some(Self::new("error", message.as_str())),
+            _ => {}
+        }
+
+        if event.is("error") || value["type"] == "error" {
+            return Some(Self::from_error_object(&value));
+        }
+
+        None
+    }
+
+    fn from_error_object(error: &Value) -> Self {
+        let kind = [&error["type"], &error["code"]]
+            .into_iter()
+            .filter_map(|v| match v {
+                Value::String(s) if s != "error" => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+            .next()
+            .unwrap_or_else(|| "error".to_string());
+        let message = error["message"]
+            .as_str()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| error.to_string());
+        Self::new(kind, message)
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+/// Longest server-requested wait that is honored before giving up on a retry
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Upper bound of the exponential backoff
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Phrases providers use when a request exceeds the context window
+const CONTEXT_OVERFLOW_MARKERS: &[&str] = &[
+    "context_length_exceeded",
+    "context length",
+    "context window",
+    "prompt is too long",
+    "too many tokens",
+    "maximum tokens",
+    "input is too long",
+];
+
+/// Classified API failure, decides whether and when a request is retried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// Missing, invalid or unauthorized API key (401/403)
+    Auth {
+        status: Option<u16>,
+        message: String,
+    },
+    /// Too many requests (429)
+    RateLimit {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// The request does not fit the model's context window
+    ContextOverflow { message: String },
+    /// Server error or provider overload (5xx)
+    Server {
+        status: Option<u16>,
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// Any other rejected request (400, 404, ...)
+    InvalidRequest {
+        status: Option<u16>,
+        message: String,
+    },
+    /// Connection failed, timed out or dropped
+    Network { message: String },
+    /// The stream ended early or carried data that could not be decoded
+    MalformedStream { message: String },
+    /// The conversation carries images but the model's profile says it lacks vision
+    NoVision { model: String },
+}
+
+impl ApiError {
+    /// Classify a non-success HTTP response
+    pub fn from_status(status: u16, retry_after: Option<Duration>, body: &str) -> Self {
+        let message = error_message(body);
+        if is_context_overflow(body) {
+            return Self::ContextOverflow { message };
+        }
+        match status {
+            401 | 403 => Self::Auth {
+                status: Some(status),
+                message,
+            },
+            429 => Self::RateLimit {
+                retry_after,
+                message,
+            },
+            408 | 409 | 500..=599 => Self::Server {
+                status: Some(status),
+                retry_after,
+                message,
+            },
+            _ => Self::InvalidRequest {
+                status: Some(status),
+                message,
+            },
+        }
+    }
+
+    /// Classify an error event reported inside a stream
+    pub fn from_stream_error(error: &StreamError) -> Self {
+        let kind = error.kind.to_lowercase();
+        let message = error.message.clone();
+
+        if is_context_overflow(&kind) || is_context_overflow(&message) {
+            Self::ContextOverflow { message }
+        } else if kind.contains("rate_limit") {
+            Self::RateLimit {
+                retry_after: None,
+                message,
+            }
+        } else if kind.contains("auth") || kind.contains("permission") || kind.contains("api_key") {
+            Self::Auth {
+                status: None,
+                message,
+            }
+        } else if kind.contains("invalid_request") || kind.contains("not_found") {
+            Self::InvalidRequest {
+                status: None,
+                message,
+            }
+        } else {
+            // overloaded_error, server_error, api_error and unknown kinds are transient
+            Self::Server {
+                status: None,
+                retry_after: None,
+                message: error.to_string(),
+            }
+        }
+    }
+
+    /// Classify any error produced while talking to the API
+    pub fn classify(error: &anyhow::Error) -> Self {
+        if let Some(api_error) = error.downcast_ref::<ApiError>() {
+            return api_error.clone();
+        }
+        if let Some(stream_error) = error.downcast_ref::<StreamError>() {
+            return Self::from_stream_error(stream_error);
+        }
+        if let Some(reqwest_error) = error.downcast_ref::<reqwest::Error>() {
+            return Self::from_reqwest(reqwest_error);
+        }
+        Self::Network {
+            message: error.to_string(),
+        }
+    }
+
+    pub fn from_reqwest(error: &reqwest::Error) -> Self {
+        if error.is_decode() {
+            Self::MalformedStream {
+                message: error.to_string(),
+            }
+        } else {
+            Self::Network {
+                message: error.to_string(),
+            }
+        }
+    }
+
+    /// Whether retrying the same request may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimit { .. }
+                | Self::Server { .. }
+                | Self::Network { .. }
+                | Self::MalformedStream { .. }
+        )
+    }
+
+    /// Whether another model may answer when this one keeps failing
+    pub fn should_fall_back(&self) -> bool {
+        matches!(self, Self::RateLimit { .. } | Self::Server { .. })
+    }
+
+    /// Wait requested by the server (`Retry-After`)
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimit { retry_after, .. } | Self::Server { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Actionable advice shown to the user when the request finally fails
+    pub fn hint(&self) -> String {
+        let key = match self {
+            Self::Auth { .. } => "api_hint_auth",
+            Self::RateLimit { .. } => "api_hint_rate_limit",
+            Self::ContextOverflow { .. } => "api_hint_context_overflow",
+            Self::Server { .. } => "api_hint_server",
+            Self::InvalidRequest { .. } => "api_hint_invalid_request",
+            Self::Network { .. } => "api_hint_network",
+            Self::MalformedStream { .. } => "api_hint_malformed_stream",
+            Self::NoVision { .. } => "api_hint_no_vision",
+        };
+        get_i18n().get(key)
+    }
+
+    fn title(&self) -> String {
+        let key = match self {
+            Self::Auth { .. } => "api_err_auth",
+            Self::RateLimit { .. } => "api_err_rate_limit",
+            Self::ContextOverflow { .. } => "api_err_context_overflow",
+            Self::Server { .. } => "api_err_server",
+            Self::InvalidRequest { .. } => "api_err_invalid_request",
+            Self::Network { .. } => "api_err_network",
+            Self::MalformedStream { .. } => "api_err_malformed_stream",
+            Self::NoVision { .. } => "api_err_no_vision",
+        };
+        get_i18n().get(key)
+    }
+
+    fn status(&self) -> Option<u16> {
+        match self {
+            Self::Auth { status, .. }
+            | Self::Server { status, .. }
+            | Self::InvalidRequest { status, .. } => *status,
+            Self::RateLimit { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Self::Auth { message, .. }
+            | Self::RateLimit { message, .. }
+            | Self::ContextOverflow { message }
+            | Self::Server { message, .. }
+            | Self::InvalidRequest { message, .. }
+            | Self::Network { message }
+            | Self::MalformedStream { message } => message,
+            Self::NoVision { model } => model,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status() {
+            Some(status) => write!(f, "{} (HTTP {}): {}", self.title(), status, self.message()),
+            None => write!(f, "{}: {}", self.title(), self.message()),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+fn is_context_overflow(text: &str) -> bool {
+    let text = text.to_lowercase();
+    CONTEXT_OVERFLOW_MARKERS.iter().any(|m| text.contains(m))
+}
+
+/// Human readable message out of an error response body
+fn error_message(body: &str) -> String {
+    if let Ok(value) = serde_json::from_str::<Value>(body) {
+        let message = value["error"]["message"]
+            .as_str()
+            .or_else(|| value["error"].as_str())
+            .or_else(|| value["message"].as_str());
+        if let Some(message) = message {
+            return message.to_string();
+        }
+    }
+    let body = body.trim();
+    if body.chars().count() > 500 {
+        format!("{}...", body.chars().take(500).collect::<String>())
+    } else {
+        body.to_string()
+    }
+}
+
+/// Parse `retry-after-ms` / `Retry-After` (seconds or an HTTP date)
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(ms) = header("retry-after-ms").and_then(|v| v.trim().parse::<f64>().ok()) {
+        return (ms >= 0.0).then(|| Duration::from_millis(ms as u64));
+    }
+
+    let value = header("retry-after")?.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return (secs >= 0.0).then(|| Duration::from_millis((secs * 1000.0) as u64));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Delay before retry number `attempt` (starting at 1).
+/// Honors the server's `Retry-After`, otherwise exponential backoff with jitter;
+/// `jitter` is a random value in `[0, 1)`.
+pub fn backoff_delay(
+    attempt: u32,
+    base_delay_ms: u64,
+    retry_after: Option<Duration>,
+    jitter: f64,
+) -> Duration {
+    if let Some(wait) = retry_after {
+        // Small spread so parallel clients don't come back at the same instant
+        return wait.min(MAX_RETRY_AFTER) + Duration::from_millis((jitter * 250.0) as u64);
+    }
+    let exp = base_delay_ms
+        .saturating_mul(1u64 << attempt.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF.as_millis() as u64);
+    // Equal jitter: half fixed, half random
+    Duration::from_millis(exp / 2 + (exp as f64 / 2.0 * jitter) as u64)
+}
+
+/// Random value in `[0, 1)` for backoff jitter
+pub fn jitter() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_from_status_classifies() {
+        assert!(matches!(
+            ApiError::from_status(401, None, r#"{"error":{"message":"Invalid API key"}}"#),
+            ApiError::Auth { status: Some(401), ref message } if message == "Invalid API key"
+        ));
+        assert!(matches!(
+            ApiError::from_status(
+                400,
+                None,
+                r#"{"error":{"message":"This model's maximum context length is 8192 tokens","code":"context_length_exceeded"}}"#
+            ),
+            ApiError::ContextOverflow { .. }
+        ));
+        assert!(matches!(
+            ApiError::from_status(400, None, "bad tool schema"),
+            ApiError::InvalidRequest { .. }
+        ));
+
+        let limited = ApiError::from_status(429, Some(Duration::from_secs(3)), "slow down");
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(3)));
+        assert!(ApiError::from_status(503, None, "").is_retryable());
+        assert!(!ApiError::from_status(403, None, "").is_retryable());
+    }
+
+    #[test]
+    fn test_from_stream_error() {
+        let overloaded = StreamError::new("overloaded_error", "Overloaded");
+        assert!(ApiError::from_stream_error(&overloaded).is_retryable());
+        let too_long =
+            StreamError::new("invalid_request_error", "prompt is too long: 210000 tokens");
+        assert!(matches!(
+            ApiError::from_stream_error(&too_long),
+            ApiError::ContextOverflow { .. }
+        ));
+        let wrapped = anyhow::Error::from(StreamError::new("rate_limit_error", "slow down"));
+        assert!(matches!(
+            ApiError::classify(&wrapped),
+            ApiError::RateLimit { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("7"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("1500"));
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(Duration::from_millis(1500))
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(
+            backoff_delay(1, 300, Some(Duration::from_secs(2)), 0.0),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            backoff_delay(1, 300, Some(Duration::from_secs(3600)), 0.0),
+            MAX_RETRY_AFTER
+        );
+        assert_eq!(backoff_delay(3, 300, None, 0.0), Duration::from_millis(600));
+        assert_eq!(
+            backoff_delay(3, 300, None, 0.999),
+            Duration::from_millis(1199)
+        );
+        assert!(backoff_delay(30, 300, None, 0.999) <= MAX_BACKOFF);
+        assert!((0.0..1.0).contains(&jitter()));
+    }
+}
@@ -1,74 +1,161 @@
 use std::path::Path;
 
-use history::{Message, ToolCall};
+use history::{Message, MessageContent, ToolCall};
 use tools;
 use ui::get_i18n;
 use ui::ToolCallDisplay;
 
-/// Execute tool calls and collect results
+/// Execute tool calls and collect results (in the model's order).
+/// Consecutive read-only calls that never ask the user are awaited together, so their waits
+/// (network, MCP servers, blocking searches) overlap; plain file reads still finish one
+/// after another. Everything else runs alone, so prompts and live output never interleave.
 pub async fn execute_tool_calls(
     tool_calls: &[ToolCall],
     working_dir: &Path,
     displays: &mut std::collections::HashMap<String, ToolCallDisplay>,
     require_approval: bool,
 ) -> Vec<Message> {
+    let valid_calls: Vec<&ToolCall> = tool_calls.iter().filter(|tc| is_valid_call(tc)).collect();
     let mut results = Vec::new();
+    let mut images = Vec::new();
 
-    for tc in tool_calls {
-        // Skip invalid tool calls
-        if tc.id.is_empty() || tc.function.name.is_empty() {
-            let i18n = get_i18n();
-            eprintln!(
-                "\x1b[33m[!] {}:\x1b[0m {} id={}, name={}",
-                i18n.get("warning"),
-                i18n.get("api_skip_invalid_tool_call"),
-                tc.id,
-                tc.function.name
-            );
-            continue;
-        }
-
-        // Validate JSON arguments before execution
-        if serde_json::from_str::<serde_json::Value>(&tc.function.arguments).is_err() {
-            let i18n = get_i18n();
-            eprintln!(
-                "\x1b[33m[!] {}:\x1b[0m {} {}",
-                i18n.get("warning"),
-                i18n.get("api_skip_invalid_json_args"),
-                tc.function.name
-            );
-            continue;
-        }
-
-        let tool_result = tools::execute_tool(
-            &tc.function.name,
-            &tc.function.arguments,
-            working_dir,
-            require_approval,
+    for batch in batch_calls(&valid_calls, require_approval) {
+        let outcomes = futures::future::join_all(
+            batch
+                .iter()
+                .map(|tc| run_tool_call(tc, working_dir, require_approval)),
         )
-        .await
-        .unwrap_or_else(|e| {
-            let i18n = get_i18n();
-            let tmpl = i18n.get("api_tool_execution_error");
-            let msg = tmpl.replace("{}", &e.to_string());
-            tools::ToolResult::error(msg)
-        });
+        .await;
+
+        for (tc, tool_result) in batch.iter().zip(outcomes) {
+            // Update UI display
+            if let Some(display) = displays.get_mut(&tc.id) {
+                display.finish(tool_result.success, Some(tool_result.brief.clone()));
+                println!();
+                display.render_final();
+            }
 
-        // Update UI display
-        if let Some(display) = displays.get_mut(&tc.id) {
-            display.finish(tool_result.success, Some(tool_result.brief.clone()));
-            println!();
-            display.render_final();
+            images.extend(tool_result.images);
+            results.push(Message {
+                role: "tool".to_string(),
+                content: tool_result.message.into(),
+                tool_calls: None,
+                tool_call_id: Some(tc.id.clone()),
+                name: Some(tc.function.name.clone()),
+                usage: None,
+                model: None,
+                thinking_blocks: Vec::new(),
+            });
         }
+    }
 
+    // Tool messages are text-only on most providers, so images follow as a user message
+    if !images.is_empty() {
         results.push(Message {
-            role: "tool".to_string(),
-            content: tool_result.message,
+            role: "user".to_string(),
+            content: MessageContent::with_images(get_i18n().get("tool_images_attached"), images),
             tool_calls: None,
-            tool_call_id: Some(tc.id.clone()),
-            name: Some(tc.function.name.clone()),
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
         });
     }
 
     results
 }
+
+/// Split calls into batches: runs of calls that can share a batch, every other call alone
+fn batch_calls<'a>(calls: &[&'a ToolCall], require_approval: bool) -> Vec<Vec<&'a ToolCall>> {
+    let concurrent =
+        |tc: &ToolCall| tools::can_run_concurrently(&tc.function.name, require_approval);
+    let mut batches: Vec<Vec<&ToolCall>> = Vec::new();
+    for tc in calls {
+        match batches.last_mut() {
+            Some(batch) if concurrent(tc) && concurrent(batch[0]) => batch.push(tc),
+            _ => batches.push(vec![tc]),
+        }
+    }
+    batches
+}
+
+/// Skip invalid tool calls (missing id/name or malformed JSON arguments)
+fn is_valid_call(tc: &ToolCall) -> bool {
+    if tc.id.is_empty() || tc.function.name.is_empty() {
+        let i18n = get_i18n();
+        eprintln!(
+            "\x1b[33m[!] {}:\x1b[0m {} id={}, name={}",
+            i18n.get("warning"),
+            i18n.get("api_skip_invalid_tool_call"),
+            tc.id,
+            tc.function.name
+        );
+        return false;
+    }
+
+    // Validate JSON arguments before execution
+    if serde_json::from_str::<serde_json::Value>(&tc.function.arguments).is_err() {
+        let i18n = get_i18n();
+        eprintln!(
+            "\x1b[33m[!] {}:\x1b[0m {} {}",
+            i18n.get("warning"),
+            i18n.get("api_skip_invalid_json_args"),
+            tc.function.name
+        );
+        return false;
+    }
+
+    true
+}
+
+async fn run_tool_call(
+    tc: &ToolCall,
+    working_dir: &Path,
+    require_approval: bool,
+) -> tools::ToolResult {
+    tools::execute_tool(
+        &tc.function.name,
+        &tc.function.arguments,
+        working_dir,
+        require_approval,
+    )
+    .await
+    .unwrap_or_else(|e| {
+        let i18n = get_i18n();
+        let tmpl = i18n.get("api_tool_execution_error");
+        let msg = tmpl.replace("{}", &e.to_string());
+        tools::ToolResult::error(msg)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str) -> ToolCall {
+        ToolCall {
+            id: format!("call_{}", name),
+            tool_type: "function".to_string(),
+            function: history::FunctionCall {
+                name: name.to_string(),
+                arguments: "{}".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_batch_calls_groups_consecutive_reads() {
+        let calls = [
+            call("file_read"),
+            call("file_list"),
+            call("file_write"),
+            call("file_read"),
+            call("run_command"),
+            call("run_command"),
+        ];
+        let refs: Vec<&ToolCall> = calls.iter().collect();
+        let sizes: Vec<usize> = batch_calls(&refs, true).iter().map(|b| b.len()).collect();
+        assert_eq!(sizes, vec![2, 1, 1, 1, 1]);
+    }
+}
@@ -1,11 +1,20 @@
 mod accumulator;
 mod client;
+mod error;
 mod executor;
 mod parser;
+mod providers;
+mod recorder;
+mod side_usage;
+mod sse;
 mod stream;
 mod types;
 
 pub use accumulator::ToolCallAccumulator;
 pub use client::ApiClient;
+pub use error::{ApiError, StreamError};
 pub use executor::execute_tool_calls;
+pub use providers::{create_provider, Provider, StreamDecoder, StreamFormat};
+pub use side_usage::{record_side_usage, take_side_usage};
+pub use sse::{SseEvent, SseEventParser};
 pub use types::StreamChunk;
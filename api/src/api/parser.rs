@@ -2,89 +2,72 @@ use anyhow::Result;
 
 use super::types::{ChatResponse, StreamChunk};
 
-/// Parse a single SSE line (complete data)
-pub fn parse_sse_line(line: &str) -> Option<Result<StreamChunk>> {
-    let trimmed = line.trim();
-
-    if trimmed.is_empty() || !trimmed.starts_with("data: ") {
-        return None;
-    }
-
-    let data = &trimmed[6..];
-    parse_sse_message(data)
-}
-
-/// Parse SSE message data
-pub fn parse_sse_message(data: &str) -> Option<Result<StreamChunk>> {
+/// Parse SSE message data into every chunk it carries
+/// (reasoning, content, each parallel tool call, then the finish reason)
+pub fn parse_sse_message(data: &str) -> Vec<Result<StreamChunk>> {
     let data = data.trim();
 
     if data.is_empty() {
-        return None;
+        return Vec::new();
     }
 
     if data == "[DONE]" {
-        return Some(Ok(StreamChunk::Done));
+        return vec![Ok(StreamChunk::Done)];
     }
 
-    match serde_json::from_str::<ChatResponse>(data) {
-        Ok(response) => {
-            if let Some(choice) = response.choices.first() {
-                // Check finish_reason, emit as separate event
-                if let Some(reason) = &choice.finish_reason {
-                    if reason == "stop" || reason == "length" || reason == "tool_calls" {
-                        return Some(Ok(StreamChunk::FinishReason(reason.clone())));
-                    }
-                }
-
-                if let Some(delta) = &choice.delta {
-                    // Handle tool_calls (highest priority)
-                    if let Some(tool_calls) = &delta.tool_calls {
-                        for tc in tool_calls {
-                            let id = tc.id.as_deref().unwrap_or("");
-                            let name = tc
-                                .function
-                                .as_ref()
-                                .and_then(|f| f.name.as_deref())
-                                .unwrap_or("");
-                            let args = tc
-                                .function
-                                .as_ref()
-                                .and_then(|f| f.arguments.as_deref())
-                                .unwrap_or("");
-
-                            // Return if any content
-                            if !id.is_empty() || !name.is_empty() || !args.is_empty() {
-                                return Some(Ok(StreamChunk::ToolCall {
-                                    id: id.to_string(),
-                                    name: name.to_string(),
-                                    arguments: args.to_string(),
-                                }));
-                            }
-                        }
-                    }
-
-                    // Handle content (actual response)
-                    if let Some(content) = &delta.content {
-                        if !content.is_empty() {
-                            return Some(Ok(StreamChunk::Content(content.clone())));
-                        }
-                    }
-
-                    // Handle reasoning_content (thinking process)
-                    if let Some(reasoning) = &delta.reasoning_content {
-                        if !reasoning.is_empty() {
-                            return Some(Ok(StreamChunk::Reasoning(reasoning.clone())));
-                        }
-                    }
-                }
-            }
+    // Events are complete at this point, so a parse failure means a payload we don't know
+    let Ok(response) = serde_json::from_str::<ChatResponse>(data) else {
+        return Vec::new();
+    };
+
+    let mut chunks = Vec::new();
+    let Some(choice) = response.choices.first() else {
+        return chunks;
+    };
+
+    if let Some(delta) = &choice.delta {
+        // Handle reasoning_content (thinking process)
+        if let Some(reasoning) = delta.reasoning_content.as_ref().filter(|r| !r.is_empty()) {
+            chunks.push(Ok(StreamChunk::Reasoning(reasoning.clone())));
         }
-        Err(_e) => {
-            // SseLineStream correctly handles stream splitting
-            // Errors here are mostly JSON structure errors, can be safely ignored
+
+        // Handle content (actual response)
+        if let Some(content) = delta.content.as_ref().filter(|c| !c.is_empty()) {
+            chunks.push(Ok(StreamChunk::Content(content.clone())));
+        }
+
+        // Handle tool_calls, a single delta may carry several parallel calls
+        for tc in delta.tool_calls.iter().flatten() {
+            let id = tc.id.as_deref().unwrap_or("");
+            let name = tc
+                .function
+                .as_ref()
+                .and_then(|f| f.name.as_deref())
+                .unwrap_or("");
+            let args = tc
+                .function
+                .as_ref()
+                .and_then(|f| f.arguments.as_deref())
+                .unwrap_or("");
+
+            if !id.is_empty() || !name.is_empty() || !args.is_empty() {
+                chunks.push(Ok(StreamChunk::ToolCall {
+                    index: tc.index,
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    arguments: args.to_string(),
+                }));
+            }
         }
     }
-    None
+
+    // Finish reason comes last so the delta above is not lost. Any reason ends the response
+    // (servers also send content_filter, function_call, eos and others)
+    if let Some(reason) = choice.finish_reason.as_ref().filter(|r| !r.is_empty()) {
+        chunks.push(Ok(StreamChunk::FinishReason(reason.clone())));
+    }
+
+    chunks
 }
 
 /// Check if JSON structure is complete (brackets and quotes paired)
@@ -126,56 +109,28 @@ pub fn is_json_semantically_complete(tool_name: &str, arguments: &str) -> bool {
         return false;
     };
 
-    let Some(obj) = json.as_object() else {
-        return false;
-    };
-
-    // Check required parameters based on tool type
-    match tool_name {
-        "file_read" | "file_list" => {
-            // path parameter required, must not be empty
-            obj.get("path")
-                .and_then(|v| v.as_str())
-                .map(|s| !s.is_empty())
-                .unwrap_or(true) // file_list path is optional
-        }
-        "file_write" => {
-            // path parameter required
-            let has_path = obj
-                .get("path")
-                .and_then(|v| v.as_str())
-                .map(|s| !s.is_empty())
-                .unwrap_or(false);
-
-            if !has_path {
-                return false;
-            }
+    // Required parameters are declared by each tool
+    tools::is_arguments_complete(tool_name, &json)
+}
 
-            // content parameter required
-            obj.get("content")
-                .and_then(|v| v.as_str())
-                .map(|_| true)
-                .unwrap_or(false)
-        }
-        "file_replace" => {
-            // path and edits parameters required
-            let has_path = obj
-                .get("path")
-                .and_then(|v| v.as_str())
-                .map(|s| !s.is_empty())
-                .unwrap_or(false);
-
-            let has_edits = obj
-                .get("edits")
-                .and_then(|v| v.as_array())
-                .map(|arr| !arr.is_empty())
-                .unwrap_or(false);
-
-            has_path && has_edits
-        }
-        _ => {
-            // Other tools only need complete JSON structure
-            true
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sse_message_emits_every_parallel_tool_call() {
+        let data = r#"{"choices":[{"delta":{"tool_calls":[
+            {"index":0,"id":"call_a","type":"function","function":{"name":"file_read","arguments":""}},
+            {"index":1,"id":"call_b","type":"function","function":{"name":"file_list","arguments":"{}"}}
+        ]},"finish_reason":null}]}"#;
+
+        let chunks: Vec<StreamChunk> = parse_sse_message(data)
+            .into_iter()
+            .map(|c| c.unwrap())
+            .collect();
+
+        assert_eq!(chunks.len(), 2);
+        assert!(matches!(&chunks[0], StreamChunk::ToolCall { index: 0, id, .. } if id == "call_a"));
+        assert!(matches!(&chunks[1], StreamChunk::ToolCall { index: 1, id, .. } if id == "call_b"));
     }
 }
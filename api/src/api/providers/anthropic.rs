@@ -0,0 +1,578 @@
+use anyhow::Result;
+use config::ModelProfile;
+use history::{Message, Usage};
+use reqwest::RequestBuilder;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::{
+    arguments_value, assistant_text, merge_extra, set_field, token_count, Provider, StreamDecoder,
+};
+use crate::api::sse::SseEvent;
+use crate::api::types::{ChatRequest, StreamChunk};
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// The Messages API requires max_tokens on every request
+const DEFAULT_MAX_TOKENS: u32 = 8192;
+
+/// Native Anthropic Messages API backend (`/messages`)
+pub struct AnthropicProvider;
+
+impl Provider for AnthropicProvider {
+    fn chat_url(&self, api_url: &str) -> String {
+        format!("{}/messages", api_url)
+    }
+
+    fn models_url(&self, api_url: &str) -> String {
+        format!("{}/models", api_url)
+    }
+
+    fn authorize(&self, request: RequestBuilder, api_key: &str) -> RequestBuilder {
+        request
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+    }
+
+    fn build_body(&self, request: &ChatRequest) -> Value {
+        let (system, messages) = convert_messages(&request.messages);
+
+        let profile = &request.profile;
+        let thinking = thinking_budget(profile);
+        let mut max_tokens = request.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+        // max_tokens includes the thinking budget and must exceed it; raise it so the
+        // answer keeps the room it was given
+        if let Some(budget) = thinking.filter(|b| *b >= max_tokens) {
+            max_tokens += budget;
+        }
+        let mut body = json!({
+            "model": request.model,
+            "messages": messages,
+            "max_tokens": max_tokens,
+            "stream": request.stream,
+        });
+
+        match thinking {
+            Some(budget) => {
+                // Thinking requests are rejected with a custom temperature or top_p
+                body["thinking"] = json!({ "type": "enabled", "budget_tokens": budget });
+            }
+            None => {
+                set_field(&mut body, "temperature", profile.temperature);
+                set_field(&mut body, "top_p", profile.top_p);
+            }
+        }
+        if !profile.stop.is_empty() {
+            body["stop_sequences"] = json!(profile.stop);
+        }
+
+        if !system.is_empty() {
+            body["system"] = json!(system);
+        }
+
+        if !request.tools.is_empty() {
+            let tools: Vec<Value> = request
+                .tools
+                .iter()
+                .map(|t| {
+                    json!({
+                        "name": t.function.name,
+                        "description": t.function.description,
+                        "input_schema": t.function.parameters,
+                    })
+                })
+                .collect();
+            body["tools"] = json!(tools);
+        }
+
+        merge_extra(&mut body, profile);
+        body
+    }
+
+    fn stream_decoder(&self) -> Box<dyn StreamDecoder> {
+        Box::new(AnthropicDecoder::default())
+    }
+
+    fn parse_response(&self, body: &Value) -> Result<Message> {
+        let content = body["content"]
+            .as_array()
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter(|b| b["type"] == "text")
+                    .filter_map(|b| b["text"].as_str())
+                    .collect::<Vec<_>>()
+                    .join("")
+            })
+            .unwrap_or_default();
+        let mut message = assistant_text(content);
+        message.thinking_blocks = body["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|b| is_thinking_block(b))
+            .cloned()
+            .collect();
+        if body["usage"].is_object() {
+            message.usage = Some(Usage::new(
+                input_tokens(&body["usage"]),
+                token_count(&body["usage"]["output_tokens"]),
+            ));
+        }
+        Ok(message)
+    }
+}
+
+/// Split out system prompts and convert history into Messages API content blocks
+fn convert_messages(messages: &[Message]) -> (String, Vec<Value>) {
+    let mut system = Vec::new();
+    let mut converted: Vec<Value> = Vec::new();
+
+    for msg in messages {
+        match msg.role.as_str() {
+            "system" => system.push(msg.content.text.clone()),
+            "assistant" => {
+                let mut blocks = Vec::new();
+                if !msg.content.is_empty() {
+                    blocks.push(json!({ "type": "text", "text": msg.content.text }));
+                }
+                for tc in msg.tool_calls.iter().flatten() {
+                    blocks.push(json!({
+                        "type": "tool_use",
+                        "id": tc.id,
+                        "name": tc.function.name,
+                        "input": arguments_value(&tc.function.arguments),
+                    }));
+                }
+                if !blocks.is_empty() {
+                    // A turn that continues after tool results must start with its signed
+                    // thinking, so it goes back in front of the text and tool_use blocks
+                    let blocks: Vec<Value> =
+                        msg.thinking_blocks.iter().cloned().chain(blocks).collect();
+                    converted.push(json!({ "role": "assistant", "content": blocks }));
+                }
+            }
+            "tool" => {
+                let block = json!({
+                    "type": "tool_result",
+                    "tool_use_id": msg.tool_call_id.clone().unwrap_or_default(),
+                    "content": msg.content.text,
+                });
+                // Consecutive tool results belong to the same user turn
+                match converted.last_mut() {
+                    Some(last)
+                        if last["role"] == "user"
+                            && last["content"][0]["type"] == "tool_result" =>
+                    {
+                        if let Some(blocks) = last["content"].as_array_mut() {
+                            blocks.push(block);
+                        }
+                    }
+                    _ => converted.push(json!({ "role": "user", "content": [block] })),
+                }
+            }
+            _ => match converted.last_mut() {
+                // Images returned by tools follow their results within the same user turn
+                Some(last) if last["role"] == "user" => {
+                    if let Some(blocks) = last["content"].as_array_mut() {
+                        blocks.extend(user_blocks(msg));
+                    }
+                }
+                _ => converted.push(json!({ "role": "user", "content": user_blocks(msg) })),
+            },
+        }
+    }
+
+    (system.join("\n\n"), converted)
+}
+
+/// Text and image blocks of a user message
+fn user_blocks(msg: &Message) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    for image in &msg.content.images {
+        let source = match image.data_parts() {
+            Some((media_type, data)) => {
+                json!({ "type": "base64", "media_type": media_type, "data": data })
+            }
+            None => json!({ "type": "url", "url": image.url }),
+        };
+        blocks.push(json!({ "type": "image", "source": source }));
+    }
+    if !msg.content.is_empty() || blocks.is_empty() {
+        blocks.push(json!({ "type": "text", "text": msg.content.text }));
+    }
+    blocks
+}
+
+/// Prompt tokens including cache reads and writes
+fn input_tokens(usage: &Value) -> u64 {
+    token_count(&usage["input_tokens"])
+        + token_count(&usage["cache_read_input_tokens"])
+        + token_count(&usage["cache_creation_input_tokens"])
+}
+
+/// Map Anthropic stop reasons onto OpenAI-style finish reasons
+fn map_stop_reason(reason: &str) -> String {
+    match reason {
+        "tool_use" => "tool_calls",
+        "max_tokens" => "length",
+        _ => "stop",
+    }
+    .to_string()
+}
+
+/// Thinking budget of a profile: `thinking_budget`, else one derived from `reasoning_effort`
+fn thinking_budget(profile: &ModelProfile) -> Option<u32> {
+    profile
+        .thinking_budget
+        .or_else(|| match profile.reasoning_effort.as_deref()? {
+            "minimal" | "low" => Some(1024),
+            "medium" => Some(4096),
+            "high" => Some(16384),
+            _ => None,
+        })
+}
+
+fn is_thinking_block(block: &Value) -> bool {
+    block["type"] == "thinking" || block["type"] == "redacted_thinking"
+}
+
+/// Tool-use block being streamed
+struct ToolBlock {
+    id: String,
+    has_arguments: bool,
+}
+
+#[derive(Default)]
+struct AnthropicDecoder {
+    tool_blocks: HashMap<u64, ToolBlock>,
+    /// Thinking blocks being streamed, completed by their deltas and signature
+    thinking_blocks: HashMap<u64, Value>,
+    /// Prompt tokens from message_start, reported together with the output tokens
+    input_tokens: u64,
+}
+
+impl StreamDecoder for AnthropicDecoder {
+    fn decode(&mut self, event: &SseEvent) -> Vec<Result<StreamChunk>> {
+        let Ok(event) = serde_json::from_str::<Value>(&event.data) else {
+            return Vec::new();
+        };
+        let index = event["index"].as_u64().unwrap_or(0);
+
+        match event["type"].as_str().unwrap_or("") {
+            "message_start" => {
+                self.input_tokens = input_tokens(&event["message"]["usage"]);
+                Vec::new()
+            }
+            "content_block_start" => {
+                let block = &event["content_block"];
+                if is_thinking_block(block) {
+                    self.thinking_blocks.insert(index, block.clone());
+                    return Vec::new();
+                }
+                if block["type"] == "tool_use" {
+                    let id = block["id"].as_str().unwrap_or("").to_string();
+                    let name = block["name"].as_str().unwrap_or("").to_string();
+                    self.tool_blocks.insert(
+                        index,
+                        ToolBlock {
+                            id: id.clone(),
+                            has_arguments: false,
+                        },
+                    );
+                    return vec![Ok(StreamChunk::ToolCall {
+                        index: index as usize,
+                        id,
+                        name,
+                        arguments: String::new(),
+                    })];
+                }
+                Vec::new()
+            }
+            "content_block_delta" => {
+                let delta = &event["delta"];
+                match delta["type"].as_str().unwrap_or("") {
+                    "text_delta" => text_chunk(delta["text"].as_str(), StreamChunk::Content),
+                    "thinking_delta" => {
+                        if let Some(block) = self.thinking_blocks.get_mut(&index) {
+                            append_text(block, "thinking", &delta["thinking"]);
+                        }
+                        text_chunk(delta["thinking"].as_str(), StreamChunk::Reasoning)
+                    }
+                    "signature_delta" => {
+                        if let Some(block) = self.thinking_blocks.get_mut(&index) {
+                            append_text(block, "signature", &delta["signature"]);
+                        }
+                        Vec::new()
+                    }
+                    "input_json_delta" => {
+                        let partial = delta["partial_json"].as_str().unwrap_or("");
+                        match self.tool_blocks.get_mut(&index) {
+                            Some(block) if !partial.is_empty() => {
+                                block.has_arguments = true;
+                                vec![Ok(StreamChunk::ToolCall {
+                                    index: index as usize,
+                                    id: block.id.clone(),
+                                    name: String::new(),
+                                    arguments: partial.to_string(),
+                                })]
+                            }
+                            _ => Vec::new(),
+                        }
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            "content_block_stop" => {
+                if let Some(block) = self.thinking_blocks.remove(&index) {
+                    return vec![Ok(StreamChunk::ThinkingBlock(block))];
+                }
+                match self.tool_blocks.remove(&index) {
+                    // Tools without parameters stream no input at all
+                    Some(block) if !block.has_arguments => vec![Ok(StreamChunk::ToolCall {
+                        index: index as usize,
+                        id: block.id,
+                        name: String::new(),
+                        arguments: "{}".to_string(),
+                    })],
+                    _ => Vec::new(),
+                }
+            }
+            "message_delta" => {
+                let mut chunks = Vec::new();
+                if event["usage"].is_object() {
+                    chunks.push(Ok(StreamChunk::Usage(Usage::new(
+                        self.input_tokens,
+                        token_count(&event["usage"]["output_tokens"]),
+                    ))));
+                }
+                if let Some(reason) = event["delta"]["stop_reason"].as_str() {
+                    chunks.push(Ok(StreamChunk::FinishReason(map_stop_reason(reason))));
+                }
+                chunks
+            }
+            "message_stop" => vec![Ok(StreamChunk::Done)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Append a streamed text delta to a string field of a block
+fn append_text(block: &mut Value, field: &str, delta: &Value) {
+    let text = format!(
+        "{}{}",
+        block[field].as_str().unwrap_or(""),
+        delta.as_str().unwrap_or("")
+    );
+    block[field] = json!(text);
+}
+
+fn text_chunk(text: Option<&str>, wrap: fn(String) -> StreamChunk) -> Vec<Result<StreamChunk>> {
+    match text {
+        Some(t) if !t.is_empty() => vec![Ok(wrap(t.to_string()))],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use history::{FunctionCall, ToolCall};
+
+    fn msg(role: &str, content: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: content.into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_convert_messages_groups_tool_results() {
+        let mut assistant = msg("assistant", "");
+        assistant.tool_calls = Some(vec![
+            ToolCall {
+                id: "toolu_1".to_string(),
+                tool_type: "function".to_string(),
+                function: FunctionCall {
+                    name: "file_read".to_string(),
+                    arguments: r#"{"path":"a.rs"}"#.to_string(),
+                },
+            },
+            ToolCall {
+                id: "toolu_2".to_string(),
+                tool_type: "function".to_string(),
+                function: FunctionCall {
+                    name: "file_read".to_string(),
+                    arguments: r#"{"path":"b.rs"}"#.to_string(),
+                },
+            },
+        ]);
+        let mut result_a = msg("tool", "A");
+        result_a.tool_call_id = Some("toolu_1".to_string());
+        let mut result_b = msg("tool", "B");
+        result_b.tool_call_id = Some("toolu_2".to_string());
+
+        let (system, converted) = convert_messages(&[
+            msg("system", "be nice"),
+            msg("user", "read both"),
+            assistant,
+            result_a,
+            result_b,
+        ]);
+
+        assert_eq!(system, "be nice");
+        assert_eq!(converted.len(), 3);
+        assert_eq!(converted[1]["content"][0]["type"], "tool_use");
+        assert_eq!(converted[1]["content"][0]["input"]["path"], "a.rs");
+        assert_eq!(converted[2]["role"], "user");
+        assert_eq!(converted[2]["content"].as_array().unwrap().len(), 2);
+        assert_eq!(converted[2]["content"][1]["tool_use_id"], "toolu_2");
+    }
+
+    fn request(max_tokens: Option<u32>, profile: ModelProfile) -> ChatRequest {
+        ChatRequest {
+            model: "claude-sonnet-4".to_string(),
+            messages: vec![msg("user", "hi")],
+            tools: vec![],
+            stream: true,
+            max_tokens,
+            stream_options: None,
+            profile,
+        }
+    }
+
+    #[test]
+    fn test_build_body_thinking() {
+        let sampling = ModelProfile {
+            temperature: Some(0.2),
+            top_p: Some(0.9),
+            ..Default::default()
+        };
+        let body = AnthropicProvider.build_body(&request(None, sampling.clone()));
+        assert_eq!(body["temperature"], json!(0.2f32));
+        assert!(body.get("thinking").is_none());
+
+        // Thinking drops sampling parameters and raises max_tokens above the budget
+        let thinking = ModelProfile {
+            thinking_budget: Some(2000),
+            ..sampling.clone()
+        };
+        let body = AnthropicProvider.build_body(&request(Some(1000), thinking));
+        assert_eq!(body["thinking"]["budget_tokens"], 2000);
+        assert_eq!(body["max_tokens"], 3000);
+        assert!(body.get("temperature").is_none());
+        assert!(body.get("top_p").is_none());
+
+        // A budget below max_tokens leaves it alone
+        let effort = ModelProfile {
+            reasoning_effort: Some("medium".to_string()),
+            ..Default::default()
+        };
+        let body = AnthropicProvider.build_body(&request(None, effort));
+        assert_eq!(body["thinking"]["budget_tokens"], 4096);
+        assert_eq!(body["max_tokens"], DEFAULT_MAX_TOKENS);
+    }
+
+    #[test]
+    fn test_thinking_round_trip_with_tool_call() {
+        let mut decoder = AnthropicDecoder::default();
+        let events = [
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"read "}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"it"}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"sig=="}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"redacted_thinking","data":"opaque"}}"#,
+            r#"{"type":"content_block_stop","index":1}"#,
+            r#"{"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_1","name":"file_read","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"{\"path\":\"a.rs\"}"}}"#,
+            r#"{"type":"content_block_stop","index":2}"#,
+        ];
+        let mut assistant = msg("assistant", "");
+        let mut calls = Vec::new();
+        for event in events {
+            for chunk in decoder.decode(&SseEvent::data_only(event)) {
+                match chunk.unwrap() {
+                    StreamChunk::ThinkingBlock(block) => assistant.thinking_blocks.push(block),
+                    StreamChunk::ToolCall { id, .. } if !id.is_empty() && calls.is_empty() => {
+                        calls.push(ToolCall {
+                            id,
+                            tool_type: "function".to_string(),
+                            function: FunctionCall {
+                                name: "file_read".to_string(),
+                                arguments: r#"{"path":"a.rs"}"#.to_string(),
+                            },
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        assistant.tool_calls = Some(calls);
+        let mut result = msg("tool", "A");
+        result.tool_call_id = Some("toolu_1".to_string());
+
+        // The next request replays the signed blocks before the tool_use block
+        let (_, converted) = convert_messages(&[msg("user", "read a.rs"), assistant, result]);
+        let blocks = converted[1]["content"].as_array().unwrap();
+        assert_eq!(
+            blocks[0],
+            json!({ "type": "thinking", "thinking": "read it", "signature": "sig==" })
+        );
+        assert_eq!(
+            blocks[1],
+            json!({ "type": "redacted_thinking", "data": "opaque" })
+        );
+        assert_eq!(blocks[2]["type"], "tool_use");
+        assert_eq!(blocks[2]["id"], "toolu_1");
+
+        let response = json!({ "content": [
+            { "type": "thinking", "thinking": "hm", "signature": "s" },
+            { "type": "text", "text": "done" }
+        ] });
+        let message = AnthropicProvider.parse_response(&response).unwrap();
+        assert_eq!(message.content.text, "done");
+        assert_eq!(message.thinking_blocks.len(), 1);
+    }
+
+    #[test]
+    fn test_decoder_maps_events() {
+        let mut decoder = AnthropicDecoder::default();
+        let events = [
+            r#"{"type":"message_start","message":{"usage":{"input_tokens":10,"cache_read_input_tokens":5,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"hmm"}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"Hi"}}"#,
+            r#"{"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_9","name":"file_list","input":{}}}"#,
+            r#"{"type":"content_block_stop","index":2}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":42}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+
+        let chunks: Vec<StreamChunk> = events
+            .iter()
+            .flat_map(|e| decoder.decode(&SseEvent::data_only(e)))
+            .map(|c| c.unwrap())
+            .collect();
+
+        assert!(matches!(&chunks[0], StreamChunk::Reasoning(t) if t == "hmm"));
+        assert!(matches!(&chunks[1], StreamChunk::Content(t) if t == "Hi"));
+        assert!(
+            matches!(&chunks[2], StreamChunk::ToolCall { id, name, .. } if id == "toolu_9" && name == "file_list")
+        );
+        assert!(
+            matches!(&chunks[3], StreamChunk::ToolCall { id, arguments, .. } if id == "toolu_9" && arguments == "{}")
+        );
+        assert!(
+            matches!(&chunks[4], StreamChunk::Usage(u) if u.prompt_tokens == 15 && u.completion_tokens == 42)
+        );
+        assert!(matches!(&chunks[5], StreamChunk::FinishReason(r) if r == "tool_calls"));
+        assert!(matches!(chunks[6], StreamChunk::Done));
+    }
+}
@@ -0,0 +1,241 @@
+mod anthropic;
+mod ollama;
+mod openai;
+mod openai_responses;
+
+use anyhow::Result;
+use config::ModelProfile;
+use history::Message;
+use reqwest::RequestBuilder;
+
+use super::sse::SseEvent;
+use super::types::{ChatRequest, StreamChunk};
+
+pub use anthropic::AnthropicProvider;
+pub use ollama::OllamaProvider;
+pub use openai::OpenAiProvider;
+pub use openai_responses::OpenAiResponsesProvider;
+
+/// How a provider frames its streaming response body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// Server-Sent Events (`data: {...}` lines)
+    Sse,
+    /// Newline-delimited JSON (one object per line)
+    Ndjson,
+}
+
+/// A provider backend: maps the provider-neutral `ChatRequest` onto a wire protocol
+/// and maps the streamed response back into `StreamChunk`s.
+pub trait Provider: Send + Sync {
+    /// Chat endpoint URL
+    fn chat_url(&self, api_url: &str) -> String;
+
+    /// Models list endpoint URL
+    fn models_url(&self, api_url: &str) -> String;
+
+    /// Attach authentication headers
+    fn authorize(&self, request: RequestBuilder, api_key: &str) -> RequestBuilder;
+
+    /// Build the JSON request body
+    fn build_body(&self, request: &ChatRequest) -> serde_json::Value;
+
+    /// Framing of the streaming response
+    fn stream_format(&self) -> StreamFormat {
+        StreamFormat::Sse
+    }
+
+    /// Create a decoder for one streaming response
+    fn stream_decoder(&self) -> Box<dyn StreamDecoder>;
+
+    /// Parse a non-streaming response into an assistant message
+    fn parse_response(&self, body: &serde_json::Value) -> Result<Message>;
+
+    /// Parse the models list response
+    fn parse_models(&self, body: &serde_json::Value) -> Vec<String> {
+        body["data"]
+            .as_array()
+            .map(|models| {
+                models
+                    .iter()
+                    .filter_map(|m| m["id"].as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Stateful decoder for a single streaming response
+pub trait StreamDecoder: Send {
+    /// Decode one event (an SSE event, or one NDJSON line as a data-only event)
+    /// into every chunk it carries. Error payloads are handled before this is called.
+    fn decode(&mut self, event: &SseEvent) -> Vec<Result<StreamChunk>>;
+}
+
+/// Create the provider backend configured by name (falls back to OpenAI chat)
+pub fn create_provider(name: &str) -> Box<dyn Provider> {
+    match name {
+        "anthropic" => Box::new(AnthropicProvider),
+        "ollama" => Box::new(OllamaProvider),
+        "openai-responses" => Box::new(OpenAiResponsesProvider),
+        _ => Box::new(OpenAiProvider),
+    }
+}
+
+/// Build an assistant message with plain text content
+fn assistant_text(content: String) -> Message {
+    Message {
+        role: "assistant".to_string(),
+        content: content.into(),
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+        usage: None,
+        model: None,
+        thinking_blocks: Vec::new(),
+    }
+}
+
+/// Read a token count field, treating missing values as zero
+fn token_count(value: &serde_json::Value) -> u64 {
+    value.as_u64().unwrap_or(0)
+}
+
+/// Parse tool call arguments into a JSON value (providers that expect objects)
+fn arguments_value(arguments: &str) -> serde_json::Value {
+    serde_json::from_str(arguments).unwrap_or_else(|_| serde_json::json!({}))
+}
+
+/// Set a body field when the profile sets it
+fn set_field<T: serde::Serialize>(body: &mut serde_json::Value, key: &str, value: Option<T>) {
+    if let Some(value) = value {
+        body[key] = serde_json::json!(value);
+    }
+}
+
+/// Merge the profile's extra fields into the body; applied last so they override
+fn merge_extra(body: &mut serde_json::Value, profile: &ModelProfile) {
+    if let Some(object) = body.as_object_mut() {
+        for (key, value) in &profile.extra {
+            object.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(profile: ModelProfile) -> ChatRequest {
+        ChatRequest {
+            model: "m".to_string(),
+            messages: vec![assistant_text("hi".to_string())],
+            tools: vec![],
+            stream: true,
+            max_tokens: profile.max_tokens,
+            stream_options: None,
+            profile,
+        }
+    }
+
+    fn profile() -> ModelProfile {
+        let mut profile = ModelProfile {
+            temperature: Some(0.5),
+            top_p: Some(0.9),
+            max_tokens: Some(4096),
+            reasoning_effort: Some("high".to_string()),
+            thinking_budget: Some(2048),
+            stop: vec!["</end>".to_string()],
+            seed: Some(7),
+            ..Default::default()
+        };
+        profile.extra.insert("top_k".to_string(), json!(20));
+        profile
+    }
+
+    #[test]
+    fn test_profile_maps_onto_each_provider() {
+        let body = OpenAiProvider.build_body(&request(profile()));
+        assert_eq!(body["temperature"], 0.5);
+        assert_eq!(body["max_tokens"], 4096);
+        assert_eq!(body["reasoning_effort"], "high");
+        assert_eq!(body["stop"], json!(["</end>"]));
+        assert_eq!(body["seed"], 7);
+        assert_eq!(body["top_k"], 20);
+
+        let body = AnthropicProvider.build_body(&request(profile()));
+        assert_eq!(body["max_tokens"], 4096);
+        assert_eq!(body["stop_sequences"], json!(["</end>"]));
+        assert_eq!(body["thinking"]["budget_tokens"], 2048);
+        assert!(body.get("seed").is_none());
+
+        let body = OllamaProvider.build_body(&request(profile()));
+        assert_eq!(body["options"]["num_predict"], 4096);
+        assert_eq!(body["options"]["seed"], 7);
+        assert_eq!(body["think"], "high");
+
+        let body = OpenAiResponsesProvider.build_body(&request(profile()));
+        assert_eq!(body["max_output_tokens"], 4096);
+        assert_eq!(body["reasoning"]["effort"], "high");
+        assert_eq!(body["top_k"], 20);
+    }
+
+    #[test]
+    fn test_images_map_onto_each_provider() {
+        let image = history::ImageUrl {
+            url: "data:image/png;base64,iVBO".to_string(),
+            detail: None,
+        };
+        let mut request = request(ModelProfile::default());
+        request.messages = vec![Message {
+            role: "user".to_string(),
+            content: history::MessageContent::with_images("what is this?", vec![image]),
+            ..assistant_text(String::new())
+        }];
+
+        let body = OpenAiProvider.build_body(&request);
+        assert_eq!(body["messages"][0]["content"][0]["text"], "what is this?");
+        assert_eq!(
+            body["messages"][0]["content"][1]["image_url"]["url"],
+            "data:image/png;base64,iVBO"
+        );
+
+        let body = AnthropicProvider.build_body(&request);
+        let source = &body["messages"][0]["content"][0]["source"];
+        assert_eq!(source["media_type"], "image/png");
+        assert_eq!(source["data"], "iVBO");
+        assert_eq!(body["messages"][0]["content"][1]["text"], "what is this?");
+
+        let body = OllamaProvider.build_body(&request);
+        assert_eq!(body["messages"][0]["content"], "what is this?");
+        assert_eq!(body["messages"][0]["images"], json!(["iVBO"]));
+
+        let body = OpenAiResponsesProvider.build_body(&request);
+        assert_eq!(body["input"][0]["content"][0]["type"], "input_text");
+        assert_eq!(
+            body["input"][0]["content"][1]["image_url"],
+            "data:image/png;base64,iVBO"
+        );
+    }
+
+    #[test]
+    fn test_empty_profile_keeps_defaults() {
+        let body = OpenAiProvider.build_body(&request(ModelProfile::default()));
+        assert!(body.get("temperature").is_none());
+        assert!(body.get("max_tokens").is_none());
+
+        // Anthropic requires max_tokens, and raises it above a larger thinking budget
+        let mut short = ModelProfile {
+            max_tokens: Some(1000),
+            thinking_budget: Some(2048),
+            ..Default::default()
+        };
+        let body = AnthropicProvider.build_body(&request(short.clone()));
+        assert_eq!(body["thinking"]["budget_tokens"], 2048);
+        assert_eq!(body["max_tokens"], 3048);
+        short.max_tokens = None;
+        let body = AnthropicProvider.build_body(&request(short));
+        assert!(body["max_tokens"].as_u64().unwrap() > 2048);
+    }
+}
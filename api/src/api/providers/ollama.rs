@@ -0,0 +1,238 @@
+use anyhow::Result;
+use history::{Message, Usage};
+use reqwest::RequestBuilder;
+use serde_json::{json, Value};
+
+use super::{
+    arguments_value, assistant_text, merge_extra, set_field, token_count, Provider, StreamDecoder,
+    StreamFormat,
+};
+use crate::api::sse::SseEvent;
+use crate::api::types::{ChatRequest, StreamChunk};
+
+/// Ollama native `/api/chat` backend (NDJSON streaming)
+pub struct OllamaProvider;
+
+impl Provider for OllamaProvider {
+    fn chat_url(&self, api_url: &str) -> String {
+        format!("{}/api/chat", api_url)
+    }
+
+    fn models_url(&self, api_url: &str) -> String {
+        format!("{}/api/tags", api_url)
+    }
+
+    fn authorize(&self, request: RequestBuilder, api_key: &str) -> RequestBuilder {
+        // Local Ollama needs no key, but proxies in front of it may
+        if api_key.is_empty() {
+            request
+        } else {
+            request.header("Authorization", format!("Bearer {}", api_key))
+        }
+    }
+
+    fn build_body(&self, request: &ChatRequest) -> Value {
+        let messages: Vec<Value> = request.messages.iter().map(convert_message).collect();
+
+        let mut body = json!({
+            "model": request.model,
+            "messages": messages,
+            "stream": request.stream,
+        });
+
+        if !request.tools.is_empty() {
+            body["tools"] = serde_json::to_value(&request.tools).unwrap_or_default();
+        }
+
+        let profile = &request.profile;
+        let mut options = json!({});
+        set_field(&mut options, "num_predict", request.max_tokens);
+        set_field(&mut options, "temperature", profile.temperature);
+        set_field(&mut options, "top_p", profile.top_p);
+        set_field(&mut options, "seed", profile.seed);
+        if !profile.stop.is_empty() {
+            options["stop"] = json!(profile.stop);
+        }
+        if options.as_object().is_some_and(|o| !o.is_empty()) {
+            body["options"] = options;
+        }
+
+        // `think` takes an effort level on models that support one, otherwise a flag
+        if let Some(effort) = &profile.reasoning_effort {
+            body["think"] = json!(effort);
+        } else if profile.thinking_budget.is_some() {
+            body["think"] = json!(true);
+        }
+
+        merge_extra(&mut body, profile);
+        body
+    }
+
+    fn stream_format(&self) -> StreamFormat {
+        StreamFormat::Ndjson
+    }
+
+    fn stream_decoder(&self) -> Box<dyn StreamDecoder> {
+        Box::new(OllamaDecoder::default())
+    }
+
+    fn parse_response(&self, body: &Value) -> Result<Message> {
+        let content = body["message"]["content"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        let mut message = assistant_text(content);
+        message.usage = Some(parse_usage(body));
+        Ok(message)
+    }
+
+    fn parse_models(&self, body: &Value) -> Vec<String> {
+        body["models"]
+            .as_array()
+            .map(|models| {
+                models
+                    .iter()
+                    .filter_map(|m| m["name"].as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn convert_message(msg: &Message) -> Value {
+    match msg.role.as_str() {
+        "assistant" => {
+            let mut value = json!({ "role": "assistant", "content": msg.content.text });
+            if let Some(tool_calls) = &msg.tool_calls {
+                let calls: Vec<Value> = tool_calls
+                    .iter()
+                    .map(|tc| {
+                        json!({
+                            "function": {
+                                "name": tc.function.name,
+                                "arguments": arguments_value(&tc.function.arguments),
+                            }
+                        })
+                    })
+                    .collect();
+                value["tool_calls"] = json!(calls);
+            }
+            value
+        }
+        "tool" => {
+            let mut value = json!({ "role": "tool", "content": msg.content.text });
+            if let Some(name) = &msg.name {
+                value["tool_name"] = json!(name);
+            }
+            value
+        }
+        role => {
+            let mut value = json!({ "role": role, "content": msg.content.text });
+            if msg.content.has_images() {
+                // Ollama takes bare base64 payloads alongside the text
+                let images: Vec<&str> = msg
+                    .content
+                    .images
+                    .iter()
+                    .filter_map(|image| image.data_parts().map(|(_, data)| data))
+                    .collect();
+                value["images"] = json!(images);
+            }
+            value
+        }
+    }
+}
+
+fn parse_usage(body: &Value) -> Usage {
+    Usage::new(
+        token_count(&body["prompt_eval_count"]),
+        token_count(&body["eval_count"]),
+    )
+}
+
+#[derive(Default)]
+struct OllamaDecoder {
+    tool_calls_seen: usize,
+}
+
+impl StreamDecoder for OllamaDecoder {
+    fn decode(&mut self, event: &SseEvent) -> Vec<Result<StreamChunk>> {
+        let Ok(line) = serde_json::from_str::<Value>(&event.data) else {
+            return Vec::new();
+        };
+
+        let mut chunks = Vec::new();
+        let message = &line["message"];
+
+        if let Some(thinking) = message["thinking"].as_str().filter(|t| !t.is_empty()) {
+            chunks.push(Ok(StreamChunk::Reasoning(thinking.to_string())));
+        }
+
+        if let Some(content) = message["content"].as_str().filter(|c| !c.is_empty()) {
+            chunks.push(Ok(StreamChunk::Content(content.to_string())));
+        }
+
+        // Ollama sends each tool call whole and without an id
+        for tc in message["tool_calls"].as_array().into_iter().flatten() {
+            let index = self.tool_calls_seen;
+            self.tool_calls_seen += 1;
+            let id = format!("call_{}_{}", chrono::Utc::now().timestamp_millis(), index);
+            let name = tc["function"]["name"].as_str().unwrap_or("").to_string();
+            let arguments = match &tc["function"]["arguments"] {
+                Value::String(s) => s.clone(),
+                Value::Null => "{}".to_string(),
+                other => other.to_string(),
+            };
+            chunks.push(Ok(StreamChunk::ToolCall {
+                index,
+                id,
+                name,
+                arguments,
+            }));
+        }
+
+        if line["done"].as_bool() == Some(true) {
+            let reason = if self.tool_calls_seen > 0 {
+                "tool_calls"
+            } else if line["done_reason"] == "length" {
+                "length"
+            } else {
+                "stop"
+            };
+            chunks.push(Ok(StreamChunk::Usage(parse_usage(&line))));
+            chunks.push(Ok(StreamChunk::FinishReason(reason.to_string())));
+            chunks.push(Ok(StreamChunk::Done));
+        }
+
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_handles_tool_calls_and_done() {
+        let mut decoder = OllamaDecoder::default();
+
+        let first = decoder.decode(&SseEvent::data_only(
+            r#"{"message":{"role":"assistant","content":"Hi"},"done":false}"#,
+        ));
+        assert!(matches!(&first[0], Ok(StreamChunk::Content(c)) if c == "Hi"));
+
+        let chunks: Vec<StreamChunk> = decoder.decode(&SseEvent::data_only(
+                r#"{"message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"file_read","arguments":{"path":"a.rs"}}}]},"done":true,"done_reason":"stop","prompt_eval_count":12,"eval_count":3}"#,
+            ))
+            .into_iter()
+            .map(|c| c.unwrap())
+            .collect();
+
+        assert!(
+            matches!(&chunks[0], StreamChunk::ToolCall { name, arguments, .. } if name == "file_read" && arguments == r#"{"path":"a.rs"}"#)
+        );
+        assert!(matches!(&chunks[1], StreamChunk::Usage(u) if u.total_tokens() == 15));
+        assert!(matches!(&chunks[2], StreamChunk::FinishReason(r) if r == "tool_calls"));
+        assert!(matches!(chunks[3], StreamChunk::Done));
+    }
+}
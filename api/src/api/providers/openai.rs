@@ -0,0 +1,131 @@
+use anyhow::Result;
+use history::{Message, Usage};
+use reqwest::RequestBuilder;
+
+use super::{assistant_text, merge_extra, set_field, token_count, Provider, StreamDecoder};
+use crate::api::parser::parse_sse_message;
+use crate::api::sse::SseEvent;
+use crate::api::types::{ChatRequest, StreamChunk};
+
+/// OpenAI-compatible `/chat/completions` backend
+pub struct OpenAiProvider;
+
+impl Provider for OpenAiProvider {
+    fn chat_url(&self, api_url: &str) -> String {
+        format!("{}/chat/completions", api_url)
+    }
+
+    fn models_url(&self, api_url: &str) -> String {
+        format!("{}/models", api_url)
+    }
+
+    fn authorize(&self, request: RequestBuilder, api_key: &str) -> RequestBuilder {
+        request.header("Authorization", format!("Bearer {}", api_key))
+    }
+
+    fn build_body(&self, request: &ChatRequest) -> serde_json::Value {
+        let mut body = serde_json::to_value(request).unwrap_or_default();
+        // Other providers' reasoning blocks mean nothing to this API
+        if let Some(messages) = body["messages"].as_array_mut() {
+            for message in messages {
+                if let Some(message) = message.as_object_mut() {
+                    message.remove("thinking_blocks");
+                }
+            }
+        }
+
+        let profile = &request.profile;
+        set_field(&mut body, "temperature", profile.temperature);
+        set_field(&mut body, "top_p", profile.top_p);
+        set_field(
+            &mut body,
+            "reasoning_effort",
+            profile.reasoning_effort.as_ref(),
+        );
+        set_field(&mut body, "seed", profile.seed);
+        if !profile.stop.is_empty() {
+            body["stop"] = serde_json::json!(profile.stop);
+        }
+        merge_extra(&mut body, profile);
+
+        body
+    }
+
+    fn stream_decoder(&self) -> Box<dyn StreamDecoder> {
+        Box::new(OpenAiDecoder)
+    }
+
+    fn parse_response(&self, body: &serde_json::Value) -> Result<Message> {
+        let content = body["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        let mut message = assistant_text(content);
+        message.usage = parse_usage(&body["usage"]);
+        Ok(message)
+    }
+}
+
+struct OpenAiDecoder;
+
+impl StreamDecoder for OpenAiDecoder {
+    fn decode(&mut self, event: &SseEvent) -> Vec<Result<StreamChunk>> {
+        let payload = event.data.as_str();
+        let mut chunks = parse_sse_message(payload);
+
+        // The usage chunk has empty choices, but some servers attach usage to the last delta
+        if payload.contains("\"usage\"") {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(payload) {
+                if let Some(usage) = parse_usage(&value["usage"]) {
+                    chunks.push(Ok(StreamChunk::Usage(usage)));
+                }
+            }
+        }
+
+        chunks
+    }
+}
+
+fn parse_usage(usage: &serde_json::Value) -> Option<Usage> {
+    if !usage.is_object() {
+        return None;
+    }
+    Some(Usage::new(
+        token_count(&usage["prompt_tokens"]),
+        token_count(&usage["completion_tokens"]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_from_stream_and_response() {
+        let mut decoder = OpenAiDecoder;
+        let chunks = decoder.decode(&SseEvent::data_only(
+            r#"{"choices":[],"usage":{"prompt_tokens":120,"completion_tokens":30,"total_tokens":150}}"#,
+        ));
+        assert!(chunks.iter().any(|c| matches!(
+            c,
+            Ok(StreamChunk::Usage(u)) if u.prompt_tokens == 120 && u.completion_tokens == 30
+        )));
+
+        // No usage object, no usage chunk
+        let chunks = decoder.decode(&SseEvent::data_only(
+            r#"{"choices":[{"index":0,"delta":{"content":"usage"}}]}"#,
+        ));
+        assert!(!chunks
+            .iter()
+            .any(|c| matches!(c, Ok(StreamChunk::Usage(_)))));
+
+        let message = OpenAiProvider
+            .parse_response(&serde_json::json!({
+                "choices": [{ "message": { "content": "done" } }],
+                "usage": { "prompt_tokens": 7, "completion_tokens": 2 }
+            }))
+            .unwrap();
+        let usage = message.usage.unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens), (7, 2));
+    }
+}
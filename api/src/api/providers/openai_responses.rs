@@ -0,0 +1,219 @@
+use anyhow::Result;
+use history::{Message, Usage};
+use reqwest::RequestBuilder;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::{assistant_text, merge_extra, set_field, token_count, Provider, StreamDecoder};
+use crate::api::error::StreamError;
+use crate::api::sse::SseEvent;
+use crate::api::types::{ChatRequest, StreamChunk};
+
+/// OpenAI Responses API backend (`/responses`)
+pub struct OpenAiResponsesProvider;
+
+impl Provider for OpenAiResponsesProvider {
+    fn chat_url(&self, api_url: &str) -> String {
+        format!("{}/responses", api_url)
+    }
+
+    fn models_url(&self, api_url: &str) -> String {
+        format!("{}/models", api_url)
+    }
+
+    fn authorize(&self, request: RequestBuilder, api_key: &str) -> RequestBuilder {
+        request.header("Authorization", format!("Bearer {}", api_key))
+    }
+
+    fn build_body(&self, request: &ChatRequest) -> Value {
+        let mut instructions = Vec::new();
+        let mut input = Vec::new();
+
+        for msg in &request.messages {
+            match msg.role.as_str() {
+                "system" => instructions.push(msg.content.text.clone()),
+                "tool" => input.push(json!({
+                    "type": "function_call_output",
+                    "call_id": msg.tool_call_id.clone().unwrap_or_default(),
+                    "output": msg.content.text,
+                })),
+                role => {
+                    if msg.content.has_images() {
+                        input.push(json!({ "role": role, "content": input_parts(msg) }));
+                    } else if !msg.content.is_empty() {
+                        input.push(json!({ "role": role, "content": msg.content.text }));
+                    }
+                    for tc in msg.tool_calls.iter().flatten() {
+                        input.push(json!({
+                            "type": "function_call",
+                            "call_id": tc.id,
+                            "name": tc.function.name,
+                            "arguments": tc.function.arguments,
+                        }));
+                    }
+                }
+            }
+        }
+
+        let mut body = json!({
+            "model": request.model,
+            "input": input,
+            "stream": request.stream,
+            "store": false,
+        });
+
+        if !instructions.is_empty() {
+            body["instructions"] = json!(instructions.join("\n\n"));
+        }
+
+        if !request.tools.is_empty() {
+            let tools: Vec<Value> = request
+                .tools
+                .iter()
+                .map(|t| {
+                    json!({
+                        "type": "function",
+                        "name": t.function.name,
+                        "description": t.function.description,
+                        "parameters": t.function.parameters,
+                    })
+                })
+                .collect();
+            body["tools"] = json!(tools);
+        }
+
+        let profile = &request.profile;
+        set_field(&mut body, "max_output_tokens", request.max_tokens);
+        set_field(&mut body, "temperature", profile.temperature);
+        set_field(&mut body, "top_p", profile.top_p);
+        if let Some(effort) = &profile.reasoning_effort {
+            body["reasoning"] = json!({ "effort": effort, "summary": "auto" });
+        }
+
+        merge_extra(&mut body, profile);
+        body
+    }
+
+    fn stream_decoder(&self) -> Box<dyn StreamDecoder> {
+        Box::new(ResponsesDecoder::default())
+    }
+
+    fn parse_response(&self, body: &Value) -> Result<Message> {
+        let content = body["output"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|item| item["type"] == "message")
+            .flat_map(|item| item["content"].as_array().into_iter().flatten())
+            .filter(|part| part["type"] == "output_text")
+            .filter_map(|part| part["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("");
+        let mut message = assistant_text(content);
+        message.usage = parse_usage(&body["usage"]);
+        Ok(message)
+    }
+}
+
+/// Input content parts for a message with images
+fn input_parts(msg: &Message) -> Vec<Value> {
+    let mut parts = Vec::new();
+    if !msg.content.is_empty() {
+        parts.push(json!({ "type": "input_text", "text": msg.content.text }));
+    }
+    for image in &msg.content.images {
+        parts.push(json!({ "type": "input_image", "image_url": image.url }));
+    }
+    parts
+}
+
+fn parse_usage(usage: &Value) -> Option<Usage> {
+    if !usage.is_object() {
+        return None;
+    }
+    Some(Usage::new(
+        token_count(&usage["input_tokens"]),
+        token_count(&usage["output_tokens"]),
+    ))
+}
+
+#[derive(Default)]
+struct ResponsesDecoder {
+    /// output_index -> call_id of function_call items
+    calls: HashMap<u64, String>,
+}
+
+impl StreamDecoder for ResponsesDecoder {
+    fn decode(&mut self, event: &SseEvent) -> Vec<Result<StreamChunk>> {
+        let Ok(event) = serde_json::from_str::<Value>(&event.data) else {
+            return Vec::new();
+        };
+        let output_index = event["output_index"].as_u64().unwrap_or(0);
+
+        match event["type"].as_str().unwrap_or("") {
+            "response.output_text.delta" => text_delta(&event, StreamChunk::Content),
+            "response.reasoning_summary_text.delta" | "response.reasoning_text.delta" => {
+                text_delta(&event, StreamChunk::Reasoning)
+            }
+            "response.output_item.added" => {
+                let item = &event["item"];
+                if item["type"] != "function_call" {
+                    return Vec::new();
+                }
+                let call_id = item["call_id"].as_str().unwrap_or("").to_string();
+                self.calls.insert(output_index, call_id.clone());
+                vec![Ok(StreamChunk::ToolCall {
+                    index: output_index as usize,
+                    id: call_id,
+                    name: item["name"].as_str().unwrap_or("").to_string(),
+                    arguments: String::new(),
+                })]
+            }
+            "response.function_call_arguments.delta" => {
+                match (self.calls.get(&output_index), event["delta"].as_str()) {
+                    (Some(id), Some(delta)) if !delta.is_empty() => {
+                        vec![Ok(StreamChunk::ToolCall {
+                            index: output_index as usize,
+                            id: id.clone(),
+                            name: String::new(),
+                            arguments: delta.to_string(),
+                        })]
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            "response.completed" | "response.incomplete" => {
+                let reason = if event["type"] == "response.incomplete" {
+                    "length"
+                } else if self.calls.is_empty() {
+                    "stop"
+                } else {
+                    "tool_calls"
+                };
+                let mut chunks = Vec::new();
+                if let Some(usage) = parse_usage(&event["response"]["usage"]) {
+                    chunks.push(Ok(StreamChunk::Usage(usage)));
+                }
+                chunks.push(Ok(StreamChunk::FinishReason(reason.to_string())));
+                chunks.push(Ok(StreamChunk::Done));
+                chunks
+            }
+            "response.failed" => {
+                let error = &event["response"]["error"];
+                vec![Err(StreamError::new(
+                    error["code"].as_str().unwrap_or("response_failed"),
+                    error["message"].as_str().unwrap_or("unknown error"),
+                )
+                .into())]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn text_delta(event: &Value, wrap: fn(String) -> StreamChunk) -> Vec<Result<StreamChunk>> {
+    match event["delta"].as_str() {
+        Some(t) if !t.is_empty() => vec![Ok(wrap(t.to_string()))],
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Records raw API exchanges (`--record <dir>`) for replay with the `mock_llm` server.
+///
+/// Files are numbered per exchange: `0001-request.json` holds the request body, followed by
+/// `0001-response.sse` (raw stream bytes), `0001-response.json` or `0001-error.json`.
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    next: AtomicU32,
+}
+
+/// Files of one recorded exchange
+#[derive(Debug)]
+pub struct Exchange {
+    prefix: PathBuf,
+}
+
+impl Recorder {
+    pub fn new(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create record directory {}", dir.display()))?;
+
+        // Continue numbering after an earlier recording in the same directory
+        let last = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.split_once('-')?.0.parse::<u32>().ok()
+            })
+            .max()
+            .unwrap_or(0);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            next: AtomicU32::new(last + 1),
+        })
+    }
+
+    /// Save a request body and start a new exchange
+    pub fn record_request(&self, body: &Value) -> Result<Exchange> {
+        let seq = self.next.fetch_add(1, Ordering::SeqCst);
+        let exchange = Exchange {
+            prefix: self.dir.join(format!("{:04}", seq)),
+        };
+        fs::write(
+            exchange.path("request.json"),
+            serde_json::to_string_pretty(body)?,
+        )?;
+        Ok(exchange)
+    }
+}
+
+impl Exchange {
+    fn path(&self, suffix: &str) -> PathBuf {
+        PathBuf::from(format!("{}-{}", self.prefix.display(), suffix))
+    }
+
+    /// File receiving the raw bytes of a streaming response
+    pub fn stream_file(&self) -> Result<File> {
+        Ok(File::create(self.path("response.sse"))?)
+    }
+
+    pub fn record_response(&self, body: &str) -> Result<()> {
+        Ok(fs::write(self.path("response.json"), body)?)
+    }
+
+    pub fn record_error(&self, status: u16, retry_after: Option<u64>, body: &str) -> Result<()> {
+        let error = json!({ "status": status, "retry_after": retry_after, "body": body });
+        Ok(fs::write(
+            self.path("error.json"),
+            serde_json::to_string_pretty(&error)?,
+        )?)
+    }
+}
+
+/// Append stream bytes to a recording, ignoring write failures
+pub fn write_stream_bytes(file: &mut Option<File>, bytes: &[u8]) {
+    if let Some(f) = file {
+        if f.write_all(bytes).is_err() {
+            *file = None;
+        }
+    }
+}
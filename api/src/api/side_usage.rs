@@ -0,0 +1,16 @@
+use history::Usage;
+use std::sync::Mutex;
+
+/// Usage of requests made outside the conversation, not yet added to the session
+static PENDING: Mutex<Vec<Usage>> = Mutex::new(Vec::new());
+
+/// Queue the usage of a helper request (compaction, prompt optimization, review,
+/// commit message) so it is counted in the session totals
+pub fn record_side_usage(usage: Usage) {
+    PENDING.lock().unwrap().push(usage);
+}
+
+/// Take the queued usage records
+pub fn take_side_usage() -> Vec<Usage> {
+    std::mem::take(&mut *PENDING.lock().unwrap())
+}
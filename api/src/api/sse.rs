@@ -0,0 +1,143 @@
+/// A dispatched Server-Sent Event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Value of the `event:` field, None for the default `message` type
+    pub event: Option<String>,
+    /// `data:` lines joined with `\n`
+    pub data: String,
+    /// Last event id seen on the stream
+    pub id: Option<String>,
+}
+
+impl SseEvent {
+    /// Event carrying only a data payload (used for NDJSON lines)
+    pub fn data_only(data: &str) -> Self {
+        Self {
+            event: None,
+            data: data.to_string(),
+            id: None,
+        }
+    }
+
+    /// Whether this event has the given `event:` type
+    pub fn is(&self, event_type: &str) -> bool {
+        self.event.as_deref() == Some(event_type)
+    }
+}
+
+/// Incremental SSE parser following the WHATWG event stream rules:
+/// fields are collected line by line and an event is dispatched on a blank line.
+#[derive(Debug, Default)]
+pub struct SseEventParser {
+    event: Option<String>,
+    data: String,
+    has_data: bool,
+    last_id: Option<String>,
+    started: bool,
+}
+
+impl SseEventParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one line (without terminator), returns an event when one is complete
+    pub fn push_line(&mut self, line: &str) -> Option<SseEvent> {
+        // Strip a UTF-8 BOM at the very start of the stream
+        let line = if self.started {
+            line
+        } else {
+            self.started = true;
+            line.strip_prefix('\u{feff}').unwrap_or(line)
+        };
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        // Comment line (also used as keep-alive)
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.find(':') {
+            Some(pos) => {
+                let value = &line[pos + 1..];
+                (&line[..pos], value.strip_prefix(' ').unwrap_or(value))
+            }
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            // `retry` and unknown fields are ignored
+            _ => {}
+        }
+
+        None
+    }
+
+    /// End of stream: dispatch a pending event that was not followed by a blank line
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        self.dispatch()
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if !self.has_data {
+            return None;
+        }
+        self.has_data = false;
+        Some(SseEvent {
+            event: event.filter(|e| !e.is_empty()),
+            data: std::mem::take(&mut self.data),
+            id: self.last_id.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lines: &[&str]) -> Vec<SseEvent> {
+        let mut parser = SseEventParser::new();
+        let mut events: Vec<SseEvent> = lines.iter().filter_map(|l| parser.push_line(l)).collect();
+        events.extend(parser.finish());
+        events
+    }
+
+    #[test]
+    fn test_event_fields_and_multiline_data() {
+        let events = parse(&[
+            ": keep-alive",
+            "event: content_block_delta",
+            "id: 7",
+            "data: {\"a\":",
+            "data:1}",
+            "",
+            "data: [DONE]",
+        ]);
+
+        assert_eq!(events.len(), 2);
+        assert!(events[0].is("content_block_delta"));
+        assert_eq!(events[0].data, "{\"a\":\n1}");
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+        assert_eq!(events[1].event, None);
+        assert_eq!(events[1].data, "[DONE]");
+    }
+
+    #[test]
+    fn test_event_without_data_is_not_dispatched() {
+        let events = parse(&["event: ping", "", "retry: 1000", ""]);
+        assert!(events.is_empty());
+    }
+}
@@ -1,61 +1,94 @@
 use anyhow::Result;
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use ui::get_i18n;
 
-/// SSE line buffering stream
-/// Correctly handles split JSON data (a single data: line may be split across byte chunks)
-pub struct SseLineStream<S> {
+use super::error::{ApiError, StreamError};
+use super::providers::{StreamDecoder, StreamFormat};
+use super::sse::{SseEvent, SseEventParser};
+use super::types::StreamChunk;
+
+/// Line buffering stream over raw response bytes
+/// Buffers bytes (not text) so multi-byte UTF-8 sequences split across chunks stay intact,
+/// and accepts `\n`, `\r\n` and `\r` line terminators
+pub struct LineStream<S> {
     inner: S,
-    buffer: String,
+    buffer: Vec<u8>,
+    finished: bool,
 }
 
-impl<S> SseLineStream<S> {
+impl<S> LineStream<S> {
     pub fn new(stream: S) -> Self {
         Self {
             inner: stream,
-            buffer: String::new(),
+            buffer: Vec::new(),
+            finished: false,
         }
     }
+
+    /// Take the next complete line out of the buffer
+    fn take_line(&mut self) -> Option<String> {
+        let pos = self.buffer.iter().position(|&b| b == b'\n' || b == b'\r')?;
+
+        let terminator_len = if self.buffer[pos] == b'\r' {
+            match self.buffer.get(pos + 1) {
+                Some(b'\n') => 2,
+                Some(_) => 1,
+                // A trailing \r may be the first half of \r\n, wait for more data
+                None if !self.finished => return None,
+                None => 1,
+            }
+        } else {
+            1
+        };
+
+        let line: Vec<u8> = self
+            .buffer
+            .drain(..pos + terminator_len)
+            .take(pos)
+            .collect();
+        Some(String::from_utf8_lossy(&line).into_owned())
+    }
 }
 
-impl<S> Stream for SseLineStream<S>
+impl<S, B, E> Stream for LineStream<S>
 where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = std::result::Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
 {
     type Item = Result<String>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
             // First check if we have a complete line in the buffer
-            if let Some(pos) = self.buffer.find('\n') {
-                let line = self.buffer.drain(0..=pos).collect::<String>();
-                let trimmed = line.trim_end_matches('\n').to_string();
-                if !trimmed.is_empty() || self.buffer.is_empty() {
-                    return Poll::Ready(Some(Ok(trimmed)));
+            if let Some(line) = self.take_line() {
+                return Poll::Ready(Some(Ok(line)));
+            }
+
+            if self.finished {
+                // Stream ended, send remaining buffer data
+                if self.buffer.is_empty() {
+                    return Poll::Ready(None);
                 }
+                let remaining = std::mem::take(&mut self.buffer);
+                return Poll::Ready(Some(Ok(String::from_utf8_lossy(&remaining).into_owned())));
             }
 
             // Get next bytes from the stream
             match Pin::new(&mut self.inner).poll_next(cx) {
                 Poll::Ready(Some(Ok(bytes))) => {
-                    let text = String::from_utf8_lossy(&bytes);
-                    self.buffer.push_str(&text);
+                    self.buffer.extend_from_slice(bytes.as_ref());
                 }
                 Poll::Ready(Some(Err(e))) => {
                     let i18n = get_i18n();
                     let tmpl = i18n.get("api_stream_error");
-                    let msg = tmpl.replace("{}", &e.to_string());
-                    return Poll::Ready(Some(Err(anyhow::anyhow!(msg))));
+                    let message = tmpl.replace("{}", &e.to_string());
+                    return Poll::Ready(Some(Err(ApiError::Network { message }.into())));
                 }
                 Poll::Ready(None) => {
-                    // Stream ended, send remaining buffer data
-                    if !self.buffer.is_empty() {
-                        let remaining = std::mem::take(&mut self.buffer);
-                        return Poll::Ready(Some(Ok(remaining)));
-                    }
-                    return Poll::Ready(None);
+                    self.finished = true;
                 }
                 Poll::Pending => {
                     return Poll::Pending;
@@ -64,3 +97,337 @@ where
         }
     }
 }
+
+/// Decode a streaming response body into chunks.
+/// SSE bodies are parsed into events, NDJSON lines are passed on as data-only events.
+/// Provider `error` payloads become `StreamError`s and a body that ends before the response
+/// finished becomes `ApiError::MalformedStream`. Data that is not JSON (proxy keep-alives and
+/// the like) is skipped.
+pub fn decode_stream<S, B, E>(
+    bytes: S,
+    format: StreamFormat,
+    mut decoder: Box<dyn StreamDecoder>,
+) -> impl Stream<Item = Result<StreamChunk>> + Send
+where
+    S: Stream<Item = std::result::Result<B, E>> + Unpin + Send,
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    let mut parser = SseEventParser::new();
+    // Set once the response finished or failed, nothing is expected after that
+    let mut finished = false;
+
+    // A trailing None marks the end of input so a final unterminated event is not lost
+    LineStream::new(bytes)
+        .map(Some)
+        .chain(futures::stream::once(async { None }))
+        .flat_map(move |line| {
+            let (event, at_end) = match line {
+                Some(Ok(line)) => match format {
+                    StreamFormat::Sse => (parser.push_line(&line), false),
+                    StreamFormat::Ndjson => {
+                        let line = line.trim();
+                        ((!line.is_empty()).then(|| SseEvent::data_only(line)), false)
+                    }
+                },
+                Some(Err(e)) => {
+                    finished = true;
+                    return futures::stream::iter(vec![Err(e)]);
+                }
+                None => (parser.finish(), true),
+            };
+
+            let mut chunks = match event {
+                Some(event) => match StreamError::from_event(&event) {
+                    Some(error) => vec![Err(error.into())],
+                    None if is_undecodable(&event) => {
+                        debug_skipped(&event.data);
+                        Vec::new()
+                    }
+                    None => decoder.decode(&event),
+                },
+                None => Vec::new(),
+            };
+
+            finished |= chunks.iter().any(|chunk| {
+                matches!(
+                    chunk,
+                    Err(_) | Ok(StreamChunk::FinishReason(_)) | Ok(StreamChunk::Done)
+                )
+            });
+            if at_end && !finished {
+                chunks.push(Err(malformed("api_stream_truncated", "")));
+            }
+            futures::stream::iter(chunks)
+        })
+}
+
+/// Providers only send JSON payloads (plus the `[DONE]` sentinel)
+fn is_undecodable(event: &SseEvent) -> bool {
+    let data = event.data.trim();
+    data != "[DONE]" && serde_json::from_str::<serde::de::IgnoredAny>(data).is_err()
+}
+
+/// Skipped data is only reported with `FRIENDEV_DEBUG` set
+fn debug_skipped(data: &str) {
+    if std::env::var_os("FRIENDEV_DEBUG").is_some() {
+        eprintln!(
+            "\x1b[90m[debug] skipped non-JSON stream data: {}\x1b[0m",
+            truncate(data, 200)
+        );
+    }
+}
+
+fn malformed(key: &str, data: &str) -> anyhow::Error {
+    let message = get_i18n().get(key).replace("{}", &truncate(data, 200));
+    ApiError::MalformedStream { message }.into()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}...", text.chars().take(max).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::providers::create_provider;
+    use std::collections::BTreeMap;
+
+    /// Decoded transcript, with tool call fragments merged by index
+    #[derive(Debug, Default)]
+    struct Transcript {
+        content: String,
+        reasoning: String,
+        tool_calls: BTreeMap<usize, (String, String, String)>,
+        finish_reason: Option<String>,
+        usage: Option<(u64, u64)>,
+        done: bool,
+        errors: Vec<StreamError>,
+        api_errors: Vec<ApiError>,
+    }
+
+    /// Feed a fixture through the full decoder in small byte chunks
+    /// (splitting lines and multi-byte characters)
+    fn decode_fixture(provider: &str, body: &str, chunk_size: usize) -> Transcript {
+        let provider = create_provider(provider);
+        let pieces: Vec<std::result::Result<Vec<u8>, String>> = body
+            .as_bytes()
+            .chunks(chunk_size)
+            .map(|c| Ok(c.to_vec()))
+            .collect();
+        let chunks: Vec<Result<StreamChunk>> = futures::executor::block_on(
+            decode_stream(
+                futures::stream::iter(pieces),
+                provider.stream_format(),
+                provider.stream_decoder(),
+            )
+            .collect(),
+        );
+
+        let mut transcript = Transcript::default();
+        for chunk in chunks {
+            match chunk {
+                Ok(StreamChunk::Content(text)) => transcript.content.push_str(&text),
+                Ok(StreamChunk::Reasoning(text)) => transcript.reasoning.push_str(&text),
+                Ok(StreamChunk::ToolCall {
+                    index,
+                    id,
+                    name,
+                    arguments,
+                }) => {
+                    let call = transcript.tool_calls.entry(index).or_default();
+                    if !id.is_empty() {
+                        call.0 = id;
+                    }
+                    if !name.is_empty() {
+                        call.1 = name;
+                    }
+                    call.2.push_str(&arguments);
+                }
+                Ok(StreamChunk::FinishReason(reason)) => transcript.finish_reason = Some(reason),
+                Ok(StreamChunk::Usage(u)) => {
+                    transcript.usage = Some((u.prompt_tokens, u.completion_tokens))
+                }
+                Ok(StreamChunk::Done) => transcript.done = true,
+                Ok(StreamChunk::Model(_)) | Ok(StreamChunk::ThinkingBlock(_)) => {}
+                Err(e) => match e.downcast::<StreamError>() {
+                    Ok(error) => transcript.errors.push(error),
+                    Err(e) => transcript.api_errors.push(ApiError::classify(&e)),
+                },
+            }
+        }
+        transcript
+    }
+
+    fn tool_call(t: &Transcript, index: usize) -> (&str, &str, &str) {
+        let (id, name, args) = &t.tool_calls[&index];
+        (id, name, args)
+    }
+
+    #[test]
+    fn test_openai_chat_parallel_tool_calls() {
+        let body = include_str!("../../tests/fixtures/openai_chat_parallel_tools.sse");
+        for chunk_size in [1, 7, 64, body.len()] {
+            let t = decode_fixture("openai", body, chunk_size);
+            assert_eq!(t.content, "Reading both files — 你好");
+            assert_eq!(
+                tool_call(&t, 0),
+                ("call_read", "file_read", r#"{"path": "src/main.rs"}"#)
+            );
+            assert_eq!(
+                tool_call(&t, 1),
+                ("call_list", "file_list", r#"{"path": "tests"}"#)
+            );
+            assert_eq!(t.finish_reason.as_deref(), Some("tool_calls"));
+            assert_eq!(t.usage, Some((1523, 61)));
+            assert!(t.done);
+            assert!(t.errors.is_empty());
+            assert!(t.api_errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_openai_compatible_reasoning_with_crlf() {
+        let body =
+            include_str!("../../tests/fixtures/deepseek_reasoning.sse").replace('\n', "\r\n");
+        for chunk_size in [1, 5, body.len()] {
+            let t = decode_fixture("openai", &body, chunk_size);
+            assert_eq!(t.reasoning, "The user greets me.");
+            // Content and finish_reason share the last event
+            assert_eq!(t.content, "Hello! How can I help?");
+            assert_eq!(t.finish_reason.as_deref(), Some("stop"));
+            assert_eq!(t.usage, Some((12, 20)));
+            assert!(t.done);
+        }
+    }
+
+    #[test]
+    fn test_openai_mid_stream_error_is_typed() {
+        let body = "data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n\
+                    data: {\"error\":{\"message\":\"The server had an error\",\"type\":\"server_error\",\"code\":null}}\n\n";
+        let t = decode_fixture("openai", body, 3);
+        assert_eq!(t.content, "Hi");
+        assert_eq!(
+            t.errors,
+            vec![StreamError::new("server_error", "The server had an error")]
+        );
+    }
+
+    #[test]
+    fn test_anthropic_messages_tool_use() {
+        let body = include_str!("../../tests/fixtures/anthropic_messages_tool_use.sse");
+        for chunk_size in [1, 11, body.len()] {
+            let t = decode_fixture("anthropic", body, chunk_size);
+            assert_eq!(t.reasoning, "I should list the directory.");
+            assert_eq!(t.content, "Let me look — 一下。");
+            assert_eq!(
+                tool_call(&t, 2),
+                ("toolu_01A", "file_list", r#"{"path": "src"}"#)
+            );
+            assert_eq!(t.finish_reason.as_deref(), Some("tool_calls"));
+            assert_eq!(t.usage, Some((2310, 89)));
+            assert!(t.done);
+        }
+    }
+
+    #[test]
+    fn test_anthropic_error_event() {
+        let body = include_str!("../../tests/fixtures/anthropic_overloaded.sse");
+        let t = decode_fixture("anthropic", body, 9);
+        assert_eq!(
+            t.errors,
+            vec![StreamError::new("overloaded_error", "Overloaded")]
+        );
+        assert!(!t.done);
+    }
+
+    #[test]
+    fn test_openai_responses_function_call() {
+        let body = include_str!("../../tests/fixtures/openai_responses_function_call.sse");
+        let t = decode_fixture("openai-responses", body, 13);
+        assert_eq!(t.reasoning, "Need the file.");
+        assert_eq!(t.content, "Reading it now.");
+        assert_eq!(
+            tool_call(&t, 2),
+            ("call_abc", "file_read", r#"{"path":"Cargo.toml"}"#)
+        );
+        assert_eq!(t.finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(t.usage, Some((900, 40)));
+        assert!(t.done);
+    }
+
+    #[test]
+    fn test_ollama_ndjson_tool_call() {
+        let body = include_str!("../../tests/fixtures/ollama_chat_tool_call.ndjson");
+        let t = decode_fixture("ollama", body, 6);
+        assert_eq!(t.reasoning, "Check files.");
+        assert_eq!(t.content, "好的，");
+        let (id, name, args) = tool_call(&t, 0);
+        assert!(!id.is_empty());
+        assert_eq!((name, args), ("file_list", r#"{"path":"."}"#));
+        assert_eq!(t.finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(t.usage, Some((350, 42)));
+        assert!(t.done);
+    }
+
+    #[test]
+    fn test_last_event_without_trailing_blank_line() {
+        let t = decode_fixture("openai", "data: [DONE]", 4);
+        assert!(t.done);
+    }
+
+    #[test]
+    fn test_non_json_data_is_skipped() {
+        let t = decode_fixture(
+            "openai",
+            "data: keep-alive\n\ndata: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n\
+             data: ping\n\ndata: [DONE]\n\n",
+            7,
+        );
+        assert_eq!(t.content, "Hi");
+        assert!(t.done);
+        assert!(t.api_errors.is_empty());
+    }
+
+    #[test]
+    fn test_any_finish_reason_ends_stream() {
+        for reason in ["content_filter", "function_call", "eos"] {
+            let body = format!(
+                "data: {{\"choices\":[{{\"delta\":{{\"content\":\"x\"}}}}]}}\n\n\
+                 data: {{\"choices\":[{{\"delta\":{{}},\"finish_reason\":\"{}\"}}]}}\n\n",
+                reason
+            );
+            let t = decode_fixture("openai", &body, 9);
+            assert_eq!(t.finish_reason.as_deref(), Some(reason));
+            assert!(t.api_errors.is_empty(), "{}: {:?}", reason, t.api_errors);
+        }
+    }
+
+    #[test]
+    fn test_truncated_stream_is_malformed() {
+        let t = decode_fixture(
+            "openai",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+            5,
+        );
+        assert_eq!(t.content, "Hel");
+        assert!(matches!(
+            t.api_errors.as_slice(),
+            [ApiError::MalformedStream { .. }]
+        ));
+
+        let t = decode_fixture(
+            "anthropic",
+            "event: content_block_delta\ndata: {\"type\":\"content_blo",
+            5,
+        );
+        assert!(matches!(
+            t.api_errors.as_slice(),
+            [ApiError::MalformedStream { .. }]
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use config::ModelProfile;
 use tools;
 
 /// Chat request to be sent to the API
@@ -8,10 +9,21 @@ pub struct ChatRequest {
     pub model: String,
     pub messages: Vec<history::Message>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub tools: Vec<tools::Tool>,
+    pub tools: Vec<tools::ToolDefinition>,
     pub stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    /// Model parameters, mapped onto the body by each provider
+    #[serde(skip)]
+    pub profile: ModelProfile,
+}
+
+/// Streaming options (asks OpenAI-compatible servers for a final usage chunk)
+#[derive(Debug, Serialize)]
+pub struct StreamOptions {
+    pub include_usage: bool,
 }
 
 /// Chat API response
@@ -57,7 +69,8 @@ pub struct Delta {
 /// Tool call delta in streaming response
 #[derive(Debug, Deserialize)]
 pub struct ToolCallDelta {
-    #[allow(dead_code)]
+    /// Position of the call among parallel calls (some servers omit it)
+    #[serde(default)]
     pub index: usize,
     pub id: Option<String>,
     #[serde(rename = "type")]
@@ -80,26 +93,21 @@ pub enum StreamChunk {
     Content(String),
     /// Reasoning content (thinking process)
     Reasoning(String),
-    /// Tool call data
+    /// A complete signed reasoning block to keep on the message (Anthropic extended thinking)
+    ThinkingBlock(serde_json::Value),
+    /// Tool call data, `index` identifies the call among parallel calls
     ToolCall {
+        index: usize,
         id: String,
         name: String,
         arguments: String,
     },
     /// Finish reason: stop, length, tool_calls, etc.
     FinishReason(String),
+    /// Token usage of the whole response
+    Usage(history::Usage),
+    /// Model answering this request (first chunk, differs from the configured model on fallback)
+    Model(String),
     /// Indicates stream is done
     Done,
 }
-
-/// Models list response
-#[derive(Debug, Deserialize)]
-pub struct ModelsResponse {
-    pub data: Vec<ModelInfo>,
-}
-
-/// Model info from models list response
-#[derive(Debug, Deserialize)]
-pub struct ModelInfo {
-    pub id: String,
-}
@@ -1,3 +1,6 @@
 pub mod api;
 
-pub use api::{execute_tool_calls, ApiClient, StreamChunk, ToolCallAccumulator};
+pub use api::{
+    execute_tool_calls, record_side_usage, take_side_usage, ApiClient, ApiError, StreamChunk,
+    StreamError, ToolCallAccumulator,
+};
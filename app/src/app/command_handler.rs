@@ -1,10 +1,12 @@
+use super::context;
+use super::images;
 use super::message_builder;
 use super::startup::AppState;
 use anyhow::Result;
 use api;
 use chat;
 use commands;
-use history::Message;
+use history::{Message, MessageContent, Usage, UsageTotals};
 use security;
 use ui::get_i18n;
 
@@ -15,6 +17,10 @@ pub async fn handle_user_input(line: &str, state: &mut AppState) -> Result<()> {
         // Special handling for /agents.md command
         if line == "/agents.md" {
             handle_agents_md_command(state).await?;
+        } else if line == "/compact" {
+            handle_compact_command(state).await?;
+        } else if line == "/image" || line.starts_with("/image ") {
+            handle_image_command(line["/image".len()..].trim(), state).await?;
         } else {
             // Other commands
             if let Err(e) = commands::handle_command(
@@ -29,9 +35,26 @@ pub async fn handle_user_input(line: &str, state: &mut AppState) -> Result<()> {
                 eprintln!("\n\x1b[31m[X] {}:\x1b[0m {}\n", i18n.get("error"), e);
             }
         }
+        // Commands such as /commit and /compact make requests of their own
+        if !record_side_usage(state).is_empty() {
+            state.session.save()?;
+        }
         return Ok(());
     }
 
+    send_user_message(line, state).await
+}
+
+/// Add the usage of requests made outside the conversation (compaction, reviews, commit
+/// messages, prompt optimization) to the session; returns the added records
+pub fn record_side_usage(state: &mut AppState) -> Vec<Usage> {
+    let usage = api::take_side_usage();
+    state.session.add_side_usage(usage.clone());
+    usage
+}
+
+/// Send a user message with any pending and `@mentioned` images
+async fn send_user_message(line: &str, state: &mut AppState) -> Result<()> {
     // Security check: intercept suspicious input
     if security::is_input_suspicious(line) {
         let i18n = get_i18n();
@@ -43,13 +66,22 @@ pub async fn handle_user_input(line: &str, state: &mut AppState) -> Result<()> {
         return Ok(());
     }
 
+    let mut images = std::mem::take(&mut state.pending_images);
+    images.extend(images::load_mentioned_images(
+        line,
+        &state.session.working_directory,
+    ));
+
     // User message
     let user_message = Message {
         role: "user".to_string(),
-        content: line.to_string(),
+        content: MessageContent::with_images(line, images),
         tool_calls: None,
         tool_call_id: None,
         name: None,
+        usage: None,
+        model: None,
+        thinking_blocks: Vec::new(),
     };
     state.session.add_message(user_message);
 
@@ -67,10 +99,13 @@ async fn handle_agents_md_command(state: &mut AppState) -> Result<()> {
             // Add prompt as USER message to session
             let analysis_message = Message {
                 role: "user".to_string(),
-                content: analysis_prompt,
+                content: analysis_prompt.into(),
                 tool_calls: None,
                 tool_call_id: None,
                 name: None,
+                usage: None,
+                model: None,
+                thinking_blocks: Vec::new(),
             };
             state.session.add_message(analysis_message);
 
@@ -83,10 +118,81 @@ async fn handle_agents_md_command(state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+/// Handle /image command: attach an image, sending it now when a message follows
+async fn handle_image_command(args: &str, state: &mut AppState) -> Result<()> {
+    let i18n = get_i18n();
+    let (path, message) = match args.split_once(char::is_whitespace) {
+        Some((path, message)) => (path, message.trim()),
+        None => (args, ""),
+    };
+    if path.is_empty() {
+        println!("\n\x1b[33m[!] {}\x1b[0m\n", i18n.get("image_usage"));
+        return Ok(());
+    }
+
+    let Some(image) = images::load_image(path, &state.session.working_directory) else {
+        return Ok(());
+    };
+    images::warn_if_no_vision(&state.config);
+    state.pending_images.push(image);
+
+    if message.is_empty() {
+        println!("\x1b[90m[i] {}\x1b[0m\n", i18n.get("image_pending"));
+        return Ok(());
+    }
+    send_user_message(message, state).await
+}
+
+/// Handle /compact command: summarize older turns now
+async fn handle_compact_command(state: &mut AppState) -> Result<()> {
+    let i18n = get_i18n();
+    match context::compact_session(&mut state.session, &state.api_client).await {
+        Ok(0) => println!("\n\x1b[90m[i] {}\x1b[0m\n", i18n.get("compact_nothing")),
+        Ok(count) => {
+            state.session.save()?;
+            println!(
+                "\x1b[32m[OK]\x1b[0m {}\n",
+                i18n.get("compact_done").replace("{}", &count.to_string())
+            );
+        }
+        Err(e) => eprintln!(
+            "\n\x1b[31m[X] {}:\x1b[0m {}\n",
+            i18n.get("compact_failed"),
+            e
+        ),
+    }
+    Ok(())
+}
+
 /// Process chat loop: send message and handle tool calls
 async fn process_chat_loop(state: &mut AppState) -> Result<()> {
+    // Before the user message, which stays last (it is the checkpoint label and is
+    // dropped again when the request fails)
+    if let Some(notice) = job_notices() {
+        let at = state.session.messages.len().saturating_sub(1);
+        state.session.messages.insert(at, notice);
+    }
+    context::compact_if_needed(&mut state.session, &state.api_client, &state.config).await?;
     let mut messages =
-        message_builder::build_messages_with_agents_md(&state.session, &state.config)?;
+        message_builder::build_messages_with_agents_md(&state.session, &state.config);
+    let turn_start = state.session.messages.len();
+
+    // Files edited during this turn can be reverted with /undo
+    let label = state
+        .session
+        .messages
+        .iter()
+        .rev()
+        .find(|message| message.role == "user")
+        .map(|message| message.content.as_str())
+        .unwrap_or_default();
+    if let Err(e) = tools::checkpoint::begin_turn(state.session.id, label) {
+        eprintln!(
+            "\n\x1b[33m[!] {}:\x1b[0m {}\n",
+            get_i18n().get("checkpoint_unavailable"),
+            e
+        );
+    }
 
     loop {
         match chat::send_and_receive(&state.api_client, messages.clone(), &state.session).await {
@@ -106,12 +212,21 @@ async fn process_chat_loop(state: &mut AppState) -> Result<()> {
                     for result in tool_results {
                         state.session.add_message(result);
                     }
+                    if let Some(notice) = job_notices() {
+                        state.session.add_message(notice);
+                    }
 
                     // Continue loop to send tool results to AI
+                    context::compact_if_needed(
+                        &mut state.session,
+                        &state.api_client,
+                        &state.config,
+                    )
+                    .await?;
                     messages = message_builder::build_messages_with_agents_md(
                         &state.session,
                         &state.config,
-                    )?;
+                    );
                     continue;
                 }
 
@@ -119,7 +234,11 @@ async fn process_chat_loop(state: &mut AppState) -> Result<()> {
             }
             Err(e) => {
                 let i18n = get_i18n();
-                eprintln!("\n\x1b[31m[X] {}:\x1b[0m {}\n", i18n.get("api_error"), e);
+                eprintln!("\n\x1b[31m[X] {}:\x1b[0m {}", i18n.get("api_error"), e);
+                match e.downcast_ref::<api::ApiError>() {
+                    Some(api_error) => eprintln!("\x1b[33m[!] {}\x1b[0m\n", api_error.hint()),
+                    None => eprintln!(),
+                }
                 // Remove last message since no valid response
                 if !state.session.messages.is_empty() {
                     state.session.messages.pop();
@@ -128,5 +247,76 @@ async fn process_chat_loop(state: &mut AppState) -> Result<()> {
             }
         }
     }
+
+    commands::auto_commit_turn(
+        &state.config,
+        &state.session.working_directory,
+        &state.api_client,
+    )
+    .await;
+
+    let side_usage = record_side_usage(state);
+    let turn_start = turn_start.min(state.session.messages.len());
+    let totals = UsageTotals::collect(
+        state.session.messages[turn_start..]
+            .iter()
+            .filter_map(|m| m.usage.as_ref())
+            .chain(&side_usage),
+        &state.config,
+    );
+    print_turn_usage(&totals);
+
     Ok(())
 }
+
+/// Message telling the model about background jobs that finished since its last request
+fn job_notices() -> Option<Message> {
+    let notices = tools::jobs::take_notices();
+    if notices.is_empty() {
+        return None;
+    }
+    let i18n = get_i18n();
+    for notice in &notices {
+        println!(
+            "\x1b[90m[i] {}\x1b[0m",
+            i18n.get("job_notice_shown")
+                .replacen("{}", &notice.job.id.to_string(), 1)
+                .replacen("{}", &notice.job.command, 1)
+                .replacen("{}", &notice.job.status_text(), 1)
+        );
+    }
+    println!();
+    let messages: Vec<&str> = notices.iter().map(|n| n.message.as_str()).collect();
+    Some(Message {
+        role: "user".to_string(),
+        content: messages.join("\n\n").into(),
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+        usage: None,
+        model: None,
+        thinking_blocks: Vec::new(),
+    })
+}
+
+/// Print token usage of one user turn (all model requests including tool loops)
+fn print_turn_usage(totals: &UsageTotals) {
+    if totals.requests == 0 {
+        return;
+    }
+    let i18n = get_i18n();
+    let mut line = format!(
+        "{}: {} {} / {} {} · {} {}",
+        i18n.get("usage_tokens"),
+        totals.prompt_tokens,
+        i18n.get("usage_input"),
+        totals.completion_tokens,
+        i18n.get("usage_output"),
+        totals.requests,
+        i18n.get("usage_requests")
+    );
+    if totals.unpriced_requests < totals.requests {
+        line.push_str(&format!(" · ${:.4}", totals.cost));
+    }
+    println!("\x1b[90m[i] {}\x1b[0m\n", line);
+}
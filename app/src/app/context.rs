@@ -0,0 +1,413 @@
+use anyhow::Result;
+use api::ApiClient;
+use config::Config;
+use history::{ChatSession, Compaction, Message};
+use ui::get_i18n;
+
+use super::message_builder;
+
+/// Compact when the estimated request size exceeds this share of the context window
+const COMPACT_THRESHOLD: f64 = 0.8;
+
+/// Number of most recent user turns that are never summarized or elided
+const KEEP_RECENT_TURNS: usize = 2;
+
+/// Old tool outputs longer than this are elided when the context gets tight
+const ELIDE_TOOL_OUTPUT_CHARS: usize = 4000;
+
+/// Characters of an elided tool output that are kept as a preview
+const ELIDED_PREVIEW_CHARS: usize = 800;
+
+/// Per-message characters included in the transcript sent for summarization
+const TRANSCRIPT_MESSAGE_CHARS: usize = 2000;
+const TRANSCRIPT_TOOL_OUTPUT_CHARS: usize = 500;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You compress conversations between a user and Friendev, \
+a programming assistant with file and command tools. Write a concise summary that preserves: \
+the user's goals and requirements, decisions made, files created or modified (with paths), \
+important command results, errors and how they were resolved, and open tasks. \
+Use plain bullet points. Do not invent details.";
+
+/// Flat per-image estimate (providers charge roughly 1k tokens for a typical screenshot)
+const IMAGE_TOKENS: u64 = 1000;
+
+/// Rough token estimate: ~4 bytes per token for ASCII, one token per other character
+pub fn estimate_text_tokens(text: &str) -> u64 {
+    let (ascii, other) = text.chars().fold((0u64, 0u64), |(a, o), c| {
+        if c.is_ascii() {
+            (a + 1, o)
+        } else {
+            (a, o + 1)
+        }
+    });
+    ascii.div_ceil(4) + other
+}
+
+/// Estimated prompt tokens of a message list (including per-message overhead)
+pub fn estimate_tokens(messages: &[Message]) -> u64 {
+    messages
+        .iter()
+        .map(|m| {
+            let calls: u64 = m
+                .tool_calls
+                .iter()
+                .flatten()
+                .map(|tc| {
+                    estimate_text_tokens(&tc.function.name)
+                        + estimate_text_tokens(&tc.function.arguments)
+                })
+                .sum();
+            let images = m.content.images.len() as u64 * IMAGE_TOKENS;
+            4 + estimate_text_tokens(&m.content) + calls + images
+        })
+        .sum()
+}
+
+/// Estimated tokens taken by the tool definitions sent with each request
+fn tool_definitions_tokens() -> u64 {
+    serde_json::to_string(&tools::get_available_tools())
+        .map(|s| estimate_text_tokens(&s))
+        .unwrap_or(0)
+}
+
+/// Token budget for a request before compaction kicks in
+pub fn token_budget(config: &Config) -> u64 {
+    let limit = config.context_limit(&config.current_model) as f64;
+    ((limit * COMPACT_THRESHOLD) as u64).saturating_sub(tool_definitions_tokens())
+}
+
+/// Index of the first message of the `turns` most recent user turns
+fn recent_turns_start(messages: &[Message], turns: usize) -> usize {
+    messages
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, m)| m.role == "user")
+        .nth(turns.saturating_sub(1))
+        .map_or(0, |(i, _)| i)
+}
+
+/// Index of the assistant message that made the latest tool calls (0 if there is none).
+/// Its results are what the model is about to read, so they are never elided
+fn latest_tool_batch_start(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .rposition(|m| {
+            m.role == "assistant" && m.tool_calls.as_ref().is_some_and(|c| !c.is_empty())
+        })
+        .unwrap_or(0)
+}
+
+/// Replace large tool outputs before `keep_from` with a short preview
+pub fn elide_old_tool_outputs(messages: &mut [Message], keep_from: usize) {
+    for msg in messages.iter_mut().take(keep_from) {
+        if msg.role != "tool" {
+            continue;
+        }
+        let total = msg.content.chars().count();
+        if total <= ELIDE_TOOL_OUTPUT_CHARS {
+            continue;
+        }
+        let preview: String = msg.content.chars().take(ELIDED_PREVIEW_CHARS).collect();
+        msg.content.text = format!(
+            "{}\n\n[... tool output elided to save context: {} of {} characters omitted ...]",
+            preview,
+            total - ELIDED_PREVIEW_CHARS,
+            total
+        );
+    }
+}
+
+/// Pinned message carrying the compaction summary
+pub fn summary_message(compaction: &Compaction) -> Message {
+    Message {
+        role: "system".to_string(),
+        content: format!(
+            "# Summary of earlier conversation\n\nThe earlier part of this conversation was \
+             compacted. Summary:\n\n{}",
+            compaction.summary
+        )
+        .into(),
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+        usage: None,
+        model: None,
+        thinking_blocks: Vec::new(),
+    }
+}
+
+/// Session history to send: uncompacted messages, with old tool outputs elided
+/// when they would push the request over the token budget
+pub fn history_for_request(session: &ChatSession, config: &Config, reserved: u64) -> Vec<Message> {
+    let messages = session.uncompacted_messages();
+    let mut history = messages.to_vec();
+    let budget = token_budget(config);
+    if reserved + estimate_tokens(&history) > budget {
+        elide_old_tool_outputs(
+            &mut history,
+            recent_turns_start(messages, KEEP_RECENT_TURNS),
+        );
+    }
+    // One long tool loop can overflow on its own: elide earlier outputs of the current turn too
+    if reserved + estimate_tokens(&history) > budget {
+        elide_old_tool_outputs(&mut history, latest_tool_batch_start(messages));
+    }
+    history
+}
+
+/// Whether the request built from the session would exceed the token budget
+fn needs_compaction(session: &ChatSession, config: &Config) -> bool {
+    let messages = message_builder::build_messages_with_agents_md(session, config);
+    estimate_tokens(&messages) > token_budget(config)
+}
+
+/// Summarize everything except the most recent turns into the session's compaction.
+/// Returns the number of newly summarized messages (0 if there was nothing to compact).
+pub async fn compact_session(session: &mut ChatSession, client: &ApiClient) -> Result<usize> {
+    let start = session
+        .compaction
+        .as_ref()
+        .map_or(0, |c| c.compacted_messages.min(session.messages.len()));
+
+    // Keep recent turns; fall back to only the current one if that leaves nothing to compact.
+    // The split always lands on a user message: providers such as Anthropic reject a history
+    // whose first non-system message is an assistant tool call. A single long turn is left
+    // to `history_for_request`, which elides its earlier tool outputs instead
+    let pending = &session.messages[start..];
+    let mut end = start + recent_turns_start(pending, KEEP_RECENT_TURNS);
+    if end <= start {
+        end = start + recent_turns_start(pending, 1);
+    }
+    if end <= start {
+        return Ok(0);
+    }
+
+    let i18n = get_i18n();
+    println!(
+        "\n\x1b[36m[*] {}\x1b[0m",
+        i18n.get("compact_running")
+            .replace("{}", &(end - start).to_string())
+    );
+
+    let previous = session.compaction.as_ref().map(|c| c.summary.as_str());
+    let prompt = build_summary_prompt(previous, &session.messages[start..end]);
+
+    let messages = vec![
+        Message {
+            role: "system".to_string(),
+            content: SUMMARY_SYSTEM_PROMPT.to_string().into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        },
+        Message {
+            role: "user".to_string(),
+            content: prompt.into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        },
+    ];
+
+    let response = client.chat_complete(messages).await?;
+    let summary = response.content.trim().to_string();
+    if summary.is_empty() {
+        anyhow::bail!(i18n.get("compact_empty_summary"));
+    }
+
+    session.compaction = Some(Compaction::new(summary, end));
+
+    Ok(end - start)
+}
+
+/// Compact the session if the next request would not fit the context window
+pub async fn compact_if_needed(
+    session: &mut ChatSession,
+    client: &ApiClient,
+    config: &Config,
+) -> Result<()> {
+    if !needs_compaction(session, config) {
+        return Ok(());
+    }
+
+    match compact_session(session, client).await {
+        Ok(0) => {}
+        Ok(count) => {
+            let i18n = get_i18n();
+            println!(
+                "\x1b[32m[OK]\x1b[0m {}\n",
+                i18n.get("compact_done").replace("{}", &count.to_string())
+            );
+            session.save()?;
+        }
+        Err(e) => {
+            // Not fatal: the request may still fit, otherwise the API reports the error
+            let i18n = get_i18n();
+            eprintln!("\x1b[33m[!] {}:\x1b[0m {}\n", i18n.get("compact_failed"), e);
+        }
+    }
+    Ok(())
+}
+
+fn build_summary_prompt(previous: Option<&str>, messages: &[Message]) -> String {
+    let mut prompt = String::new();
+    if let Some(previous) = previous {
+        prompt.push_str("Summary of the conversation so far:\n\n");
+        prompt.push_str(previous);
+        prompt.push_str("\n\nContinuation to merge into the summary:\n\n");
+    } else {
+        prompt.push_str("Conversation to summarize:\n\n");
+    }
+
+    for msg in messages {
+        match msg.role.as_str() {
+            "tool" => {
+                prompt.push_str(&format!(
+                    "[tool result]\n{}\n\n",
+                    truncate_chars(&msg.content, TRANSCRIPT_TOOL_OUTPUT_CHARS)
+                ));
+            }
+            role => {
+                if !msg.content.is_empty() {
+                    prompt.push_str(&format!(
+                        "[{}]\n{}\n\n",
+                        role,
+                        truncate_chars(&msg.content, TRANSCRIPT_MESSAGE_CHARS)
+                    ));
+                }
+                for tc in msg.tool_calls.iter().flatten() {
+                    prompt.push_str(&format!(
+                        "[tool call] {} {}\n\n",
+                        tc.function.name,
+                        truncate_chars(&tc.function.arguments, TRANSCRIPT_TOOL_OUTPUT_CHARS)
+                    ));
+                }
+            }
+        }
+    }
+
+    prompt.push_str("Write the updated summary now.");
+    prompt
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let truncated: String = text.chars().take(max).collect();
+        format!("{}...", truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: content.into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_recent_turns_start() {
+        let messages = vec![
+            msg("user", "a"),
+            msg("assistant", "b"),
+            msg("user", "c"),
+            msg("tool", "d"),
+            msg("user", "e"),
+        ];
+        assert_eq!(recent_turns_start(&messages, 1), 4);
+        assert_eq!(recent_turns_start(&messages, 2), 2);
+        assert_eq!(recent_turns_start(&messages, 5), 0);
+    }
+
+    #[test]
+    fn test_elide_old_tool_outputs_keeps_recent() {
+        let big = "x".repeat(ELIDE_TOOL_OUTPUT_CHARS + 100);
+        let mut messages = vec![msg("tool", &big), msg("user", "q"), msg("tool", &big)];
+        elide_old_tool_outputs(&mut messages, 1);
+
+        assert!(messages[0].content.contains("elided"));
+        assert!(messages[0].content.len() < big.len());
+        assert_eq!(messages[2].content.text, big);
+    }
+
+    fn tool_call(id: &str) -> Message {
+        Message {
+            tool_calls: Some(vec![history::ToolCall {
+                id: id.to_string(),
+                tool_type: "function".to_string(),
+                function: history::FunctionCall {
+                    name: "run_command".to_string(),
+                    arguments: r#"{"command":"cargo test"}"#.to_string(),
+                },
+            }]),
+            ..msg("assistant", "")
+        }
+    }
+
+    /// One user turn with two rounds of tool calls, the first with a huge output
+    fn long_turn_session() -> ChatSession {
+        let big = "x".repeat(ELIDE_TOOL_OUTPUT_CHARS + 100);
+        let mut session = ChatSession::new(std::env::temp_dir());
+        for message in [
+            msg("user", "fix the failing tests"),
+            tool_call("call_1"),
+            msg("tool", &big),
+            tool_call("call_2"),
+            msg("tool", &big),
+        ] {
+            session.add_message(message);
+        }
+        session
+    }
+
+    fn test_config(api_url: &str, context_limit: u64) -> Config {
+        serde_json::from_value(serde_json::json!({
+            "api_key": "test-key",
+            "api_url": api_url,
+            "current_model": "mock-model",
+            "default_context_limit": context_limit,
+            "max_retries": 0
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_history_elides_inside_current_turn() {
+        let session = long_turn_session();
+        let history = history_for_request(&session, &test_config("http://localhost", 1000), 0);
+
+        assert!(history[2].content.contains("elided"));
+        // The latest results are what the model is about to read
+        assert_eq!(history[4].content.text, session.messages[4].content.text);
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_keeps_single_turn_whole() {
+        let server = mock_llm::MockServer::start(vec![]).await.unwrap();
+        let client = ApiClient::new(test_config(&server.url(), 128_000));
+        let mut session = long_turn_session();
+
+        // Splitting inside the turn would leave an assistant tool call as the first message
+        assert_eq!(compact_session(&mut session, &client).await.unwrap(), 0);
+        assert!(session.compaction.is_none());
+        assert!(server.requests().is_empty());
+    }
+}
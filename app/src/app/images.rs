@@ -0,0 +1,70 @@
+use config::Config;
+use history::{image_media_type, ImageUrl};
+use std::path::Path;
+use ui::get_i18n;
+
+/// Characters trimmed from the end of an `@mention` (sentence punctuation)
+const MENTION_TRAILING: &[char] = &[',', ';', ':', '!', '?', ')', '"', '\'', '.'];
+
+/// Image paths mentioned as `@file.png` in user input
+pub fn image_mentions(line: &str) -> Vec<&str> {
+    line.split_whitespace()
+        .filter_map(|word| word.strip_prefix('@'))
+        .map(|path| path.trim_end_matches(MENTION_TRAILING))
+        .filter(|path| image_media_type(Path::new(path)).is_some())
+        .collect()
+}
+
+/// Load an image relative to the working directory, printing why it failed
+pub fn load_image(path: &str, working_dir: &Path) -> Option<ImageUrl> {
+    match ImageUrl::from_file(&working_dir.join(path)) {
+        Ok(image) => {
+            let i18n = get_i18n();
+            println!(
+                "\x1b[90m[i] {}\x1b[0m",
+                i18n.get("image_attached").replace("{}", path)
+            );
+            Some(image)
+        }
+        Err(e) => {
+            let i18n = get_i18n();
+            eprintln!(
+                "\x1b[33m[!] {}:\x1b[0m {}",
+                i18n.get("image_load_failed"),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Load every image mentioned in the input
+pub fn load_mentioned_images(line: &str, working_dir: &Path) -> Vec<ImageUrl> {
+    image_mentions(line)
+        .into_iter()
+        .filter_map(|path| load_image(path, working_dir))
+        .collect()
+}
+
+/// Warn when the current model's profile says it cannot see images
+pub fn warn_if_no_vision(config: &Config) {
+    let profile = config.model_profile(&config.current_model);
+    if profile.is_some_and(|(_, profile)| !profile.supports_vision()) {
+        let i18n = get_i18n();
+        eprintln!("\x1b[33m[!] {}\x1b[0m", i18n.get("image_no_vision"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_mentions() {
+        assert_eq!(
+            image_mentions("why is @shots/bug.PNG, and @diagram.webp. broken? @src/main.rs"),
+            vec!["shots/bug.PNG", "diagram.webp"]
+        );
+        assert!(image_mentions("mail me@example.com about it").is_empty());
+    }
+}
@@ -1,28 +1,36 @@
-use anyhow::Result;
 use config::Config;
 use history::{ChatSession, Message};
 use prompts;
 
+use super::context;
+
 /// Build message sequence with SYSTEM prompt and history
 /// AGENTS.md is integrated in the system prompt (loaded in real-time)
-pub fn build_messages_with_agents_md(
-    session: &ChatSession,
-    config: &Config,
-) -> Result<Vec<Message>> {
+/// Compacted history is replaced by its pinned summary
+pub fn build_messages_with_agents_md(session: &ChatSession, config: &Config) -> Vec<Message> {
     let mut messages = vec![Message {
         role: "system".to_string(),
         content: prompts::get_system_prompt(
             &config.ai_language,
             &config.current_model,
             &session.working_directory,
-        ),
+        )
+        .into(),
         tool_calls: None,
         tool_call_id: None,
         name: None,
+        usage: None,
+        model: None,
+        thinking_blocks: Vec::new(),
     }];
 
+    if let Some(compaction) = &session.compaction {
+        messages.push(context::summary_message(compaction));
+    }
+
     // Add history messages
-    messages.extend(session.messages.clone());
+    let reserved = context::estimate_tokens(&messages);
+    messages.extend(context::history_for_request(session, config, reserved));
 
-    Ok(messages)
+    messages
 }
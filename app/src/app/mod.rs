@@ -1,4 +1,6 @@
 mod command_handler;
+mod context;
+mod images;
 mod message_builder;
 mod prompt_optimizer;
 mod reedline_config;
@@ -6,7 +8,6 @@ mod reedline_prompt;
 mod repl;
 mod review;
 mod startup;
-mod terminal_ui;
 
 pub use repl::run_repl;
 pub use startup::initialize_app;
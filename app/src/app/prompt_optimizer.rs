@@ -10,41 +10,47 @@ pub async fn optimize_prompt(
 ) -> Result<String> {
     // Build context from recent messages
     let context = build_context(session);
-    
+
     // Load AGENTS.md if available (from session's working directory)
     let agents_context = load_agents_context(&session.working_directory);
-    
+
     // Create optimization request
     let system_message = create_optimization_system_prompt(&agents_context);
     let user_message = create_optimization_request(original_prompt, &context);
-    
+
     let messages = vec![
         Message {
             role: "system".to_string(),
-            content: system_message,
+            content: system_message.into(),
             tool_calls: None,
             tool_call_id: None,
             name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
         },
         Message {
             role: "user".to_string(),
-            content: user_message,
+            content: user_message.into(),
             tool_calls: None,
             tool_call_id: None,
             name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
         },
     ];
-    
+
     // Call API (non-streaming for simplicity)
     let response = api_client.chat_complete(messages).await?;
-    
+
     Ok(response.content.trim().to_string())
 }
 
 /// Build context from recent conversation
 fn build_context(session: &ChatSession) -> String {
     let messages = &session.messages;
-    
+
     // Get last 3 message pairs (user + assistant)
     let recent_messages: Vec<String> = messages
         .iter()
@@ -52,11 +58,19 @@ fn build_context(session: &ChatSession) -> String {
         .take(6)
         .rev()
         .map(|msg| {
-            let role = if msg.role == "user" { "User" } else { "Assistant" };
-            format!("{}: {}", role, msg.content.chars().take(150).collect::<String>())
+            let role = if msg.role == "user" {
+                "User"
+            } else {
+                "Assistant"
+            };
+            format!(
+                "{}: {}",
+                role,
+                msg.content.chars().take(150).collect::<String>()
+            )
         })
         .collect();
-    
+
     if recent_messages.is_empty() {
         String::from("No previous conversation")
     } else {
@@ -90,7 +90,6 @@ pub enum InputResult {
     OptimizePrompt(String),  // Shift+Enter: optimize the current input
     CtrlC,
     CtrlD,
-    Error(String),
 }
 
 pub fn process_signal(signal: Signal) -> InputResult {
@@ -60,6 +60,9 @@ pub async fn run_repl(mut state: AppState) -> Result<()> {
                             eprintln!("\n\x1b[31m[X] {}:\x1b[0m {}\n", i18n.get("error"), e);
                         }
                     }
+                    if !command_handler::record_side_usage(&mut state).is_empty() {
+                        let _ = state.session.save();
+                    }
                 }
                 InputResult::CtrlC => {
                     let i18n = get_i18n();
@@ -83,10 +86,6 @@ pub async fn run_repl(mut state: AppState) -> Result<()> {
                     println!("\n\x1b[36m{}\x1b[0m\n", i18n.get("goodbye"));
                     break;
                 }
-                InputResult::Error(err) => {
-                    let i18n = get_i18n();
-                    eprintln!("\n\x1b[31m[X] {}:\x1b[0m {}\n", i18n.get("error"), err);
-                }
             },
             Err(err) => {
                 let i18n = get_i18n();
@@ -96,6 +95,9 @@ pub async fn run_repl(mut state: AppState) -> Result<()> {
         }
     }
 
+    // Background jobs and terminal sessions do not outlive the session
+    tools::jobs::kill_all();
+    tools::pty::close_all();
     Ok(())
 }
 
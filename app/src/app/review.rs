@@ -31,8 +31,8 @@ pub fn install_review_handler(api_client: ApiClient, config: Config) {
 
         match rx.recv() {
             Ok(Ok(approved)) => Ok(approved),
-            Ok(Err(err)) => Err(io::Error::new(io::ErrorKind::Other, err.to_string())),
-            Err(recv_err) => Err(io::Error::new(io::ErrorKind::Other, recv_err.to_string())),
+            Ok(Err(err)) => Err(io::Error::other(err.to_string())),
+            Err(recv_err) => Err(io::Error::other(recv_err.to_string())),
         }
     });
 }
@@ -45,8 +45,7 @@ async fn run_review(
     let i18n = ui::get_i18n();
 
     println!(
-        "\n  {} {}",
-        "•",
+        "\n  • {}",
         i18n.get("approval_review_request")
             .replace("{}", &request.action)
     );
@@ -69,31 +68,40 @@ async fn run_review(
         preview
     );
 
-    let mut messages = Vec::with_capacity(2);
-    messages.push(Message {
-        role: "system".to_string(),
-        content: system_prompt,
-        tool_calls: None,
-        tool_call_id: None,
-        name: None,
-    });
-    messages.push(Message {
-        role: "user".to_string(),
-        content: user_prompt,
-        tool_calls: None,
-        tool_call_id: None,
-        name: None,
-    });
+    let messages = vec![
+        Message {
+            role: "system".to_string(),
+            content: system_prompt.into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        },
+        Message {
+            role: "user".to_string(),
+            content: user_prompt.into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: None,
+            model: None,
+            thinking_blocks: Vec::new(),
+        },
+    ];
 
     let (response, tool_calls, _) = chat::send_and_receive(client, messages, &session).await?;
+    if let Some(usage) = response.usage.clone() {
+        api::record_side_usage(usage);
+    }
 
     if tool_calls.is_some() {
         anyhow::bail!(i18n.get("approval_review_tool_error"));
     }
 
     println!(
-        "\r  {} {}                                                  ",
-        "✓",
+        "\r  ✓ {}                                                  ",
         i18n.get("approval_review_done")
     );
 
@@ -2,10 +2,11 @@ use super::review;
 use anyhow::Result;
 use api::ApiClient;
 use config::Config;
-use history::ChatSession;
+use history::{ChatSession, ImageUrl};
 use i18n::I18n;
 use prompts;
 use std::env;
+use std::path::{Path, PathBuf};
 use ui;
 
 /// Application startup state
@@ -15,16 +16,16 @@ pub struct AppState {
     pub session: ChatSession,
     pub api_client: ApiClient,
     pub auto_approve: bool,
+    /// Images from /image waiting to be sent with the next message
+    pub pending_images: Vec<ImageUrl>,
 }
 
 /// Initialize the application
 pub async fn initialize_app() -> Result<AppState> {
     // Check for smart approval flags
-    let smart_approve = env::args().any(|arg| 
-        arg == "--shorekeeper" || 
-        arg == "--ally-but-i-dont-fully-trust" || 
-        arg == "--ew"
-    );
+    let smart_approve = env::args().any(|arg| {
+        arg == "--shorekeeper" || arg == "--ally-but-i-dont-fully-trust" || arg == "--ew"
+    });
 
     if smart_approve {
         ui::set_smart_approval_mode(true);
@@ -37,7 +38,7 @@ pub async fn initialize_app() -> Result<AppState> {
     let force_setup = env::args().any(|arg| arg == "--setup");
 
     // Load or initialize config
-    let config = if force_setup {
+    let mut config = if force_setup {
         // Force setup regardless of existing config
         Config::initialize()?
     } else {
@@ -47,6 +48,12 @@ pub async fn initialize_app() -> Result<AppState> {
         }
     };
 
+    // Check for --record <dir> to save raw API exchanges for replay
+    config.record_dir = env::args()
+        .skip_while(|arg| arg != "--record")
+        .nth(1)
+        .map(PathBuf::from);
+
     // Create i18n instance
     let i18n = I18n::new(&config.ui_language);
 
@@ -76,6 +83,13 @@ pub async fn initialize_app() -> Result<AppState> {
 
     // Create API client
     let api_client = ApiClient::new(config.clone());
+    if let Some(dir) = &config.record_dir {
+        println!(
+            "\x1b[36m[*]\x1b[0m \x1b[2m{}\x1b[0m \x1b[90m{}\x1b[0m\n",
+            i18n.get("api_recording_to"),
+            dir.display()
+        );
+    }
 
     // Install review handler for approval prompts
     review::install_review_handler(api_client.clone(), config.clone());
@@ -83,11 +97,46 @@ pub async fn initialize_app() -> Result<AppState> {
     // Print welcome message
     prompts::print_welcome(&config, &i18n);
 
+    // Start MCP servers and register their tools
+    mcp::start_servers(&config).await;
+
+    // Project-defined script tools
+    load_script_tools(&session.working_directory, &i18n);
+
     Ok(AppState {
         config,
         i18n,
         session,
         api_client,
         auto_approve,
+        pending_images: Vec::new(),
     })
 }
+
+/// Register the tools declared in `.friendev/tools.toml`
+fn load_script_tools(working_dir: &Path, i18n: &I18n) {
+    match tools::register_script_tools(working_dir) {
+        Ok((registered, skipped)) => {
+            if !registered.is_empty() {
+                println!(
+                    "\x1b[90m[i] {}\x1b[0m",
+                    i18n.get("script_tools_loaded")
+                        .replacen("{}", &registered.len().to_string(), 1)
+                        .replacen("{}", tools::SCRIPT_TOOLS_FILE, 1)
+                );
+            }
+            if !skipped.is_empty() {
+                eprintln!(
+                    "\x1b[33m[!] {}:\x1b[0m {}",
+                    i18n.get("script_tools_skipped"),
+                    skipped.join(", ")
+                );
+            }
+        }
+        Err(e) => eprintln!(
+            "\x1b[33m[!] {}:\x1b[0m {:#}",
+            i18n.get("script_tools_invalid"),
+            e
+        ),
+    }
+}
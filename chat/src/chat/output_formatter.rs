@@ -41,9 +41,7 @@ pub fn print_tool_call_separator() -> std::io::Result<()> {
 /// Finalize output formatting
 pub fn finalize_output(has_reasoning: bool, content_empty: bool) -> std::io::Result<()> {
     // Ensure proper newline at the end
-    if has_reasoning {
-        println!();
-    } else if !content_empty {
+    if has_reasoning || !content_empty {
         println!();
     }
     Ok(())
@@ -66,3 +64,12 @@ pub fn print_tool_parse_error() {
     );
     let _ = enhanced_output::print_warning(&debug_msg);
 }
+
+/// Note that a fallback model answered instead of the configured one
+pub fn print_answered_by(model: &str) {
+    let i18n = get_i18n();
+    println!(
+        "\x1b[90m[i] {}\x1b[0m\n",
+        i18n.get("chat_answered_by").replace("{}", model)
+    );
+}
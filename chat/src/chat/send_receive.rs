@@ -20,17 +20,24 @@ pub async fn send_and_receive(
     let stream = client.chat_stream_with_retry(messages).await?;
 
     // Handle stream chunks (with ESC interruption support)
-    let (content, tool_accumulator, has_tool_calls, interrupted) =
+    let (content, tool_accumulator, has_tool_calls, interrupted, usage, model, thinking_blocks) =
         stream_handler::handle_stream_chunks(stream).await?;
-    
+
+    if let Some(model) = model.as_deref().filter(|m| *m != client.model()) {
+        output_formatter::print_answered_by(model);
+    }
+
     // If interrupted, return empty response
     if interrupted {
         let message = Message {
             role: "assistant".to_string(),
-            content: content + "\n[生成已中断]",
+            content: (content + "\n[生成已中断]").into(),
             tool_calls: None,
             tool_call_id: None,
             name: None,
+            usage,
+            model,
+            thinking_blocks: Vec::new(),
         };
         return Ok((message, None, HashMap::new()));
     }
@@ -52,10 +59,13 @@ pub async fn send_and_receive(
 
     let message = Message {
         role: "assistant".to_string(),
-        content,
+        content: content.into(),
         tool_calls: tool_calls.clone(),
         tool_call_id: None,
         name: None,
+        usage,
+        model,
+        thinking_blocks,
     };
 
     Ok((message, tool_calls, displays))
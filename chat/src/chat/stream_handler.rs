@@ -3,18 +3,30 @@ use anyhow::Result;
 use api::{StreamChunk, ToolCallAccumulator};
 use crossterm::event::{poll, read, Event, KeyCode};
 use futures::StreamExt;
+use history::Usage;
 use std::time::Duration;
 
 /// Process stream chunks and handle output with ESC key interruption support
 pub async fn handle_stream_chunks(
     stream: impl futures::Stream<Item = Result<StreamChunk>> + Unpin,
-) -> Result<(String, ToolCallAccumulator, bool, bool)> {
+) -> Result<(
+    String,
+    ToolCallAccumulator,
+    bool,
+    bool,
+    Option<Usage>,
+    Option<String>,
+    Vec<serde_json::Value>,
+)> {
     let mut stream = Box::pin(stream);
 
     let mut content = String::new();
     let mut tool_accumulator = ToolCallAccumulator::new();
     let mut has_tool_calls = false;
     let mut interrupted = false;
+    let mut usage = None;
+    let mut model = None;
+    let mut thinking_blocks = Vec::new();
 
     let mut is_first_reasoning = true;
     let mut has_reasoning = false;
@@ -40,7 +52,9 @@ pub async fn handle_stream_chunks(
                     &mut has_reasoning,
                 )?;
             }
+            StreamChunk::ThinkingBlock(block) => thinking_blocks.push(block),
             StreamChunk::ToolCall {
+                index,
                 id,
                 name,
                 arguments,
@@ -55,12 +69,18 @@ pub async fn handle_stream_chunks(
                     has_tool_calls = true;
                 }
                 // Accumulate tool call data (will display in real-time)
-                tool_accumulator.add_chunk(id, name, arguments);
+                tool_accumulator.add_chunk(index, id, name, arguments);
             }
             StreamChunk::FinishReason(reason) => {
                 // Record finish reason
                 tool_accumulator.set_finish_reason(reason);
             }
+            StreamChunk::Usage(u) => {
+                usage = Some(u);
+            }
+            StreamChunk::Model(name) => {
+                model = Some(name);
+            }
             StreamChunk::Done => break,
         }
     }
@@ -70,7 +90,15 @@ pub async fn handle_stream_chunks(
         output_formatter::finalize_output(has_reasoning, content.is_empty())?;
     }
 
-    Ok((content, tool_accumulator, has_tool_calls, interrupted))
+    Ok((
+        content,
+        tool_accumulator,
+        has_tool_calls,
+        interrupted,
+        usage,
+        model,
+        thinking_blocks,
+    ))
 }
 
 /// Check if ESC key is pressed (non-blocking)
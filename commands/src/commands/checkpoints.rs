@@ -0,0 +1,124 @@
+use ::history::ChatSession;
+use anyhow::Result;
+use i18n::I18n;
+use tools::checkpoint::{self, Restored};
+
+/// Handle /undo [--force]: revert the files edited during the last turn
+pub fn handle_undo_command(parts: &[&str], session: &ChatSession, i18n: &I18n) -> Result<()> {
+    let force = parts.contains(&"--force");
+    let restored = checkpoint::undo(session.id, force)?;
+    if restored.checkpoints.is_empty() {
+        println!(
+            "\n\x1b[90m[i] {}\x1b[0m\n",
+            i18n.get("checkpoint_nothing_to_undo")
+        );
+        return Ok(());
+    }
+    print_restored(&restored, "/undo --force", i18n);
+    Ok(())
+}
+
+/// Handle /restore <id> [--force]: revert a checkpoint and every later one
+pub fn handle_restore_command(parts: &[&str], session: &ChatSession, i18n: &I18n) -> Result<()> {
+    let Some(id) = parts.get(1).and_then(|id| id.parse::<u32>().ok()) else {
+        println!(
+            "\n\x1b[33m[!] {}\x1b[0m\n",
+            i18n.get("checkpoint_restore_usage")
+        );
+        return Ok(());
+    };
+    let checkpoints = checkpoint::list_checkpoints(session.id)?;
+    match checkpoints.iter().find(|c| c.id == id) {
+        None => {
+            let tmpl = i18n.get("checkpoint_not_found");
+            println!(
+                "\n\x1b[31m[X] {}\x1b[0m\n",
+                tmpl.replace("{}", &id.to_string())
+            );
+            return Ok(());
+        }
+        Some(c) if c.undone => {
+            let tmpl = i18n.get("checkpoint_already_undone");
+            println!(
+                "\n\x1b[90m[i] {}\x1b[0m\n",
+                tmpl.replace("{}", &id.to_string())
+            );
+            return Ok(());
+        }
+        Some(_) => {}
+    }
+
+    let force = parts.contains(&"--force");
+    let restored = checkpoint::restore(session.id, id, force)?;
+    print_restored(&restored, &format!("/restore {} --force", id), i18n);
+    Ok(())
+}
+
+/// Handle /checkpoints: list the checkpoints of the current session
+pub fn handle_checkpoints_command(session: &ChatSession, i18n: &I18n) -> Result<()> {
+    let checkpoints = checkpoint::list_checkpoints(session.id)?;
+    if checkpoints.is_empty() {
+        println!("\n\x1b[90m[i] {}\x1b[0m\n", i18n.get("checkpoint_none"));
+        return Ok(());
+    }
+
+    println!("\n\x1b[1;33m{}:\x1b[0m", i18n.get("checkpoint_title"));
+    for c in &checkpoints {
+        let files = i18n
+            .get("checkpoint_files")
+            .replace("{}", &c.files.len().to_string());
+        let undone = if c.undone {
+            format!(" \x1b[90m[{}]\x1b[0m", i18n.get("checkpoint_undone"))
+        } else {
+            String::new()
+        };
+        println!(
+            "  \x1b[36m#{:<4}\x1b[0m \x1b[90m{}\x1b[0m  {:<10} {}{}",
+            c.id,
+            c.created_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            files,
+            c.label,
+            undone
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+fn print_restored(restored: &Restored, force_command: &str, i18n: &I18n) {
+    if restored.files.is_empty() && !restored.conflicts.is_empty() {
+        println!("\n\x1b[33m[!] {}\x1b[0m", i18n.get("checkpoint_conflicts"));
+        for path in &restored.conflicts {
+            println!("    \x1b[33m·\x1b[0m {}", path.display());
+        }
+        let hint = i18n.get("checkpoint_force_hint");
+        println!(
+            "\n\x1b[90m[i] {}\x1b[0m\n",
+            hint.replace("{}", force_command)
+        );
+        return;
+    }
+
+    let ids: Vec<String> = restored
+        .checkpoints
+        .iter()
+        .map(|id| format!("#{}", id))
+        .collect();
+    let tmpl = i18n.get("checkpoint_restored");
+    println!(
+        "\n\x1b[32m[OK]\x1b[0m {}",
+        tmpl.replace("{}", &ids.join(", "))
+    );
+    for path in &restored.files {
+        let marker = if restored.conflicts.contains(path) {
+            "\x1b[33m!\x1b[0m"
+        } else {
+            "\x1b[32m·\x1b[0m"
+        };
+        println!("    {} {}", marker, path.display());
+    }
+    println!();
+}
@@ -0,0 +1,231 @@
+use anyhow::Result;
+use api::ApiClient;
+use config::Config;
+use dialoguer::{theme::ColorfulTheme, Editor, Select};
+use ::history::Message;
+use i18n::I18n;
+use std::path::Path;
+use tools::git;
+use ui::get_i18n;
+
+/// Diff characters sent to the model when drafting a message
+const MAX_DIFF_CHARS: usize = 12_000;
+
+const COMMIT_MESSAGE_PROMPT: &str = "You write git commit messages. Given a diff, reply with \
+only the commit message: an imperative subject line of at most 72 characters, then, if the \
+change needs explaining, a blank line and a short body wrapped at 72 characters. Describe what \
+changed and why, not how. No code fences, quotes or commentary.";
+
+/// Handle /commit: draft a message for the staged changes, let the user edit it, and commit
+/// `/commit auto [on|off]` shows or toggles committing every assistant turn
+pub async fn handle_commit_command(
+    parts: &[&str],
+    config: &mut Config,
+    working_dir: &Path,
+    api_client: &ApiClient,
+    i18n: &I18n,
+) -> Result<()> {
+    if parts.get(1) == Some(&"auto") {
+        return handle_auto_commit(parts.get(2).copied(), config, i18n);
+    }
+
+    if git::repo_root(working_dir).is_none() {
+        let tmpl = i18n.get("git_not_repository");
+        println!(
+            "\n\x1b[31m[X] {}\x1b[0m\n",
+            tmpl.replace("{}", &working_dir.display().to_string())
+        );
+        return Ok(());
+    }
+    let diff = git::staged_diff(working_dir)?;
+    if diff.trim().is_empty() {
+        println!(
+            "\n\x1b[33m[!] {}\x1b[0m\n",
+            i18n.get("commit_nothing_staged")
+        );
+        return Ok(());
+    }
+
+    println!("\n\x1b[36m[*] {}\x1b[0m", i18n.get("commit_drafting"));
+    let mut message = match draft_commit_message(api_client, &diff).await {
+        Ok(message) => message,
+        Err(e) => {
+            eprintln!(
+                "\x1b[33m[!] {}:\x1b[0m {}",
+                i18n.get("commit_draft_failed"),
+                e
+            );
+            String::new()
+        }
+    };
+
+    let choices = [
+        i18n.get("commit_choice_commit"),
+        i18n.get("commit_choice_edit"),
+        i18n.get("commit_choice_cancel"),
+    ];
+    loop {
+        if !message.is_empty() {
+            println!("\n\x1b[1;33m{}:\x1b[0m", i18n.get("commit_message"));
+            for line in message.lines() {
+                println!("  {}", line);
+            }
+            println!();
+        }
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&choices)
+            .default(if message.is_empty() { 1 } else { 0 })
+            .interact_opt()?;
+        match selection {
+            Some(0) if !message.trim().is_empty() => break,
+            Some(0) | Some(1) => {
+                if let Some(edited) = Editor::new().edit(&message)? {
+                    message = edited.trim().to_string();
+                }
+            }
+            _ => {
+                println!("\n\x1b[90m[i] {}\x1b[0m\n", i18n.get("commit_cancelled"));
+                return Ok(());
+            }
+        }
+    }
+
+    let hash = git::commit(working_dir, &message)?;
+    print_committed(&hash, &message, i18n);
+    Ok(())
+}
+
+fn handle_auto_commit(arg: Option<&str>, config: &mut Config, i18n: &I18n) -> Result<()> {
+    match arg {
+        Some("on") => config.update_auto_commit(true)?,
+        Some("off") => config.update_auto_commit(false)?,
+        None => {}
+        Some(_) => {
+            println!("\n\x1b[33m[!] {}\x1b[0m\n", i18n.get("commit_auto_usage"));
+            return Ok(());
+        }
+    }
+    let key = if config.auto_commit {
+        "commit_auto_on"
+    } else {
+        "commit_auto_off"
+    };
+    println!("\n\x1b[32m[OK]\x1b[0m {}\n", i18n.get(key));
+    Ok(())
+}
+
+/// Ask the model for a commit message describing `diff`
+pub async fn draft_commit_message(api_client: &ApiClient, diff: &str) -> Result<String> {
+    let mut diff_text: String = diff.chars().take(MAX_DIFF_CHARS).collect();
+    if diff_text.len() < diff.len() {
+        diff_text.push_str("\n[... diff truncated ...]");
+    }
+    let message = |role: &str, content: String| Message {
+        role: role.to_string(),
+        content: content.into(),
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+        usage: None,
+        model: None,
+        thinking_blocks: Vec::new(),
+    };
+    let messages = vec![
+        message("system", COMMIT_MESSAGE_PROMPT.to_string()),
+        message("user", diff_text),
+    ];
+
+    let response = api_client.chat_complete(messages).await?;
+    let draft = clean_message(&response.content);
+    if draft.is_empty() {
+        anyhow::bail!(get_i18n().get("commit_draft_empty"));
+    }
+    Ok(draft)
+}
+
+/// Commit the files the tools edited during the last turn when auto-commit is on.
+/// Other staged changes are left alone; failures only print a warning.
+pub async fn auto_commit_turn(config: &Config, working_dir: &Path, api_client: &ApiClient) {
+    if !config.auto_commit {
+        return;
+    }
+    let files = tools::checkpoint::turn_files();
+    if files.is_empty() {
+        return;
+    }
+    let Some(root) = git::repo_root(working_dir) else {
+        return;
+    };
+
+    let i18n = I18n::new(&config.ui_language);
+    let result = async {
+        let (paths, diff) = git::stage_paths(&root, &files)?;
+        if diff.trim().is_empty() {
+            return Ok(None);
+        }
+        let message = match draft_commit_message(api_client, &diff).await {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!(
+                    "\x1b[33m[!] {}:\x1b[0m {}",
+                    i18n.get("commit_draft_failed"),
+                    e
+                );
+                i18n.get("commit_fallback_message")
+                    .replace("{}", &paths.join(", "))
+            }
+        };
+        let hash = git::commit_paths(&root, &paths, &message)?;
+        anyhow::Ok(Some((hash, message)))
+    }
+    .await;
+
+    match result {
+        Ok(Some((hash, message))) => print_committed(&hash, &message, &i18n),
+        Ok(None) => {}
+        Err(e) => eprintln!(
+            "\x1b[33m[!] {}:\x1b[0m {}\n",
+            i18n.get("commit_auto_failed"),
+            e
+        ),
+    }
+}
+
+/// Strip code fences and surrounding quotes that models like to add
+fn clean_message(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .trim()
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .collect();
+    lines
+        .join("\n")
+        .trim()
+        .trim_matches(|c| c == '"' || c == '`')
+        .trim()
+        .to_string()
+}
+
+fn print_committed(hash: &str, message: &str, i18n: &I18n) {
+    let subject = message.lines().next().unwrap_or_default();
+    println!(
+        "\x1b[32m[OK]\x1b[0m {} \x1b[90m{}\x1b[0m {}\n",
+        i18n.get("commit_done"),
+        hash,
+        subject
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_message() {
+        assert_eq!(
+            clean_message("```\nAdd git tools\n\nWith a body.\n```\n"),
+            "Add git tools\n\nWith a body."
+        );
+        assert_eq!(clean_message("\"Fix typo\""), "Fix typo");
+    }
+}
@@ -71,6 +71,60 @@ pub fn print_help(i18n: &I18n) {
         "/help".cyan(),
         i18n.get("cmd_help").dimmed()
     );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/usage".cyan(),
+        i18n.get("cmd_usage").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/compact".cyan(),
+        i18n.get("cmd_compact").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/mcp".cyan(),
+        i18n.get("cmd_mcp").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/commit".cyan(),
+        i18n.get("cmd_commit").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/commit auto [on|off]".cyan(),
+        i18n.get("cmd_commit_auto").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/undo [--force]".cyan(),
+        i18n.get("cmd_undo").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/checkpoints".cyan(),
+        i18n.get("cmd_checkpoints").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/restore <id> [--force]".cyan(),
+        i18n.get("cmd_restore").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/image <path> [msg]".cyan(),
+        i18n.get("cmd_image").dimmed()
+    );
     println!(
         "  {} {:25} {}",
         "·".bright_black(),
@@ -107,8 +161,20 @@ pub fn print_help(i18n: &I18n) {
     println!(
         "  {} {:25} {}",
         "·".bright_black(),
-        "/runcommand info <id>".cyan(),
-        i18n.get("cmd_runcommand_info").dimmed()
+        "/jobs".cyan(),
+        i18n.get("cmd_jobs").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/jobs tail <id> [lines]".cyan(),
+        i18n.get("cmd_jobs_tail").dimmed()
+    );
+    println!(
+        "  {} {:25} {}",
+        "·".bright_black(),
+        "/jobs kill <id>".cyan(),
+        i18n.get("cmd_jobs_kill").dimmed()
     );
 
     println!("\n{}", "═".repeat(60).bright_black());
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use i18n::I18n;
+use tools::jobs::{self, Job, KillOutcome};
+
+/// Log lines shown by /jobs tail when no count is given
+const DEFAULT_TAIL_LINES: usize = 30;
+
+/// Handle /jobs, /jobs tail <id> [lines] and /jobs kill <id>
+pub fn handle_jobs_command(parts: &[&str], i18n: &I18n) -> Result<()> {
+    match parts.get(1) {
+        None => list_jobs(i18n),
+        Some(&"tail") => {
+            let Some(job) = job_arg(parts, "/jobs tail <id> [lines]", i18n) else {
+                return Ok(());
+            };
+            let lines = parts
+                .get(3)
+                .and_then(|lines| lines.parse().ok())
+                .unwrap_or(DEFAULT_TAIL_LINES);
+            tail_job(&job, lines, i18n);
+        }
+        Some(&"kill") => {
+            let Some(job) = job_arg(parts, "/jobs kill <id>", i18n) else {
+                return Ok(());
+            };
+            match jobs::kill(job.id, true) {
+                KillOutcome::Killed(job) => println!(
+                    "\n\x1b[32m[OK]\x1b[0m {}\n",
+                    i18n.get("command_kill_done")
+                        .replace("{}", &job.id.to_string())
+                ),
+                KillOutcome::NotRunning(job) => println!(
+                    "\n\x1b[90m[i] {}\x1b[0m\n",
+                    i18n.get("command_kill_not_running")
+                        .replacen("{}", &job.id.to_string(), 1)
+                        .replacen("{}", &job.status_text(), 1)
+                ),
+                KillOutcome::NotFound => {}
+            }
+        }
+        Some(_) => println!(
+            "\n\x1b[33m[!] {}:\x1b[0m /jobs [tail <id> [lines] | kill <id>]\n",
+            i18n.get("usage")
+        ),
+    }
+    Ok(())
+}
+
+fn list_jobs(i18n: &I18n) {
+    let jobs = jobs::list();
+    if jobs.is_empty() {
+        println!("\n\x1b[90m[i] {}\x1b[0m\n", i18n.get("job_none"));
+        return;
+    }
+
+    println!("\n\x1b[1;33m{}:\x1b[0m", i18n.get("jobs_header"));
+    for job in &jobs {
+        let color = if job.is_running() { "32" } else { "90" };
+        let pid = job.pid.map(|pid| pid.to_string()).unwrap_or_default();
+        println!(
+            "  \x1b[36m#{:<4}\x1b[0m \x1b[90m{}\x1b[0m  \x1b[{}m{:<24}\x1b[0m \x1b[90mpid {:<8}\x1b[0m {}",
+            job.id,
+            job.started_at
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S"),
+            color,
+            job.status_text(),
+            pid,
+            job.command
+        );
+    }
+    println!();
+}
+
+fn tail_job(job: &Job, lines: usize, i18n: &I18n) {
+    let tail = match jobs::tail(job, lines) {
+        Ok(tail) => tail,
+        Err(e) => {
+            eprintln!(
+                "\n\x1b[31m[X] {}:\x1b[0m {}\n",
+                i18n.get("jobs_read_log_failed"),
+                e
+            );
+            return;
+        }
+    };
+
+    println!(
+        "\n\x1b[1;33m{}\x1b[0m \x1b[90m({}, {})\x1b[0m",
+        i18n.get("jobs_tail_header")
+            .replacen("{}", &job.id.to_string(), 1)
+            .replacen("{}", &lines.to_string(), 1),
+        job.status_text(),
+        job.log_path.display()
+    );
+    if tail.is_empty() {
+        println!("  \x1b[90m{}\x1b[0m", i18n.get("jobs_tail_empty"));
+    } else {
+        for line in tail.lines() {
+            println!("  {}", line);
+        }
+    }
+    println!();
+}
+
+/// The job named by the second argument; prints usage or an error when there is none
+fn job_arg(parts: &[&str], usage: &str, i18n: &I18n) -> Option<Job> {
+    let Some(id) = parts
+        .get(2)
+        .and_then(|id| id.trim_start_matches('#').parse().ok())
+    else {
+        println!("\n\x1b[33m[!] {}:\x1b[0m {}\n", i18n.get("usage"), usage);
+        return None;
+    };
+    let job = jobs::get(id);
+    if job.is_none() {
+        println!(
+            "\n\x1b[31m[X] {}\x1b[0m\n",
+            i18n.get("job_not_found").replace("{}", &id.to_string())
+        );
+    }
+    job
+}
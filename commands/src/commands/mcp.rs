@@ -0,0 +1,40 @@
+use anyhow::Result;
+use i18n::I18n;
+use mcp::{server_statuses, ServerState};
+
+/// Handle /mcp command: list servers, their status and tools
+pub fn handle_mcp_command(i18n: &I18n) -> Result<()> {
+    let statuses = server_statuses();
+    if statuses.is_empty() {
+        println!("\n\x1b[90m[i] {}\x1b[0m\n", i18n.get("mcp_none"));
+        return Ok(());
+    }
+
+    println!("\n\x1b[1;33m{}:\x1b[0m", i18n.get("mcp_title"));
+    for status in &statuses {
+        let state = match &status.state {
+            ServerState::Connected { .. } => {
+                format!("\x1b[32m{}\x1b[0m", i18n.get("mcp_status_connected"))
+            }
+            ServerState::Failed(_) => format!("\x1b[31m{}\x1b[0m", i18n.get("mcp_status_failed")),
+            ServerState::Disabled => format!("\x1b[90m{}\x1b[0m", i18n.get("mcp_status_disabled")),
+        };
+        println!(
+            "\n  \x1b[36m{}\x1b[0m [{}] \x1b[90m{}\x1b[0m",
+            status.name, state, status.target
+        );
+        match &status.state {
+            ServerState::Connected { tools } => {
+                for (name, description) in tools {
+                    let summary = description.lines().next().unwrap_or("");
+                    println!("    \x1b[32m·\x1b[0m {} \x1b[90m{}\x1b[0m", name, summary);
+                }
+            }
+            ServerState::Failed(error) => println!("    \x1b[31m{}\x1b[0m", error),
+            ServerState::Disabled => {}
+        }
+    }
+    println!();
+
+    Ok(())
+}
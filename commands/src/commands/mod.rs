@@ -1,9 +1,14 @@
 mod agents;
+mod checkpoints;
+mod commit;
 mod help;
 mod history;
+mod jobs;
 mod language;
+mod mcp;
 mod model;
 mod runcommand;
+mod usage;
 
 use anyhow::Result;
 
@@ -13,6 +18,7 @@ use config::Config;
 use i18n::I18n;
 
 pub use agents::handle_agents_md_command;
+pub use commit::{auto_commit_turn, draft_commit_message};
 pub use help::print_help;
 
 /// Handle command - returns Ok(()) if successfully processed, Err if error
@@ -28,6 +34,8 @@ pub async fn handle_command(
     match parts.first() {
         Some(&"/exit") => {
             println!("\n\x1b[36m{}\x1b[0m\n", i18n.get("goodbye"));
+            tools::jobs::kill_all();
+            tools::pty::close_all();
             std::process::exit(0);
         }
         Some(&"/help") => {
@@ -45,6 +53,34 @@ pub async fn handle_command(
         Some(&"/agents.md") => {
             handle_agents_md_command(session, &i18n).await?;
         }
+        Some(&"/usage") => {
+            usage::handle_usage_command(config, session, &i18n)?;
+        }
+        Some(&"/mcp") => {
+            mcp::handle_mcp_command(&i18n)?;
+        }
+        Some(&"/commit") => {
+            commit::handle_commit_command(
+                &parts,
+                config,
+                &session.working_directory,
+                api_client,
+                &i18n,
+            )
+            .await?;
+        }
+        Some(&"/undo") => {
+            checkpoints::handle_undo_command(&parts, session, &i18n)?;
+        }
+        Some(&"/checkpoints") => {
+            checkpoints::handle_checkpoints_command(session, &i18n)?;
+        }
+        Some(&"/restore") => {
+            checkpoints::handle_restore_command(&parts, session, &i18n)?;
+        }
+        Some(&"/jobs") => {
+            jobs::handle_jobs_command(&parts, &i18n)?;
+        }
         Some(&"/runcommand") => {
             runcommand::handle_run_command_command(&parts, &i18n)?;
         }
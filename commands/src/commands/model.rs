@@ -34,6 +34,7 @@ pub async fn handle_model_command(
                 
                 let success_msg = format!("{} {}", i18n.get("switched_model"), model_name);
                 let _ = enhanced_output::print_success(&success_msg);
+                print_model_profile(config, i18n);
             } else {
                 println!(
                     "\n\x1b[33m[!] {}:\x1b[0m /model switch <model_name>\n",
@@ -67,6 +68,7 @@ pub async fn handle_model_command(
                                 
                                 let success_msg = format!("{} {}", i18n.get("switched_model"), selected_model);
                                 let _ = enhanced_output::print_success(&success_msg);
+                                print_model_profile(config, i18n);
                             }
                         }
                         Ok(None) => {
@@ -105,3 +107,15 @@ pub async fn handle_model_command(
     }
     Ok(())
 }
+
+/// Show the request profile that applies to the newly selected model
+fn print_model_profile(config: &Config, i18n: &I18n) {
+    if let Some((pattern, profile)) = config.model_profile(&config.current_model) {
+        println!(
+            "\x1b[90m[i] {} {}: {}\x1b[0m\n",
+            i18n.get("model_profile_applied"),
+            pattern,
+            profile.summary()
+        );
+    }
+}
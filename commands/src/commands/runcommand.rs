@@ -94,54 +94,6 @@ pub fn handle_run_command_command(parts: &[&str], i18n: &I18n) -> Result<()> {
                 );
             }
         }
-        Some(&"info") => {
-            if let Some(id_str) = parts.get(2) {
-                match CommandConfig::load() {
-                    Ok(config) => {
-                        if let Some(cmd) = config.get_background_command(id_str) {
-                            println!("\n\x1b[1;33m{}:\x1b[0m", i18n.get("runcommand_info_header"));
-                            println!("  {} {}", i18n.get("runcommand_info_id"), cmd.id);
-                            println!("  {} {}", i18n.get("runcommand_info_command"), cmd.command);
-                            println!("  {} {}", i18n.get("runcommand_info_status"), cmd.status);
-                            println!(
-                                "  {} {}",
-                                i18n.get("runcommand_info_started"),
-                                cmd.start_time.format("%Y-%m-%d %H:%M:%S UTC")
-                            );
-
-                            if let Some(code) = cmd.exit_code {
-                                println!("  {} {}", i18n.get("runcommand_info_exit_code"), code);
-                            }
-
-                            if let Some(output) = &cmd.output {
-                                println!(
-                                    "\n\x1b[1;33m{}:\x1b[0m",
-                                    i18n.get("runcommand_info_output")
-                                );
-                                println!("  {}", output.replace("\n", "\n  "));
-                            }
-
-                            println!();
-                        } else {
-                            println!(
-                                "\n\x1b[31m[X] {}\x1b[0m\n",
-                                i18n.get("runcommand_info_not_found").replace("{}", id_str)
-                            );
-                        }
-                    }
-                    Err(e) => eprintln!(
-                        "\n\x1b[31m[X] {}:\x1b[0m {}\n",
-                        i18n.get("runcommand_load_config_failed"),
-                        e
-                    ),
-                }
-            } else {
-                println!(
-                    "\n\x1b[33m[!] {}:\x1b[0m /runcommand info <id>\n",
-                    i18n.get("usage")
-                );
-            }
-        }
         _ => {
             println!(
                 "\n\x1b[33m[?] {}:\x1b[0m",
@@ -156,13 +108,9 @@ pub fn handle_run_command_command(parts: &[&str], i18n: &I18n) -> Result<()> {
                 i18n.get("cmd_runcommand_add")
             );
             println!(
-                "    \x1b[36m/runcommand\x1b[0m del <cmd>   {}",
+                "    \x1b[36m/runcommand\x1b[0m del <cmd>   {}\n",
                 i18n.get("cmd_runcommand_del")
             );
-            println!(
-                "    \x1b[36m/runcommand\x1b[0m info <id>   {}\n",
-                i18n.get("cmd_runcommand_info")
-            );
         }
     }
     Ok(())
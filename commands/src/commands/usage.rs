@@ -0,0 +1,69 @@
+use anyhow::Result;
+
+use ::history::{ChatSession, UsageTotals};
+use config::Config;
+use i18n::I18n;
+
+/// Handle /usage command
+pub fn handle_usage_command(config: &Config, session: &ChatSession, i18n: &I18n) -> Result<()> {
+    // Use the in-memory copy of the current session, the saved one may lag behind
+    let mut sessions: Vec<ChatSession> = ChatSession::list_all()?
+        .into_iter()
+        .filter(|s| s.id != session.id)
+        .collect();
+    sessions.push(session.clone());
+
+    let session_totals = UsageTotals::collect(session.usages(), config);
+    let today_totals = UsageTotals::collect(
+        sessions
+            .iter()
+            .flat_map(|s| s.usages())
+            .filter(|u| u.is_today()),
+        config,
+    );
+    let project_totals = UsageTotals::collect(
+        sessions
+            .iter()
+            .filter(|s| s.working_directory == session.working_directory)
+            .flat_map(|s| s.usages()),
+        config,
+    );
+
+    if project_totals.requests == 0 && today_totals.requests == 0 {
+        println!("\n\x1b[90m[i] {}\x1b[0m\n", i18n.get("usage_none"));
+        return Ok(());
+    }
+
+    println!("\n\x1b[1;33m{}:\x1b[0m", i18n.get("usage_title"));
+    print_totals_row(&i18n.get("usage_session"), &session_totals, i18n);
+    print_totals_row(&i18n.get("usage_today"), &today_totals, i18n);
+    print_totals_row(&i18n.get("usage_project"), &project_totals, i18n);
+
+    let unpriced = today_totals
+        .unpriced_requests
+        .max(project_totals.unpriced_requests);
+    if unpriced > 0 {
+        println!(
+            "\n  \x1b[90m[i] {}\x1b[0m",
+            i18n.get("usage_unpriced")
+                .replace("{}", &unpriced.to_string())
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+fn print_totals_row(label: &str, totals: &UsageTotals, i18n: &I18n) {
+    println!(
+        "  \x1b[36m{:10}\x1b[0m {:>5} {}  {:>10} {}  {:>9} {}  \x1b[32m${:.4}\x1b[0m",
+        label,
+        totals.requests,
+        i18n.get("usage_requests"),
+        totals.prompt_tokens,
+        i18n.get("usage_input"),
+        totals.completion_tokens,
+        i18n.get("usage_output"),
+        totals.cost
+    );
+}
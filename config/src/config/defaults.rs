@@ -8,6 +8,37 @@ pub fn default_retry_delay_ms() -> u64 {
     300
 }
 
+/// Default run_command timeout in seconds
+pub const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 300;
+
+/// Default run_command timeout in seconds
+pub fn default_command_timeout_secs() -> u64 {
+    DEFAULT_COMMAND_TIMEOUT_SECS
+}
+
+/// Default context window size in tokens
+pub fn default_context_limit() -> u64 {
+    128_000
+}
+
+/// Supported provider backends (wire protocols), first one is the default
+pub const SUPPORTED_PROVIDERS: &[&str] = &["openai", "anthropic", "ollama", "openai-responses"];
+
+/// Default provider backend (OpenAI-compatible chat completions)
+pub fn default_provider() -> String {
+    SUPPORTED_PROVIDERS[0].to_string()
+}
+
+/// Default base URL for a provider backend
+pub fn default_api_url(provider: &str) -> String {
+    match provider {
+        "anthropic" => "https://api.anthropic.com/v1",
+        "ollama" => "http://localhost:11434",
+        _ => "https://api.openai.com/v1",
+    }
+    .to_string()
+}
+
 use i18n::SUPPORTED_LANGUAGES;
 
 /// Default UI language (first supported language)
@@ -1,6 +1,7 @@
 mod defaults;
 mod paths;
 mod persistence;
+mod profiles;
 mod setup;
 mod types;
 mod updates;
@@ -8,7 +9,8 @@ mod updates;
 use anyhow::Result;
 
 // Re-export public API
-pub use types::Config;
+pub use defaults::{DEFAULT_COMMAND_TIMEOUT_SECS, SUPPORTED_PROVIDERS};
+pub use types::{Config, FallbackModel, McpServerConfig, ModelPrice, ModelProfile};
 
 impl Config {
     /// Get or create config directory
@@ -50,4 +52,83 @@ impl Config {
     pub fn update_ai_language(&mut self, language: String) -> Result<()> {
         updates::update_ai_language(self, language)
     }
+
+    /// Enable or disable auto-commit
+    pub fn update_auto_commit(&mut self, enabled: bool) -> Result<()> {
+        updates::update_auto_commit(self, enabled)
+    }
+
+    /// Context window size in tokens for a model
+    pub fn context_limit(&self, model: &str) -> u64 {
+        self.context_limits
+            .get(model)
+            .copied()
+            .unwrap_or(self.default_context_limit)
+    }
+
+    /// Estimated cost in USD, None if the model has no configured price
+    pub fn estimate_cost(
+        &self,
+        model: &str,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+    ) -> Option<f64> {
+        let price = self.model_prices.get(model)?;
+        Some(
+            (prompt_tokens as f64 * price.input + completion_tokens as f64 * price.output)
+                / 1_000_000.0,
+        )
+    }
+
+    /// Request profile for a model: an exact match, else the longest matching `prefix*` pattern.
+    /// Returns the matching key along with the profile.
+    pub fn model_profile(&self, model: &str) -> Option<(&str, &ModelProfile)> {
+        profiles::find_profile(&self.model_profiles, model)
+    }
+
+    /// Config for each fallback model, in order: the entry's model and connection
+    /// settings on top of this config, without further fallbacks
+    pub fn fallback_configs(&self) -> Vec<Config> {
+        self.fallback_models
+            .iter()
+            // An entry that only repeats the current model adds nothing
+            .filter(|f| f.model != self.current_model || f.api_url.is_some())
+            .map(|f| {
+                let mut config = self.clone();
+                config.current_model = f.model.clone();
+                if let Some(provider) = &f.provider {
+                    config.provider = provider.clone();
+                }
+                if let Some(api_url) = &f.api_url {
+                    config.api_url = api_url.clone();
+                }
+                if let Some(api_key) = &f.api_key {
+                    config.api_key = api_key.clone();
+                }
+                config.fallback_models.clear();
+                config
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_cost_uses_price_per_million_tokens() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "api_key": "key",
+            "api_url": "https://api.example.com/v1",
+            "current_model": "gpt-4o",
+            "model_prices": { "gpt-4o": { "input": 2.5, "output": 10.0 } }
+        }))
+        .unwrap();
+
+        let cost = config.estimate_cost("gpt-4o", 1_000_000, 500_000).unwrap();
+        assert!((cost - 7.5).abs() < 1e-9);
+        assert_eq!(config.estimate_cost("gpt-4o", 0, 0), Some(0.0));
+        assert!(config.estimate_cost("unpriced-model", 1000, 1000).is_none());
+    }
 }
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use super::types::ModelProfile;
+
+/// Find the profile for a model: exact name first, then the longest `prefix*` pattern
+pub fn find_profile<'a>(
+    profiles: &'a HashMap<String, ModelProfile>,
+    model: &str,
+) -> Option<(&'a str, &'a ModelProfile)> {
+    if let Some((key, profile)) = profiles.get_key_value(model) {
+        return Some((key.as_str(), profile));
+    }
+
+    profiles
+        .iter()
+        .filter_map(|(key, profile)| {
+            let prefix = key.strip_suffix('*')?;
+            model
+                .starts_with(prefix)
+                .then_some((key.as_str(), profile, prefix.len()))
+        })
+        .max_by_key(|(_, _, len)| *len)
+        .map(|(key, profile, _)| (key, profile))
+}
+
+impl ModelProfile {
+    /// Whether image input may be sent to the model
+    pub fn supports_vision(&self) -> bool {
+        self.vision.unwrap_or(true)
+    }
+
+    /// Whether streamed requests ask for token usage
+    pub fn requests_stream_usage(&self) -> bool {
+        self.stream_usage.unwrap_or(true)
+    }
+
+    /// One-line description of the parameters set in this profile
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(v) = self.temperature {
+            parts.push(format!("temperature={}", v));
+        }
+        if let Some(v) = self.top_p {
+            parts.push(format!("top_p={}", v));
+        }
+        if let Some(v) = self.max_tokens {
+            parts.push(format!("max_tokens={}", v));
+        }
+        if let Some(v) = &self.reasoning_effort {
+            parts.push(format!("reasoning_effort={}", v));
+        }
+        if let Some(v) = self.thinking_budget {
+            parts.push(format!("thinking_budget={}", v));
+        }
+        if !self.stop.is_empty() {
+            parts.push(format!("stop={:?}", self.stop));
+        }
+        if let Some(v) = self.seed {
+            parts.push(format!("seed={}", v));
+        }
+        if let Some(v) = self.vision {
+            parts.push(format!("vision={}", v));
+        }
+        if let Some(v) = self.stream_usage {
+            parts.push(format!("stream_usage={}", v));
+        }
+        for (key, value) in &self.extra {
+            parts.push(format!("{}={}", key, value));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(temperature: f32) -> ModelProfile {
+        ModelProfile {
+            temperature: Some(temperature),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_profile_prefers_exact_then_longest_prefix() {
+        let profiles: HashMap<String, ModelProfile> = [
+            ("claude-*".to_string(), profile(0.5)),
+            ("claude-sonnet-*".to_string(), profile(0.3)),
+            ("claude-sonnet-4".to_string(), profile(0.1)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            find_profile(&profiles, "claude-sonnet-4").unwrap().0,
+            "claude-sonnet-4"
+        );
+        assert_eq!(
+            find_profile(&profiles, "claude-sonnet-4-5").unwrap().0,
+            "claude-sonnet-*"
+        );
+        assert_eq!(
+            find_profile(&profiles, "claude-haiku").unwrap().0,
+            "claude-*"
+        );
+        assert!(find_profile(&profiles, "gpt-4o").is_none());
+    }
+
+    #[test]
+    fn test_profile_deserializes_extra_fields() {
+        let profile: ModelProfile = serde_json::from_str(
+            r#"{"max_tokens": 8192, "stop": ["</done>"], "extra": {"top_k": 20}}"#,
+        )
+        .unwrap();
+        assert_eq!(profile.max_tokens, Some(8192));
+        assert_eq!(profile.extra["top_k"], 20);
+        assert_eq!(
+            profile.summary(),
+            "max_tokens=8192, stop=[\"</done>\"], top_k=20"
+        );
+        assert!(profile.requests_stream_usage());
+
+        let profile: ModelProfile = serde_json::from_str(r#"{"stream_usage": false}"#).unwrap();
+        assert!(!profile.requests_stream_usage());
+        assert_eq!(profile.summary(), "stream_usage=false");
+    }
+}
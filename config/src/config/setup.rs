@@ -23,24 +23,33 @@ pub fn initialize_config() -> Result<Config> {
     // Update i18n with selected language for remaining prompts
     let i18n = I18n::new(&ui_language);
 
-    // Step 2: API Key
+    // Step 2: Provider backend
+    let provider_idx = dialoguer::Select::new()
+        .with_prompt(i18n.get("setup_provider"))
+        .default(0)
+        .items(defaults::SUPPORTED_PROVIDERS)
+        .interact()?;
+    let provider = defaults::SUPPORTED_PROVIDERS[provider_idx].to_string();
+
+    // Step 3: API Key (local Ollama needs none)
     let api_key = dialoguer::Input::<String>::new()
         .with_prompt(i18n.get("setup_api_key"))
+        .allow_empty(provider == "ollama")
         .interact_text()?;
 
-    // Step 3: API URL
+    // Step 4: API URL
     let api_url = dialoguer::Input::<String>::new()
         .with_prompt(i18n.get("setup_api_url"))
-        .default("https://api.openai.com/v1".to_string())
+        .default(defaults::default_api_url(&provider))
         .interact_text()?;
 
-    // Step 4: Default Model
+    // Step 5: Default Model
     let current_model = dialoguer::Input::<String>::new()
         .with_prompt(i18n.get("setup_model"))
         .default("gpt-4".to_string())
         .interact_text()?;
 
-    // Step 5: AI Language (last)
+    // Step 6: AI Language (last)
     let ai_language = dialoguer::Input::<String>::new()
         .with_prompt(i18n.get("setup_ai_language"))
         .default(SUPPORTED_LANGUAGES[0].to_string())
@@ -50,10 +59,22 @@ pub fn initialize_config() -> Result<Config> {
         api_key,
         api_url,
         current_model,
+        provider,
         ui_language,
         ai_language,
         max_retries: defaults::default_max_retries(),
         retry_delay_ms: defaults::default_retry_delay_ms(),
+        model_prices: Default::default(),
+        context_limits: Default::default(),
+        default_context_limit: defaults::default_context_limit(),
+        model_profiles: Default::default(),
+        fallback_models: Default::default(),
+        mcp_servers: Default::default(),
+        allowed_roots: Default::default(),
+        sensitive_paths: Default::default(),
+        auto_commit: false,
+        command_timeout_secs: defaults::default_command_timeout_secs(),
+        record_dir: None,
     };
 
     persistence::save_config(&config)?;
@@ -1,5 +1,8 @@
 use super::defaults;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,6 +10,9 @@ pub struct Config {
     pub api_key: String,
     pub api_url: String,
     pub current_model: String,
+    /// Provider backend: openai, anthropic, ollama or openai-responses
+    #[serde(default = "defaults::default_provider")]
+    pub provider: String,
     #[serde(default = "defaults::default_ui_language")]
     pub ui_language: String,
     #[serde(default = "defaults::default_ai_language")]
@@ -15,4 +21,110 @@ pub struct Config {
     pub max_retries: u32,
     #[serde(default = "defaults::default_retry_delay_ms")]
     pub retry_delay_ms: u64,
+    /// Per-model prices used for cost estimates, keyed by model name
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+    /// Per-model context window sizes in tokens, keyed by model name
+    #[serde(default)]
+    pub context_limits: HashMap<String, u64>,
+    /// Context window size for models missing from context_limits
+    #[serde(default = "defaults::default_context_limit")]
+    pub default_context_limit: u64,
+    /// Per-model request parameters, keyed by model name or a `prefix*` pattern
+    #[serde(default)]
+    pub model_profiles: HashMap<String, ModelProfile>,
+    /// Models tried in order when the current model is overloaded, rate limited or failing
+    #[serde(default)]
+    pub fallback_models: Vec<FallbackModel>,
+    /// MCP tool servers started with the app, keyed by server name
+    #[serde(default)]
+    pub mcp_servers: HashMap<String, McpServerConfig>,
+    /// Directories the file tools may access besides the session's working directory
+    #[serde(default)]
+    pub allowed_roots: Vec<String>,
+    /// Path patterns that always need explicit approval, in addition to the built-in ones
+    #[serde(default)]
+    pub sensitive_paths: Vec<String>,
+    /// Commit the files edited in each assistant turn to the current git branch
+    #[serde(default)]
+    pub auto_commit: bool,
+    /// Default run_command timeout in seconds, 0 disables it
+    #[serde(default = "defaults::default_command_timeout_secs")]
+    pub command_timeout_secs: u64,
+    /// Directory for recording API exchanges (`--record <dir>`), never saved
+    #[serde(skip)]
+    pub record_dir: Option<PathBuf>,
+}
+
+/// Model price in USD per million tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+}
+
+/// MCP server launched as a subprocess (`command`) or reached over HTTP (`url`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Extra environment variables for the subprocess
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Streamable HTTP endpoint, e.g. http://localhost:3000/mcp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Extra HTTP headers, e.g. Authorization
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+/// Fallback model entry, unset connection fields are taken from the main config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FallbackModel {
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Request parameters for a model, unset fields keep the provider's defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Maximum output tokens (required by some gateways and by Anthropic)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Reasoning effort for reasoning models: low, medium or high. Anthropic has no such
+    /// parameter; without thinking_budget it becomes a budget of 1024, 4096 or 16384 tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<String>,
+    /// Extended thinking budget in tokens (Anthropic). max_tokens is raised above it when
+    /// needed, and temperature and top_p are not sent while thinking is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Whether the model accepts image input; unset means assume it does
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vision: Option<bool>,
+    /// Whether to ask OpenAI-compatible servers for token usage in the stream
+    /// (`stream_options.include_usage`); unset means ask. Turn off for servers that reject it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_usage: Option<bool>,
+    /// Extra top-level request body fields, merged in last
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub extra: Map<String, Value>,
 }
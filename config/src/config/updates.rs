@@ -27,3 +27,9 @@ pub fn update_ai_language(config: &mut Config, language: String) -> Result<()> {
     config.ai_language = language;
     persistence::save_config(config)
 }
+
+/// Enable or disable auto-commit
+pub fn update_auto_commit(config: &mut Config, enabled: bool) -> Result<()> {
+    config.auto_commit = enabled;
+    persistence::save_config(config)
+}
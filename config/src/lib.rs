@@ -1,3 +1,6 @@
 pub mod config;
 
-pub use config::Config;
+pub use config::{
+    Config, FallbackModel, McpServerConfig, ModelPrice, ModelProfile, DEFAULT_COMMAND_TIMEOUT_SECS,
+    SUPPORTED_PROVIDERS,
+};
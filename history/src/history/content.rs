@@ -0,0 +1,223 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::de::Deserializer;
+use serde::ser::{SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+/// Largest image accepted as an attachment (the strictest provider limit)
+pub const MAX_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Image extensions and their media types
+const IMAGE_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+];
+
+/// Message content: text plus attached images.
+///
+/// Serialized as a plain string when there are no images (the original session format),
+/// otherwise as an OpenAI-style content-part array: the text part followed by one
+/// `image_url` part per image. Derefs to the text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageContent {
+    pub text: String,
+    pub images: Vec<ImageUrl>,
+}
+
+/// Image reference of an `image_url` content part (usually a base64 data URL)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageUrl {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl MessageContent {
+    pub fn with_images(text: impl Into<String>, images: Vec<ImageUrl>) -> Self {
+        Self {
+            text: text.into(),
+            images,
+        }
+    }
+
+    pub fn has_images(&self) -> bool {
+        !self.images.is_empty()
+    }
+}
+
+impl Deref for MessageContent {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.text
+    }
+}
+
+impl DerefMut for MessageContent {
+    fn deref_mut(&mut self) -> &mut String {
+        &mut self.text
+    }
+}
+
+impl From<String> for MessageContent {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            images: Vec::new(),
+        }
+    }
+}
+
+impl From<&str> for MessageContent {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+impl fmt::Display for MessageContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl PartialEq<&str> for MessageContent {
+    fn eq(&self, other: &&str) -> bool {
+        self.images.is_empty() && self.text == *other
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+impl Serialize for MessageContent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.images.is_empty() {
+            return serializer.serialize_str(&self.text);
+        }
+        let text_part = !self.text.is_empty();
+        let mut seq = serializer.serialize_seq(Some(self.images.len() + text_part as usize))?;
+        if text_part {
+            seq.serialize_element(&ContentPart::Text {
+                text: self.text.clone(),
+            })?;
+        }
+        for image in &self.images {
+            seq.serialize_element(&ContentPart::ImageUrl {
+                image_url: image.clone(),
+            })?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageContent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Text(String),
+            Parts(Vec<ContentPart>),
+            Null(()),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Text(text) => text.into(),
+            Raw::Null(()) => Self::default(),
+            Raw::Parts(parts) => {
+                let mut content = Self::default();
+                for part in parts {
+                    match part {
+                        ContentPart::Text { text } => {
+                            if !content.text.is_empty() {
+                                content.text.push('\n');
+                            }
+                            content.text.push_str(&text);
+                        }
+                        ContentPart::ImageUrl { image_url } => content.images.push(image_url),
+                    }
+                }
+                content
+            }
+        })
+    }
+}
+
+impl ImageUrl {
+    /// Load an image file as a base64 data URL
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let media_type = image_media_type(path)
+            .with_context(|| format!("Unsupported image type: {}", path.display()))?;
+        let size = std::fs::metadata(path)
+            .with_context(|| format!("Cannot read {}", path.display()))?
+            .len();
+        anyhow::ensure!(
+            size <= MAX_IMAGE_BYTES,
+            "Image {} is {} KB, the limit is {} KB",
+            path.display(),
+            size / 1024,
+            MAX_IMAGE_BYTES / 1024
+        );
+        let bytes = std::fs::read(path)?;
+        Ok(Self {
+            url: format!(
+                "data:{};base64,{}",
+                media_type,
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            ),
+            detail: None,
+        })
+    }
+
+    /// Media type and base64 payload of a data URL
+    pub fn data_parts(&self) -> Option<(&str, &str)> {
+        let rest = self.url.strip_prefix("data:")?;
+        let (media_type, data) = rest.split_once(";base64,")?;
+        Some((media_type, data))
+    }
+}
+
+/// Media type of a supported image file, by extension
+pub fn image_media_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    IMAGE_TYPES
+        .iter()
+        .find(|(e, _)| *e == ext)
+        .map(|(_, media_type)| *media_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_string_round_trip() {
+        let content: MessageContent = serde_json::from_str(r#""hello""#).unwrap();
+        assert_eq!(content, "hello");
+        assert_eq!(serde_json::to_string(&content).unwrap(), r#""hello""#);
+    }
+
+    #[test]
+    fn test_content_parts_round_trip() {
+        let json = r#"[{"type":"text","text":"what is this?"},{"type":"image_url","image_url":{"url":"data:image/png;base64,iVBO"}}]"#;
+        let content: MessageContent = serde_json::from_str(json).unwrap();
+        assert_eq!(content.text, "what is this?");
+        assert_eq!(content.images[0].data_parts(), Some(("image/png", "iVBO")));
+        assert_eq!(serde_json::to_string(&content).unwrap(), json);
+    }
+
+    #[test]
+    fn test_null_content_loads_empty() {
+        let content: MessageContent = serde_json::from_str("null").unwrap();
+        assert!(content.is_empty());
+    }
+}
@@ -1,8 +1,10 @@
+mod content;
 mod management;
 mod persistence;
 mod session;
 mod types;
 
 // Re-export public API
+pub use content::{image_media_type, ImageUrl, MessageContent, MAX_IMAGE_BYTES};
 pub use session::ChatSession;
-pub use types::{FunctionCall, Message, ToolCall};
+pub use types::{Compaction, FunctionCall, Message, ToolCall, Usage, UsageTotals};
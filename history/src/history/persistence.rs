@@ -50,6 +50,6 @@ pub fn list_all_sessions() -> Result<Vec<ChatSession>> {
         }
     }
 
-    sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
     Ok(sessions)
 }
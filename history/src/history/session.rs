@@ -1,6 +1,6 @@
 use super::management;
 use super::persistence;
-use super::types::Message;
+use super::types::{Compaction, Message, Usage};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,13 @@ pub struct ChatSession {
     pub messages: Vec<Message>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Pinned summary of older messages, if the conversation was compacted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compaction: Option<Compaction>,
+    /// Token usage of requests made outside the conversation: compaction summaries,
+    /// prompt optimization, approval reviews and commit messages
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub side_usage: Vec<Usage>,
 }
 
 impl ChatSession {
@@ -28,6 +35,8 @@ impl ChatSession {
             messages: Vec::new(),
             created_at: now,
             updated_at: now,
+            compaction: None,
+            side_usage: Vec::new(),
         }
     }
 
@@ -74,6 +83,28 @@ impl ChatSession {
         first_user_msg
     }
 
+    /// Messages not covered by the compaction summary
+    pub fn uncompacted_messages(&self) -> &[Message] {
+        let start = self
+            .compaction
+            .as_ref()
+            .map_or(0, |c| c.compacted_messages.min(self.messages.len()));
+        &self.messages[start..]
+    }
+
+    /// Token usage records of all assistant responses and side requests in this session
+    pub fn usages(&self) -> impl Iterator<Item = &Usage> {
+        self.messages
+            .iter()
+            .filter_map(|m| m.usage.as_ref())
+            .chain(&self.side_usage)
+    }
+
+    /// Record usage of requests made outside the conversation
+    pub fn add_side_usage(&mut self, usage: impl IntoIterator<Item = Usage>) {
+        self.side_usage.extend(usage);
+    }
+
     /// Delete this session from disk
     pub fn delete(&self) -> Result<()> {
         management::delete_session(self)
@@ -84,3 +115,33 @@ impl ChatSession {
         management::cleanup_empty_sessions()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usages_include_side_usage() {
+        let mut session = ChatSession::new(PathBuf::from("/tmp"));
+        session.add_message(Message {
+            role: "assistant".to_string(),
+            content: "hi".into(),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+            usage: Some(Usage::new(10, 5)),
+            model: None,
+            thinking_blocks: Vec::new(),
+        });
+        session.add_side_usage([Usage::new(100, 20)]);
+
+        let prompt_tokens: u64 = session.usages().map(|u| u.prompt_tokens).sum();
+        assert_eq!(prompt_tokens, 110);
+
+        // Sessions saved before side usage was tracked still load
+        let mut value = serde_json::to_value(&session).unwrap();
+        value.as_object_mut().unwrap().remove("side_usage");
+        let loaded: ChatSession = serde_json::from_value(value).unwrap();
+        assert!(loaded.side_usage.is_empty());
+    }
+}
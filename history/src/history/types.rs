@@ -1,16 +1,31 @@
+use chrono::{DateTime, Local, Utc};
+use config::Config;
 use serde::{Deserialize, Serialize};
 
+use super::content::MessageContent;
+
 /// Message in a chat session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub role: String,
-    pub content: String,
+    /// Text, plus image parts for multimodal user messages
+    pub content: MessageContent,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Token usage reported for this assistant response (local only, never sent to the API)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+    /// Model that produced this assistant response (local only, never sent to the API)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Signed reasoning blocks of this assistant response, in the provider's own format.
+    /// Anthropic requires them back in front of the tool calls when a tool loop continues
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thinking_blocks: Vec<serde_json::Value>,
 }
 
 /// Tool call information
@@ -28,3 +43,143 @@ pub struct FunctionCall {
     pub name: String,
     pub arguments: String,
 }
+
+/// Summary that replaces the oldest messages when building requests.
+/// The summarized messages themselves are kept in the session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Compaction {
+    pub summary: String,
+    /// Number of leading session messages covered by the summary
+    pub compacted_messages: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Compaction {
+    pub fn new(summary: String, compacted_messages: usize) -> Self {
+        Self {
+            summary,
+            compacted_messages,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Token usage of a single API response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Usage {
+    /// Model that was billed for this response
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Usage {
+    pub fn new(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self {
+            model: String::new(),
+            prompt_tokens,
+            completion_tokens,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    /// Whether this response was received today (local time)
+    pub fn is_today(&self) -> bool {
+        self.created_at.with_timezone(&Local).date_naive() == Local::now().date_naive()
+    }
+}
+
+/// Aggregated token usage over many responses
+#[derive(Debug, Clone, Default)]
+pub struct UsageTotals {
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Estimated cost in USD of the priced responses
+    pub cost: f64,
+    /// Responses whose model has no configured price
+    pub unpriced_requests: u64,
+}
+
+impl UsageTotals {
+    /// Sum up usage records, pricing them with the configured price table
+    pub fn collect<'a>(usages: impl IntoIterator<Item = &'a Usage>, config: &Config) -> Self {
+        let mut totals = Self::default();
+        for usage in usages {
+            totals.add(usage, config);
+        }
+        totals
+    }
+
+    pub fn add(&mut self, usage: &Usage, config: &Config) {
+        self.requests += 1;
+        self.prompt_tokens += usage.prompt_tokens;
+        self.completion_tokens += usage.completion_tokens;
+        match config.estimate_cost(&usage.model, usage.prompt_tokens, usage.completion_tokens) {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced_requests += 1,
+        }
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Usage {
+        Usage {
+            model: model.to_string(),
+            ..Usage::new(prompt_tokens, completion_tokens)
+        }
+    }
+
+    #[test]
+    fn test_collect_sums_tokens_and_prices_known_models() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "api_key": "key",
+            "api_url": "https://api.example.com/v1",
+            "current_model": "priced",
+            "model_prices": { "priced": { "input": 1.0, "output": 2.0 } }
+        }))
+        .unwrap();
+        let usages = [
+            usage("priced", 1_000_000, 1_000_000),
+            usage("priced", 500_000, 0),
+            usage("unpriced", 100, 50),
+        ];
+
+        let totals = UsageTotals::collect(&usages, &config);
+        assert_eq!(totals.requests, 3);
+        assert_eq!(totals.prompt_tokens, 1_500_100);
+        assert_eq!(totals.completion_tokens, 1_000_050);
+        assert_eq!(totals.total_tokens(), 2_500_150);
+        assert!((totals.cost - 3.5).abs() < 1e-9);
+        assert_eq!(totals.unpriced_requests, 1);
+
+        let empty = UsageTotals::collect(&[], &config);
+        assert_eq!(empty.requests, 0);
+        assert_eq!(empty.cost, 0.0);
+    }
+
+    #[test]
+    fn test_usage_deserializes_without_optional_fields() {
+        let usage: Usage = serde_json::from_str(r#"{"prompt_tokens": 12}"#).unwrap();
+        assert_eq!(usage.prompt_tokens, 12);
+        assert_eq!(usage.completion_tokens, 0);
+        assert!(usage.model.is_empty());
+        assert!(usage.is_today());
+    }
+}
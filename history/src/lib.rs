@@ -1,3 +1,6 @@
 pub mod history;
 
-pub use history::{ChatSession, FunctionCall, Message, ToolCall};
+pub use history::{
+    image_media_type, ChatSession, Compaction, FunctionCall, ImageUrl, Message, MessageContent,
+    ToolCall, Usage, UsageTotals, MAX_IMAGE_BYTES,
+};
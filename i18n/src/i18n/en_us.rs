@@ -61,10 +61,6 @@ pub fn get_messages() -> HashMap<String, String> {
         "cmd_runcommand_del".to_string(),
         "Remove command from approval list".to_string(),
     );
-    m.insert(
-        "cmd_runcommand_info".to_string(),
-        "Show background command details".to_string(),
-    );
 
     // Status messages
     m.insert("goodbye".to_string(), "Goodbye!".to_string());
@@ -204,11 +200,11 @@ pub fn get_messages() -> HashMap<String, String> {
     );
     m.insert(
         "setup_api_key".to_string(),
-        "Please enter OpenAI API Key".to_string(),
+        "Please enter API Key".to_string(),
     );
     m.insert(
         "setup_api_url".to_string(),
-        "Please enter OpenAI Base URL".to_string(),
+        "Please enter API Base URL".to_string(),
     );
     m.insert(
         "setup_model".to_string(),
@@ -501,11 +497,11 @@ pub fn get_messages() -> HashMap<String, String> {
         "run_command_user_rejected".to_string(),
         "User rejected the operation".to_string(),
     );
+    m.insert("run_command_bg_brief".to_string(), "Started background job {}".to_string());
     m.insert(
-        "run_command_bg_brief".to_string(),
-        "Started background command: {}".to_string(),
+        "run_command_bg_output".to_string(),
+        "Command started in background\nJob ID: {}\nPID: {}\nCommand: {}\nLog: {}\n\nCheck it with command_status and stop it with command_kill; you will be notified when it exits".to_string(),
     );
-    m.insert("run_command_bg_output".to_string(), "Command started in background\nRun ID: {}\nCommand: {}\n\nUse /runcommand info {{}} to check status".to_string());
     m.insert(
         "run_command_fg_brief".to_string(),
         "Command executed: {} (exit: {})".to_string(),
@@ -562,29 +558,6 @@ pub fn get_messages() -> HashMap<String, String> {
         "runcommand_del_not_found".to_string(),
         "'{}' is not in approval list".to_string(),
     );
-    m.insert(
-        "runcommand_info_header".to_string(),
-        "Background Command Info".to_string(),
-    );
-    m.insert("runcommand_info_id".to_string(), "ID:".to_string());
-    m.insert(
-        "runcommand_info_command".to_string(),
-        "Command:".to_string(),
-    );
-    m.insert("runcommand_info_status".to_string(), "Status:".to_string());
-    m.insert(
-        "runcommand_info_started".to_string(),
-        "Started:".to_string(),
-    );
-    m.insert(
-        "runcommand_info_exit_code".to_string(),
-        "Exit Code:".to_string(),
-    );
-    m.insert("runcommand_info_output".to_string(), "Output".to_string());
-    m.insert(
-        "runcommand_info_not_found".to_string(),
-        "Command with ID '{}' not found".to_string(),
-    );
     m.insert(
         "runcommand_help_header".to_string(),
         "Help for /runcommand".to_string(),
@@ -686,5 +659,463 @@ pub fn get_messages() -> HashMap<String, String> {
         "Failed to fix JSON for tool".to_string(),
     );
 
+    // Provider setup
+    m.insert("setup_provider".to_string(), "Please select the API provider".to_string());
+
+    // Token usage
+    m.insert("cmd_usage".to_string(), "Show token usage and estimated cost".to_string());
+    m.insert("usage_title".to_string(), "Token Usage".to_string());
+    m.insert("usage_session".to_string(), "Session".to_string());
+    m.insert("usage_today".to_string(), "Today".to_string());
+    m.insert("usage_project".to_string(), "Project".to_string());
+    m.insert("usage_requests".to_string(), "requests".to_string());
+    m.insert("usage_input".to_string(), "in".to_string());
+    m.insert("usage_output".to_string(), "out".to_string());
+    m.insert("usage_tokens".to_string(), "Tokens".to_string());
+    m.insert("usage_none".to_string(), "No token usage recorded yet".to_string());
+    m.insert(
+        "usage_unpriced".to_string(),
+        "{} responses use models without a price (set model_prices in config.json)".to_string(),
+    );
+
+    // Context compaction
+    m.insert("cmd_compact".to_string(), "Summarize older turns to free context".to_string());
+    m.insert(
+        "compact_running".to_string(),
+        "Compacting {} older messages into a summary...".to_string(),
+    );
+    m.insert(
+        "compact_done".to_string(),
+        "Compacted {} messages (originals are kept in history)".to_string(),
+    );
+    m.insert("compact_nothing".to_string(), "Nothing to compact yet".to_string());
+    m.insert("compact_failed".to_string(), "Failed to compact conversation".to_string());
+    m.insert(
+        "compact_empty_summary".to_string(),
+        "The model returned an empty summary".to_string(),
+    );
+
+    // API error classes
+    m.insert("api_stream_interrupted".to_string(), "Stream interrupted, resuming".to_string());
+    m.insert(
+        "api_stream_truncated".to_string(),
+        "Response stream ended before the response was complete".to_string(),
+    );
+    m.insert("api_err_auth".to_string(), "Authentication failed".to_string());
+    m.insert("api_err_rate_limit".to_string(), "Rate limited".to_string());
+    m.insert("api_err_context_overflow".to_string(), "Context window exceeded".to_string());
+    m.insert("api_err_server".to_string(), "Server error".to_string());
+    m.insert("api_err_invalid_request".to_string(), "Request rejected".to_string());
+    m.insert("api_err_network".to_string(), "Network error".to_string());
+    m.insert("api_err_malformed_stream".to_string(), "Malformed response stream".to_string());
+    m.insert(
+        "api_hint_auth".to_string(),
+        "Check the API key and provider in the config file, or delete it to run setup again".to_string(),
+    );
+    m.insert(
+        "api_hint_rate_limit".to_string(),
+        "The provider is throttling requests; wait a moment or check your plan's quota".to_string(),
+    );
+    m.insert(
+        "api_hint_context_overflow".to_string(),
+        "The conversation is too long for this model; run /compact or switch to a model with a larger context".to_string(),
+    );
+    m.insert(
+        "api_hint_server".to_string(),
+        "The provider is having trouble; try again later or switch models with /model".to_string(),
+    );
+    m.insert(
+        "api_hint_invalid_request".to_string(),
+        "The provider rejected the request; check the model name and API URL".to_string(),
+    );
+    m.insert(
+        "api_hint_network".to_string(),
+        "Could not reach the API; check your connection, proxy and API URL".to_string(),
+    );
+    m.insert(
+        "api_hint_malformed_stream".to_string(),
+        "The response was cut off or garbled; send the message again".to_string(),
+    );
+
+    // API recording
+    m.insert("api_recording_to".to_string(), "Recording API exchanges to".to_string());
+    m.insert("api_record_failed".to_string(), "Failed to record API exchange".to_string());
+
+    // Model profiles
+    m.insert("model_profile_applied".to_string(), "Applied model profile".to_string());
+
+    // Model fallback
+    m.insert("api_fallback_model".to_string(), "{} is unavailable, falling back to {}".to_string());
+    m.insert("chat_answered_by".to_string(), "Answered by {}".to_string());
+
+    // Images
+    m.insert("file_read_image_brief".to_string(), "Loaded image, {}".to_string());
+    m.insert(
+        "file_read_image_output".to_string(),
+        "Image {} is attached in the next message".to_string(),
+    );
+    m.insert(
+        "file_read_image_unsupported".to_string(),
+        "Unsupported image type (png, jpg, gif, webp): {}".to_string(),
+    );
+    m.insert("tool_images_attached".to_string(), "Images returned by tool calls:".to_string());
+    m.insert("api_err_no_vision".to_string(), "Model does not accept image input".to_string());
+    m.insert(
+        "api_hint_no_vision".to_string(),
+        "Switch to a vision-capable model with /model, or remove `vision = false` from its profile".to_string(),
+    );
+    m.insert("image_attached".to_string(), "Image attached: {}".to_string());
+    m.insert("image_pending".to_string(), "Image will be sent with your next message".to_string());
+    m.insert("image_load_failed".to_string(), "Failed to load image".to_string());
+    m.insert("image_usage".to_string(), "Usage: /image <path> [message]".to_string());
+    m.insert(
+        "image_no_vision".to_string(),
+        "The current model's profile says it cannot see images; the request will be refused".to_string(),
+    );
+    m.insert(
+        "cmd_image".to_string(),
+        "Attach an image to the next message (or @file.png)".to_string(),
+    );
+
+    // Tool registry
+    m.insert("tool_user_rejected".to_string(), "User rejected the {} call".to_string());
+
+    // MCP
+    m.insert("mcp_connected".to_string(), "MCP server {} connected ({} tools)".to_string());
+    m.insert("mcp_failed".to_string(), "MCP server {} failed to start".to_string());
+    m.insert("mcp_tool_brief".to_string(), "{}: {} chars".to_string());
+    m.insert("mcp_tool_failed".to_string(), "MCP server {} reported an error".to_string());
+    m.insert("mcp_title".to_string(), "MCP servers".to_string());
+    m.insert(
+        "mcp_none".to_string(),
+        "No MCP servers configured; add them under mcp_servers in config.json".to_string(),
+    );
+    m.insert("mcp_status_connected".to_string(), "connected".to_string());
+    m.insert("mcp_status_failed".to_string(), "failed".to_string());
+    m.insert("mcp_status_disabled".to_string(), "disabled".to_string());
+    m.insert("cmd_mcp".to_string(), "List MCP servers and their tools".to_string());
+
+    // Script tools
+    m.insert("script_tool_brief".to_string(), "Output {} lines".to_string());
+    m.insert("script_tool_failed".to_string(), "{} exited with code {}".to_string());
+    m.insert("script_tools_loaded".to_string(), "Loaded {} tools from {}".to_string());
+    m.insert(
+        "script_tools_skipped".to_string(),
+        "Skipped script tools whose names are already taken".to_string(),
+    );
+    m.insert("script_tools_invalid".to_string(), "Failed to load script tools".to_string());
+
+    // file_read ranges and limits
+    m.insert("file_read_range_brief".to_string(), "Read lines {}-{} of {}".to_string());
+    m.insert(
+        "file_read_truncated".to_string(),
+        "[File has {} lines ({}); showing lines {}-{}. Request the rest with offset/limit]".to_string(),
+    );
+    m.insert(
+        "file_read_past_end".to_string(),
+        "[offset is past the end of the file, which has {} lines]".to_string(),
+    );
+    m.insert(
+        "file_read_lossy".to_string(),
+        "[File is not valid UTF-8; invalid bytes are shown as U+FFFD]".to_string(),
+    );
+    m.insert("file_read_binary_brief".to_string(), "Binary file, {}".to_string());
+    m.insert(
+        "file_read_binary".to_string(),
+        "{} is a binary file ({}), content not shown. Use file_read_image for images".to_string(),
+    );
+
+    // file_search
+    m.insert("file_search_brief".to_string(), "Found {} matches in {} files".to_string());
+    m.insert("file_search_none".to_string(), "No matches for {}".to_string());
+    m.insert(
+        "file_search_truncated".to_string(),
+        "[Stopped at {} matches; narrow the pattern, path or include globs to see the rest]".to_string(),
+    );
+    m.insert("file_search_bad_pattern".to_string(), "Invalid search pattern: {}".to_string());
+    m.insert("file_search_bad_glob".to_string(), "Invalid glob: {}".to_string());
+
+    // file_glob / file_tree
+    m.insert("file_glob_brief".to_string(), "Found {} paths".to_string());
+    m.insert("file_glob_none".to_string(), "No paths match the pattern".to_string());
+    m.insert(
+        "file_glob_truncated".to_string(),
+        "[Showing the first {} of {} paths; narrow the pattern or path to see the rest]".to_string(),
+    );
+    m.insert("file_tree_brief".to_string(), "Listed {} entries".to_string());
+    m.insert(
+        "file_tree_truncated".to_string(),
+        "[Stopped at {} entries; list a subdirectory or lower max_depth to see the rest]".to_string(),
+    );
+
+    // apply_patch
+    m.insert("apply_patch_parse_error".to_string(), "Invalid patch: {}".to_string());
+    m.insert("apply_patch_empty".to_string(), "The patch contains no file changes".to_string());
+    m.insert(
+        "apply_patch_file_exists".to_string(),
+        "Cannot create {}: file already exists".to_string(),
+    );
+    m.insert("apply_patch_failed_brief".to_string(), "{} files failed to patch".to_string());
+    m.insert(
+        "apply_patch_failed".to_string(),
+        "Patch not applied, no files were changed. Fix the failing hunks against the current file content and send the whole patch again.".to_string(),
+    );
+    m.insert("apply_patch_hunk_failed".to_string(), "Hunk #{} in {} did not match: {}".to_string());
+    m.insert(
+        "apply_patch_closest".to_string(),
+        "Closest region is lines {}-{} ({} of {} lines equal):".to_string(),
+    );
+    m.insert("apply_patch_no_match".to_string(), "No similar region found in the file".to_string());
+    m.insert(
+        "apply_patch_adjusted".to_string(),
+        "Hunk #{} in {} applied at line {} (offset {}, fuzz {})".to_string(),
+    );
+    m.insert("apply_patch_brief".to_string(), "Patched {} files".to_string());
+    m.insert("apply_patch_header".to_string(), "Patch applied:".to_string());
+    m.insert("apply_patch_created".to_string(), "  created {} (+{})".to_string());
+    m.insert("apply_patch_deleted".to_string(), "  deleted {}".to_string());
+    m.insert("apply_patch_renamed".to_string(), "  renamed to {} (+{} -{})".to_string());
+    m.insert("apply_patch_modified".to_string(), "  modified {} (+{} -{})".to_string());
+
+    // apply_patch approval
+    m.insert("apply_patch_subject".to_string(), "{} files".to_string());
+
+    // file_diff_edit stale-line protection
+    m.insert(
+        "file_diff_edit_stale_brief".to_string(),
+        "{} hunks do not match the file".to_string(),
+    );
+    m.insert(
+        "file_diff_edit_stale".to_string(),
+        "Edit rejected, {} was not changed. The lines below are the current content; read the file again and retry with updated line numbers.".to_string(),
+    );
+    m.insert(
+        "file_diff_edit_stale_hunk".to_string(),
+        "Hunk #{} (lines {}-{}) does not match expected_old. Current lines (> marks the range):".to_string(),
+    );
+    m.insert(
+        "file_diff_edit_stale_found".to_string(),
+        "expected_old was found starting at line {}".to_string(),
+    );
+    m.insert(
+        "file_diff_edit_changed_since_read".to_string(),
+        "[!] The file changed since it was last read; check the result below carefully.".to_string(),
+    );
+
+    // Checkpoints
+    m.insert("cmd_undo".to_string(), "Revert the files edited in the last turn".to_string());
+    m.insert("cmd_checkpoints".to_string(), "List the checkpoints of this session".to_string());
+    m.insert("cmd_restore".to_string(), "Revert a checkpoint and all later ones".to_string());
+    m.insert(
+        "checkpoint_unavailable".to_string(),
+        "Checkpoints unavailable for this turn".to_string(),
+    );
+    m.insert("checkpoint_nothing_to_undo".to_string(), "Nothing to undo".to_string());
+    m.insert("checkpoint_restore_usage".to_string(), "Usage: /restore <id> [--force]".to_string());
+    m.insert(
+        "checkpoint_not_found".to_string(),
+        "Checkpoint #{} not found, see /checkpoints".to_string(),
+    );
+    m.insert(
+        "checkpoint_already_undone".to_string(),
+        "Checkpoint #{} is already undone".to_string(),
+    );
+    m.insert("checkpoint_none".to_string(), "No checkpoints in this session".to_string());
+    m.insert("checkpoint_title".to_string(), "Checkpoints".to_string());
+    m.insert("checkpoint_files".to_string(), "{} file(s)".to_string());
+    m.insert("checkpoint_undone".to_string(), "undone".to_string());
+    m.insert(
+        "checkpoint_conflicts".to_string(),
+        "These files were changed after the edit, nothing was restored:".to_string(),
+    );
+    m.insert("checkpoint_force_hint".to_string(), "Run {} to overwrite them".to_string());
+    m.insert("checkpoint_restored".to_string(), "Reverted checkpoint {}".to_string());
+
+    // Git tools and commits
+    m.insert("git_not_repository".to_string(), "Not a git repository: {}".to_string());
+    m.insert("git_failed".to_string(), "git failed: {}".to_string());
+    m.insert(
+        "git_output_truncated".to_string(),
+        "[Output truncated at {} KB; narrow it down with path or stat]".to_string(),
+    );
+    m.insert("git_status_brief".to_string(), "{} changed files".to_string());
+    m.insert("git_status_clean".to_string(), "Working tree clean".to_string());
+    m.insert("git_diff_brief".to_string(), "{} files changed".to_string());
+    m.insert("git_diff_empty".to_string(), "No differences".to_string());
+    m.insert("git_log_brief".to_string(), "{} commits".to_string());
+    m.insert("git_log_empty".to_string(), "No commits".to_string());
+    m.insert(
+        "cmd_commit".to_string(),
+        "Draft a message for the staged changes and commit".to_string(),
+    );
+    m.insert("cmd_commit_auto".to_string(), "Commit the files edited in each turn".to_string());
+    m.insert("commit_nothing_staged".to_string(), "Nothing staged, use git add first".to_string());
+    m.insert("commit_drafting".to_string(), "Drafting commit message...".to_string());
+    m.insert("commit_draft_failed".to_string(), "Could not draft a commit message".to_string());
+    m.insert(
+        "commit_draft_empty".to_string(),
+        "The model returned an empty commit message".to_string(),
+    );
+    m.insert("commit_message".to_string(), "Commit message".to_string());
+    m.insert("commit_choice_commit".to_string(), "Commit".to_string());
+    m.insert("commit_choice_edit".to_string(), "Edit message".to_string());
+    m.insert("commit_choice_cancel".to_string(), "Cancel".to_string());
+    m.insert("commit_cancelled".to_string(), "Commit cancelled".to_string());
+    m.insert("commit_done".to_string(), "Committed".to_string());
+    m.insert("commit_auto_usage".to_string(), "Usage: /commit auto [on|off]".to_string());
+    m.insert(
+        "commit_auto_on".to_string(),
+        "Auto-commit is on: files edited in each turn are committed".to_string(),
+    );
+    m.insert("commit_auto_off".to_string(), "Auto-commit is off".to_string());
+    m.insert("commit_auto_failed".to_string(), "Auto-commit failed".to_string());
+    m.insert("commit_fallback_message".to_string(), "Update {}".to_string());
+
+    // file_move / file_copy / file_delete / dir_create
+    m.insert("file_paths_more".to_string(), "... and {} more".to_string());
+    m.insert(
+        "file_transfer_into_itself".to_string(),
+        "Cannot move or copy a path into itself: {}".to_string(),
+    );
+    m.insert(
+        "file_transfer_target_exists".to_string(),
+        "Destination already exists: {} (set overwrite to replace a file)".to_string(),
+    );
+    m.insert(
+        "file_transfer_target_is_dir".to_string(),
+        "Only files can be overwritten, not directories: {}".to_string(),
+    );
+    m.insert("file_move_preview".to_string(), "Paths to move:".to_string());
+    m.insert("file_move_brief".to_string(), "Moved {} files".to_string());
+    m.insert("file_move_done".to_string(), "Moved {} to {} ({} files)".to_string());
+    m.insert(
+        "file_copy_dir_needs_recursive".to_string(),
+        "{} is a directory, set recursive to copy it".to_string(),
+    );
+    m.insert("file_copy_preview".to_string(), "Paths to copy:".to_string());
+    m.insert("file_copy_brief".to_string(), "Copied {} files".to_string());
+    m.insert("file_copy_done".to_string(), "Copied {} to {} ({} files)".to_string());
+    m.insert(
+        "file_delete_working_dir".to_string(),
+        "Refusing to delete the working directory or one of its parents: {}".to_string(),
+    );
+    m.insert(
+        "file_delete_needs_recursive".to_string(),
+        "{} is a directory with {} entries, set recursive to delete it".to_string(),
+    );
+    m.insert("file_delete_preview".to_string(), "Paths to delete:".to_string());
+    m.insert("file_delete_brief".to_string(), "Deleted {}".to_string());
+    m.insert("file_delete_done".to_string(), "Deleted {} ({} files)".to_string());
+    m.insert("dir_create_exists".to_string(), "Directory already exists: {}".to_string());
+    m.insert("dir_create_preview".to_string(), "Directories to create:".to_string());
+    m.insert("dir_create_brief".to_string(), "Created {}".to_string());
+    m.insert("dir_create_done".to_string(), "Directory created: {}".to_string());
+
+    // Path policy
+    m.insert(
+        "path_outside_workspace".to_string(),
+        "Path is outside the workspace: {} (allowed roots: {}; add more with allowed_roots in the config)".to_string(),
+    );
+    m.insert(
+        "path_sensitive_rejected".to_string(),
+        "Access to sensitive path denied by user: {}".to_string(),
+    );
+    m.insert(
+        "path_policy_invalid_pattern".to_string(),
+        "Invalid sensitive_paths pattern in config, using built-in patterns only".to_string(),
+    );
+    m.insert(
+        "file_search_sensitive_skipped".to_string(),
+        "Skipped {} sensitive file(s)".to_string(),
+    );
+    m.insert("path_access_read".to_string(), "read".to_string());
+    m.insert("path_access_write".to_string(), "modify".to_string());
+    m.insert("sensitive_title".to_string(), "  [!] Sensitive Path Access".to_string());
+    m.insert(
+        "sensitive_action_wants".to_string(),
+        "The AI wants to {} the following sensitive paths:".to_string(),
+    );
+    m.insert("sensitive_matches".to_string(), "(matches {})".to_string());
+    m.insert("sensitive_opt_reject".to_string(), "Reject".to_string());
+    m.insert("sensitive_opt_allow".to_string(), "Allow this once".to_string());
+
+    // Command execution
+    m.insert(
+        "run_command_cwd_invalid".to_string(),
+        "Command directory does not exist: {}".to_string(),
+    );
+    m.insert(
+        "run_command_timed_out".to_string(),
+        "Command timed out after {}s and was stopped".to_string(),
+    );
+    m.insert("run_command_interrupted".to_string(), "Command interrupted by user".to_string());
+    m.insert("run_command_output_truncated".to_string(), "... [{} lines omitted] ...".to_string());
+
+    // Background jobs
+    m.insert("job_status_running".to_string(), "running".to_string());
+    m.insert("job_status_exited".to_string(), "exited with code {}".to_string());
+    m.insert("job_status_signaled".to_string(), "terminated by a signal".to_string());
+    m.insert("job_status_killed".to_string(), "killed".to_string());
+    m.insert(
+        "job_notice".to_string(),
+        "[Background job {} finished] `{}` {}. Last lines of its output:\n{}".to_string(),
+    );
+    m.insert("job_notice_shown".to_string(), "Background job {} ({}) {}".to_string());
+    m.insert("job_not_found".to_string(), "No background job with ID {}".to_string());
+    m.insert("job_none".to_string(), "No background jobs".to_string());
+    m.insert(
+        "job_details".to_string(),
+        "Job {}: {}\nCommand: {}\nPID: {}\nDirectory: {}\nStarted: {}\nLog: {}\n\nLast lines of output:\n{}".to_string(),
+    );
+    m.insert("command_status_list_brief".to_string(), "{} background job(s)".to_string());
+    m.insert("command_status_brief".to_string(), "Job {}: {}".to_string());
+    m.insert("command_kill_done".to_string(), "Job {} killed".to_string());
+    m.insert("command_kill_not_running".to_string(), "Job {} is not running ({})".to_string());
+    m.insert("cmd_jobs".to_string(), "List background jobs".to_string());
+    m.insert("cmd_jobs_kill".to_string(), "Stop a background job".to_string());
+    m.insert("cmd_jobs_tail".to_string(), "Show the end of a job's output".to_string());
+    m.insert("jobs_header".to_string(), "Background Jobs".to_string());
+    m.insert("jobs_tail_header".to_string(), "Job {}: last {} lines".to_string());
+    m.insert("jobs_tail_empty".to_string(), "(no output yet)".to_string());
+    m.insert("jobs_read_log_failed".to_string(), "Failed to read job log".to_string());
+
+    // Interactive terminal sessions
+    m.insert("pty_started".to_string(), "Started terminal session {}".to_string());
+    m.insert(
+        "pty_started_details".to_string(),
+        "Session {} is running `{}`. Type into it with pty_send and end it with pty_close.".to_string(),
+    );
+    m.insert("pty_start_failed".to_string(), "Failed to start terminal session: {}".to_string());
+    m.insert("pty_write_failed".to_string(), "Failed to send input: {}".to_string());
+    m.insert("pty_unknown_key".to_string(), "Unknown key: {}".to_string());
+    m.insert(
+        "pty_not_found".to_string(),
+        "No terminal session with ID {} (sessions close when their program exits or after {} minutes without input)".to_string(),
+    );
+    m.insert("pty_send_brief".to_string(), "Session {}: {} line(s) of output".to_string());
+    m.insert(
+        "pty_exited".to_string(),
+        "Session {} ended: the program exited with code {}".to_string(),
+    );
+    m.insert("pty_closed".to_string(), "Closed terminal session {}".to_string());
+    m.insert("pty_no_output".to_string(), "(no new output)".to_string());
+    m.insert("pty_output".to_string(), "New output:\n{}".to_string());
+    m.insert("pty_screen".to_string(), "Screen:\n{}".to_string());
+
+    // MCP tool name collisions
+    m.insert(
+        "mcp_tool_name_taken".to_string(),
+        "Skipped tool {} of MCP server {}: the name {} is already taken".to_string(),
+    );
+
+    // Oversized lines in file_read
+    m.insert(
+        "file_read_line_truncated".to_string(),
+        "[Line {} is longer than {}; only its beginning is shown. Use search or run_command to inspect the rest]".to_string(),
+    );
+
+    // file_move errors
+    m.insert("file_move_failed".to_string(), "Failed to move {}: {}".to_string());
+
     m
 }
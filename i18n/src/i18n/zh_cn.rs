@@ -49,10 +49,6 @@ pub fn get_messages() -> HashMap<String, String> {
         "cmd_runcommand_del".to_string(),
         "从审批列表移除命令".to_string(),
     );
-    m.insert(
-        "cmd_runcommand_info".to_string(),
-        "显示后台命令详情".to_string(),
-    );
 
     // 状态消息
     m.insert("goodbye".to_string(), "再见！".to_string());
@@ -162,11 +158,11 @@ pub fn get_messages() -> HashMap<String, String> {
     );
     m.insert(
         "setup_api_key".to_string(),
-        "请输入 OpenAI API Key".to_string(),
+        "请输入 API Key".to_string(),
     );
     m.insert(
         "setup_api_url".to_string(),
-        "请输入 OpenAI API URL".to_string(),
+        "请输入 API URL".to_string(),
     );
     m.insert("setup_model".to_string(), "请输入默认模型".to_string());
     m.insert(
@@ -435,14 +431,10 @@ pub fn get_messages() -> HashMap<String, String> {
         "run_command_user_rejected".to_string(),
         "用户拒绝了该操作".to_string(),
     );
-    m.insert(
-        "run_command_bg_brief".to_string(),
-        "已启动后台命令: {}".to_string(),
-    );
+    m.insert("run_command_bg_brief".to_string(), "已启动后台任务 {}".to_string());
     m.insert(
         "run_command_bg_output".to_string(),
-        "命令已在后台启动\n运行 ID: {}\n命令: {}\n\n使用 /runcommand info {{}} 查看状态"
-            .to_string(),
+        "命令已在后台启动\n任务 ID: {}\nPID: {}\n命令: {}\n日志: {}\n\n可用 command_status 查看状态、command_kill 终止；任务结束时会收到通知".to_string(),
     );
     m.insert(
         "run_command_fg_brief".to_string(),
@@ -497,26 +489,6 @@ pub fn get_messages() -> HashMap<String, String> {
         "runcommand_del_not_found".to_string(),
         "'{}' 不在审批列表中".to_string(),
     );
-    m.insert(
-        "runcommand_info_header".to_string(),
-        "后台命令信息".to_string(),
-    );
-    m.insert("runcommand_info_id".to_string(), "ID:".to_string());
-    m.insert("runcommand_info_command".to_string(), "命令:".to_string());
-    m.insert("runcommand_info_status".to_string(), "状态:".to_string());
-    m.insert(
-        "runcommand_info_started".to_string(),
-        "开始时间:".to_string(),
-    );
-    m.insert(
-        "runcommand_info_exit_code".to_string(),
-        "退出码:".to_string(),
-    );
-    m.insert("runcommand_info_output".to_string(), "输出".to_string());
-    m.insert(
-        "runcommand_info_not_found".to_string(),
-        "未找到 ID 为 '{}' 的命令".to_string(),
-    );
     m.insert(
         "runcommand_help_header".to_string(),
         "/runcommand 帮助".to_string(),
@@ -603,5 +575,340 @@ pub fn get_messages() -> HashMap<String, String> {
         "修复工具 JSON 失败".to_string(),
     );
 
+    // 服务商设置
+    m.insert("setup_provider".to_string(), "请选择 API 服务商".to_string());
+
+    // Token 用量
+    m.insert("cmd_usage".to_string(), "显示 Token 用量与预估费用".to_string());
+    m.insert("usage_title".to_string(), "Token 用量".to_string());
+    m.insert("usage_session".to_string(), "当前会话".to_string());
+    m.insert("usage_today".to_string(), "今天".to_string());
+    m.insert("usage_project".to_string(), "当前项目".to_string());
+    m.insert("usage_requests".to_string(), "次请求".to_string());
+    m.insert("usage_input".to_string(), "输入".to_string());
+    m.insert("usage_output".to_string(), "输出".to_string());
+    m.insert("usage_tokens".to_string(), "Token".to_string());
+    m.insert("usage_none".to_string(), "暂无 Token 用量记录".to_string());
+    m.insert(
+        "usage_unpriced".to_string(),
+        "{} 次响应的模型未配置价格（可在 config.json 的 model_prices 中设置）".to_string(),
+    );
+
+    // 上下文压缩
+    m.insert("cmd_compact".to_string(), "总结较早的对话以释放上下文".to_string());
+    m.insert("compact_running".to_string(), "正在将 {} 条较早的消息压缩为摘要...".to_string());
+    m.insert("compact_done".to_string(), "已压缩 {} 条消息（原始消息仍保留在历史记录中）".to_string());
+    m.insert("compact_nothing".to_string(), "暂无可压缩的内容".to_string());
+    m.insert("compact_failed".to_string(), "压缩对话失败".to_string());
+    m.insert("compact_empty_summary".to_string(), "模型返回了空摘要".to_string());
+
+    // API 错误分类
+    m.insert("api_stream_interrupted".to_string(), "流式响应中断，正在续传".to_string());
+    m.insert("api_stream_truncated".to_string(), "响应流在回复完成前结束".to_string());
+    m.insert("api_err_auth".to_string(), "认证失败".to_string());
+    m.insert("api_err_rate_limit".to_string(), "请求频率受限".to_string());
+    m.insert("api_err_context_overflow".to_string(), "超出上下文窗口".to_string());
+    m.insert("api_err_server".to_string(), "服务器错误".to_string());
+    m.insert("api_err_invalid_request".to_string(), "请求被拒绝".to_string());
+    m.insert("api_err_network".to_string(), "网络错误".to_string());
+    m.insert("api_err_malformed_stream".to_string(), "响应流格式错误".to_string());
+    m.insert("api_hint_auth".to_string(), "请检查配置文件中的 API 密钥和提供商，或删除配置文件重新运行设置".to_string());
+    m.insert("api_hint_rate_limit".to_string(), "提供商正在限流，请稍后再试或检查套餐配额".to_string());
+    m.insert(
+        "api_hint_context_overflow".to_string(),
+        "对话超出该模型的上下文长度，请运行 /compact 或切换到上下文更大的模型".to_string(),
+    );
+    m.insert("api_hint_server".to_string(), "提供商服务异常，请稍后再试或使用 /model 切换模型".to_string());
+    m.insert("api_hint_invalid_request".to_string(), "提供商拒绝了请求，请检查模型名称和 API 地址".to_string());
+    m.insert("api_hint_network".to_string(), "无法连接 API，请检查网络、代理和 API 地址".to_string());
+    m.insert("api_hint_malformed_stream".to_string(), "响应被截断或损坏，请重新发送消息".to_string());
+
+    // API 录制
+    m.insert("api_recording_to".to_string(), "API 交互录制目录".to_string());
+    m.insert("api_record_failed".to_string(), "录制 API 交互失败".to_string());
+
+    // 模型参数配置
+    m.insert("model_profile_applied".to_string(), "已应用模型参数配置".to_string());
+
+    // 模型回退
+    m.insert("api_fallback_model".to_string(), "{} 不可用，回退到 {}".to_string());
+    m.insert("chat_answered_by".to_string(), "由 {} 回答".to_string());
+
+    // 图片
+    m.insert("file_read_image_brief".to_string(), "已加载图片，{}".to_string());
+    m.insert("file_read_image_output".to_string(), "图片 {} 已附在下一条消息中".to_string());
+    m.insert(
+        "file_read_image_unsupported".to_string(),
+        "不支持的图片格式（png、jpg、gif、webp）: {}".to_string(),
+    );
+    m.insert("tool_images_attached".to_string(), "工具调用返回的图片:".to_string());
+    m.insert("api_err_no_vision".to_string(), "模型不支持图片输入".to_string());
+    m.insert(
+        "api_hint_no_vision".to_string(),
+        "请用 /model 切换到支持视觉的模型，或去掉该模型配置中的 `vision = false`".to_string(),
+    );
+    m.insert("image_attached".to_string(), "已附加图片: {}".to_string());
+    m.insert("image_pending".to_string(), "图片将随下一条消息发送".to_string());
+    m.insert("image_load_failed".to_string(), "加载图片失败".to_string());
+    m.insert("image_usage".to_string(), "用法: /image <路径> [消息]".to_string());
+    m.insert("image_no_vision".to_string(), "当前模型的配置表明它不支持图片，请求将被拒绝".to_string());
+    m.insert("cmd_image".to_string(), "附加图片到下一条消息（也可用 @file.png）".to_string());
+
+    // 工具注册表
+    m.insert("tool_user_rejected".to_string(), "用户拒绝了 {} 调用".to_string());
+
+    // MCP
+    m.insert("mcp_connected".to_string(), "MCP 服务器 {} 已连接（{} 个工具）".to_string());
+    m.insert("mcp_failed".to_string(), "MCP 服务器 {} 启动失败".to_string());
+    m.insert("mcp_tool_brief".to_string(), "{}: {} 个字符".to_string());
+    m.insert("mcp_tool_failed".to_string(), "MCP 服务器 {} 返回错误".to_string());
+    m.insert("mcp_title".to_string(), "MCP 服务器".to_string());
+    m.insert("mcp_none".to_string(), "未配置 MCP 服务器，请在 config.json 的 mcp_servers 中添加".to_string());
+    m.insert("mcp_status_connected".to_string(), "已连接".to_string());
+    m.insert("mcp_status_failed".to_string(), "失败".to_string());
+    m.insert("mcp_status_disabled".to_string(), "已禁用".to_string());
+    m.insert("cmd_mcp".to_string(), "列出 MCP 服务器及其工具".to_string());
+
+    // 脚本工具
+    m.insert("script_tool_brief".to_string(), "输出 {} 行".to_string());
+    m.insert("script_tool_failed".to_string(), "{} 退出码 {}".to_string());
+    m.insert("script_tools_loaded".to_string(), "已加载 {} 个工具（来自 {}）".to_string());
+    m.insert("script_tools_skipped".to_string(), "已跳过与现有工具重名的脚本工具".to_string());
+    m.insert("script_tools_invalid".to_string(), "加载脚本工具失败".to_string());
+
+    // file_read 行范围与限制
+    m.insert("file_read_range_brief".to_string(), "读取第 {}-{} 行，共 {} 行".to_string());
+    m.insert(
+        "file_read_truncated".to_string(),
+        "[文件共 {} 行（{}），仅显示第 {}-{} 行。请使用 offset/limit 读取其余部分]".to_string(),
+    );
+    m.insert("file_read_past_end".to_string(), "[offset 超出文件末尾，文件共 {} 行]".to_string());
+    m.insert("file_read_lossy".to_string(), "[文件不是有效的 UTF-8，无效字节显示为 U+FFFD]".to_string());
+    m.insert("file_read_binary_brief".to_string(), "二进制文件，{}".to_string());
+    m.insert(
+        "file_read_binary".to_string(),
+        "{} 是二进制文件（{}），未显示内容。图片请使用 file_read_image".to_string(),
+    );
+
+    // file_search 内容搜索
+    m.insert("file_search_brief".to_string(), "找到 {} 处匹配，共 {} 个文件".to_string());
+    m.insert("file_search_none".to_string(), "没有找到 {} 的匹配".to_string());
+    m.insert(
+        "file_search_truncated".to_string(),
+        "[已达到 {} 处匹配上限，请缩小 pattern、path 或 include 范围查看其余结果]".to_string(),
+    );
+    m.insert("file_search_bad_pattern".to_string(), "无效的搜索模式: {}".to_string());
+    m.insert("file_search_bad_glob".to_string(), "无效的 glob: {}".to_string());
+
+    // file_glob / file_tree
+    m.insert("file_glob_brief".to_string(), "找到 {} 个路径".to_string());
+    m.insert("file_glob_none".to_string(), "没有匹配该模式的路径".to_string());
+    m.insert(
+        "file_glob_truncated".to_string(),
+        "[仅显示 {} 个路径，共 {} 个；请缩小 pattern 或 path 范围查看其余结果]".to_string(),
+    );
+    m.insert("file_tree_brief".to_string(), "列出 {} 个条目".to_string());
+    m.insert(
+        "file_tree_truncated".to_string(),
+        "[已达到 {} 个条目上限，请列出子目录或降低 max_depth 查看其余内容]".to_string(),
+    );
+
+    // apply_patch 补丁
+    m.insert("apply_patch_parse_error".to_string(), "无效的补丁: {}".to_string());
+    m.insert("apply_patch_empty".to_string(), "补丁中没有文件修改".to_string());
+    m.insert("apply_patch_file_exists".to_string(), "无法创建 {}: 文件已存在".to_string());
+    m.insert("apply_patch_failed_brief".to_string(), "{} 个文件应用补丁失败".to_string());
+    m.insert(
+        "apply_patch_failed".to_string(),
+        "补丁未应用，没有修改任何文件。请根据文件当前内容修正失败的 hunk 后重新发送完整补丁。".to_string(),
+    );
+    m.insert("apply_patch_hunk_failed".to_string(), "{} 号 hunk（{}）无法匹配: {}".to_string());
+    m.insert("apply_patch_closest".to_string(), "最接近的区域为第 {}-{} 行（{} / {} 行相同）:".to_string());
+    m.insert("apply_patch_no_match".to_string(), "文件中没有找到相似的区域".to_string());
+    m.insert(
+        "apply_patch_adjusted".to_string(),
+        "{} 号 hunk（{}）应用于第 {} 行（偏移 {}，fuzz {}）".to_string(),
+    );
+    m.insert("apply_patch_brief".to_string(), "已修改 {} 个文件".to_string());
+    m.insert("apply_patch_header".to_string(), "补丁已应用:".to_string());
+    m.insert("apply_patch_created".to_string(), "  新建 {} (+{})".to_string());
+    m.insert("apply_patch_deleted".to_string(), "  删除 {}".to_string());
+    m.insert("apply_patch_renamed".to_string(), "  重命名为 {} (+{} -{})".to_string());
+    m.insert("apply_patch_modified".to_string(), "  修改 {} (+{} -{})".to_string());
+
+    // apply_patch 审批
+    m.insert("apply_patch_subject".to_string(), "{} 个文件".to_string());
+
+    // file_diff_edit 过期行保护
+    m.insert("file_diff_edit_stale_brief".to_string(), "{} 个 hunk 与文件内容不一致".to_string());
+    m.insert(
+        "file_diff_edit_stale".to_string(),
+        "编辑已拒绝，{} 未修改。以下为当前内容，请重新读取文件并使用更新后的行号重试。".to_string(),
+    );
+    m.insert(
+        "file_diff_edit_stale_hunk".to_string(),
+        "{} 号 hunk（第 {}-{} 行）与 expected_old 不一致。当前内容（> 标出该范围）:".to_string(),
+    );
+    m.insert("file_diff_edit_stale_found".to_string(), "expected_old 出现在第 {} 行开始的位置".to_string());
+    m.insert(
+        "file_diff_edit_changed_since_read".to_string(),
+        "[!] 文件在上次读取后已被修改，请仔细检查下面的结果。".to_string(),
+    );
+
+    // 检查点
+    m.insert("cmd_undo".to_string(), "撤销上一轮对话中修改的文件".to_string());
+    m.insert("cmd_checkpoints".to_string(), "列出本会话的检查点".to_string());
+    m.insert("cmd_restore".to_string(), "恢复到指定检查点之前（含之后的检查点）".to_string());
+    m.insert("checkpoint_unavailable".to_string(), "本轮无法创建检查点".to_string());
+    m.insert("checkpoint_nothing_to_undo".to_string(), "没有可撤销的修改".to_string());
+    m.insert("checkpoint_restore_usage".to_string(), "用法: /restore <id> [--force]".to_string());
+    m.insert("checkpoint_not_found".to_string(), "未找到检查点 #{}，请使用 /checkpoints 查看".to_string());
+    m.insert("checkpoint_already_undone".to_string(), "检查点 #{} 已撤销".to_string());
+    m.insert("checkpoint_none".to_string(), "本会话没有检查点".to_string());
+    m.insert("checkpoint_title".to_string(), "检查点".to_string());
+    m.insert("checkpoint_files".to_string(), "{} 个文件".to_string());
+    m.insert("checkpoint_undone".to_string(), "已撤销".to_string());
+    m.insert("checkpoint_conflicts".to_string(), "以下文件在修改后又被改动过，未恢复任何文件:".to_string());
+    m.insert("checkpoint_force_hint".to_string(), "使用 {} 强制覆盖".to_string());
+    m.insert("checkpoint_restored".to_string(), "已撤销检查点 {}".to_string());
+
+    // Git 工具与提交
+    m.insert("git_not_repository".to_string(), "不是 git 仓库: {}".to_string());
+    m.insert("git_failed".to_string(), "git 执行失败: {}".to_string());
+    m.insert(
+        "git_output_truncated".to_string(),
+        "[输出超过 {} KB 已截断，可使用 path 或 stat 缩小范围]".to_string(),
+    );
+    m.insert("git_status_brief".to_string(), "{} 个文件有变更".to_string());
+    m.insert("git_status_clean".to_string(), "工作区无变更".to_string());
+    m.insert("git_diff_brief".to_string(), "{} 个文件有改动".to_string());
+    m.insert("git_diff_empty".to_string(), "没有差异".to_string());
+    m.insert("git_log_brief".to_string(), "{} 个提交".to_string());
+    m.insert("git_log_empty".to_string(), "没有提交".to_string());
+    m.insert("cmd_commit".to_string(), "为暂存的修改生成提交信息并提交".to_string());
+    m.insert("cmd_commit_auto".to_string(), "每轮对话结束后自动提交修改的文件".to_string());
+    m.insert("commit_nothing_staged".to_string(), "没有暂存的修改，请先使用 git add".to_string());
+    m.insert("commit_drafting".to_string(), "正在生成提交信息...".to_string());
+    m.insert("commit_draft_failed".to_string(), "无法生成提交信息".to_string());
+    m.insert("commit_draft_empty".to_string(), "模型返回的提交信息为空".to_string());
+    m.insert("commit_message".to_string(), "提交信息".to_string());
+    m.insert("commit_choice_commit".to_string(), "提交".to_string());
+    m.insert("commit_choice_edit".to_string(), "编辑提交信息".to_string());
+    m.insert("commit_choice_cancel".to_string(), "取消".to_string());
+    m.insert("commit_cancelled".to_string(), "已取消提交".to_string());
+    m.insert("commit_done".to_string(), "已提交".to_string());
+    m.insert("commit_auto_usage".to_string(), "用法: /commit auto [on|off]".to_string());
+    m.insert("commit_auto_on".to_string(), "自动提交已开启：每轮对话修改的文件会被提交".to_string());
+    m.insert("commit_auto_off".to_string(), "自动提交已关闭".to_string());
+    m.insert("commit_auto_failed".to_string(), "自动提交失败".to_string());
+    m.insert("commit_fallback_message".to_string(), "更新 {}".to_string());
+
+    // file_move / file_copy / file_delete / dir_create
+    m.insert("file_paths_more".to_string(), "... 另有 {} 项".to_string());
+    m.insert("file_transfer_into_itself".to_string(), "不能把路径移动或复制到其自身之中: {}".to_string());
+    m.insert(
+        "file_transfer_target_exists".to_string(),
+        "目标已存在: {}（如需替换文件请设置 overwrite）".to_string(),
+    );
+    m.insert("file_transfer_target_is_dir".to_string(), "只能覆盖文件，不能覆盖目录: {}".to_string());
+    m.insert("file_move_preview".to_string(), "将要移动的路径:".to_string());
+    m.insert("file_move_brief".to_string(), "移动了 {} 个文件".to_string());
+    m.insert("file_move_done".to_string(), "已将 {} 移动到 {}（{} 个文件）".to_string());
+    m.insert("file_copy_dir_needs_recursive".to_string(), "{} 是目录，复制目录需要设置 recursive".to_string());
+    m.insert("file_copy_preview".to_string(), "将要复制的路径:".to_string());
+    m.insert("file_copy_brief".to_string(), "复制了 {} 个文件".to_string());
+    m.insert("file_copy_done".to_string(), "已将 {} 复制到 {}（{} 个文件）".to_string());
+    m.insert("file_delete_working_dir".to_string(), "拒绝删除工作目录或其上级目录: {}".to_string());
+    m.insert(
+        "file_delete_needs_recursive".to_string(),
+        "{} 是包含 {} 项内容的目录，删除需要设置 recursive".to_string(),
+    );
+    m.insert("file_delete_preview".to_string(), "将要删除的路径:".to_string());
+    m.insert("file_delete_brief".to_string(), "已删除 {}".to_string());
+    m.insert("file_delete_done".to_string(), "已删除 {}（{} 个文件）".to_string());
+    m.insert("dir_create_exists".to_string(), "目录已存在: {}".to_string());
+    m.insert("dir_create_preview".to_string(), "将要创建的目录:".to_string());
+    m.insert("dir_create_brief".to_string(), "已创建 {}".to_string());
+    m.insert("dir_create_done".to_string(), "已创建目录: {}".to_string());
+
+    // 路径策略
+    m.insert(
+        "path_outside_workspace".to_string(),
+        "路径不在工作区内: {}（允许的根目录: {}；可在配置的 allowed_roots 中添加）".to_string(),
+    );
+    m.insert("path_sensitive_rejected".to_string(), "用户拒绝访问敏感路径: {}".to_string());
+    m.insert(
+        "path_policy_invalid_pattern".to_string(),
+        "配置中的 sensitive_paths 模式无效，仅使用内置模式".to_string(),
+    );
+    m.insert("file_search_sensitive_skipped".to_string(), "已跳过 {} 个敏感文件".to_string());
+    m.insert("path_access_read".to_string(), "读取".to_string());
+    m.insert("path_access_write".to_string(), "修改".to_string());
+    m.insert("sensitive_title".to_string(), "  [!] 敏感路径访问".to_string());
+    m.insert("sensitive_action_wants".to_string(), "AI 想要{}以下敏感路径:".to_string());
+    m.insert("sensitive_matches".to_string(), "（匹配 {}）".to_string());
+    m.insert("sensitive_opt_reject".to_string(), "拒绝".to_string());
+    m.insert("sensitive_opt_allow".to_string(), "仅本次允许".to_string());
+
+    // 命令执行
+    m.insert("run_command_cwd_invalid".to_string(), "命令目录不存在: {}".to_string());
+    m.insert("run_command_timed_out".to_string(), "命令超过 {} 秒未结束，已终止".to_string());
+    m.insert("run_command_interrupted".to_string(), "命令已被用户中止".to_string());
+    m.insert("run_command_output_truncated".to_string(), "... [省略 {} 行] ...".to_string());
+
+    // 后台任务
+    m.insert("job_status_running".to_string(), "运行中".to_string());
+    m.insert("job_status_exited".to_string(), "已退出，退出码 {}".to_string());
+    m.insert("job_status_signaled".to_string(), "被信号终止".to_string());
+    m.insert("job_status_killed".to_string(), "已终止".to_string());
+    m.insert("job_notice".to_string(), "[后台任务 {} 已结束] `{}` {}。输出的最后几行:\n{}".to_string());
+    m.insert("job_notice_shown".to_string(), "后台任务 {}（{}）{}".to_string());
+    m.insert("job_not_found".to_string(), "没有 ID 为 {} 的后台任务".to_string());
+    m.insert("job_none".to_string(), "没有后台任务".to_string());
+    m.insert(
+        "job_details".to_string(),
+        "任务 {}: {}\n命令: {}\nPID: {}\n目录: {}\n启动时间: {}\n日志: {}\n\n输出的最后几行:\n{}".to_string(),
+    );
+    m.insert("command_status_list_brief".to_string(), "{} 个后台任务".to_string());
+    m.insert("command_status_brief".to_string(), "任务 {}: {}".to_string());
+    m.insert("command_kill_done".to_string(), "任务 {} 已终止".to_string());
+    m.insert("command_kill_not_running".to_string(), "任务 {} 未在运行（{}）".to_string());
+    m.insert("cmd_jobs".to_string(), "列出后台任务".to_string());
+    m.insert("cmd_jobs_kill".to_string(), "终止后台任务".to_string());
+    m.insert("cmd_jobs_tail".to_string(), "查看任务输出的末尾".to_string());
+    m.insert("jobs_header".to_string(), "后台任务".to_string());
+    m.insert("jobs_tail_header".to_string(), "任务 {} 输出的最后 {} 行".to_string());
+    m.insert("jobs_tail_empty".to_string(), "（暂无输出）".to_string());
+    m.insert("jobs_read_log_failed".to_string(), "读取任务日志失败".to_string());
+
+    // 交互式终端会话
+    m.insert("pty_started".to_string(), "已启动终端会话 {}".to_string());
+    m.insert(
+        "pty_started_details".to_string(),
+        "会话 {} 正在运行 `{}`。使用 pty_send 输入，使用 pty_close 结束。".to_string(),
+    );
+    m.insert("pty_start_failed".to_string(), "启动终端会话失败: {}".to_string());
+    m.insert("pty_write_failed".to_string(), "发送输入失败: {}".to_string());
+    m.insert("pty_unknown_key".to_string(), "未知按键: {}".to_string());
+    m.insert("pty_not_found".to_string(), "没有 ID 为 {} 的终端会话（程序退出或 {} 分钟无输入后会话会关闭）".to_string());
+    m.insert("pty_send_brief".to_string(), "会话 {}: {} 行输出".to_string());
+    m.insert("pty_exited".to_string(), "会话 {} 已结束: 程序退出码 {}".to_string());
+    m.insert("pty_closed".to_string(), "已关闭终端会话 {}".to_string());
+    m.insert("pty_no_output".to_string(), "（没有新输出）".to_string());
+    m.insert("pty_output".to_string(), "新输出:\n{}".to_string());
+    m.insert("pty_screen".to_string(), "屏幕:\n{}".to_string());
+
+    // MCP 工具名称冲突
+    m.insert("mcp_tool_name_taken".to_string(), "已跳过 MCP 工具 {}（服务器 {}）: 名称 {} 已被占用".to_string());
+
+    // file_read 超长行
+    m.insert(
+        "file_read_line_truncated".to_string(),
+        "[第 {} 行超过 {}，仅显示开头部分。可使用搜索或 run_command 查看其余内容]".to_string(),
+    );
+
+    // file_move 错误
+    m.insert("file_move_failed".to_string(), "移动 {} 失败: {}".to_string());
+
     m
 }
@@ -0,0 +1,5 @@
+pub mod mcp;
+
+pub use mcp::{
+    server_statuses, start_servers, McpClient, McpTool, McpToolInfo, ServerState, ServerStatus,
+};
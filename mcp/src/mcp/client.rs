@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use config::McpServerConfig;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use super::protocol::{McpToolInfo, PROTOCOL_VERSION};
+use super::transport::{self, Transport};
+
+/// Longest wait for the handshake and tool listing
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest wait for a tool call
+const CALL_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Connected MCP server
+pub struct McpClient {
+    name: String,
+    transport: Box<dyn Transport>,
+    tools: Vec<McpToolInfo>,
+}
+
+/// Outcome of `tools/call`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallToolResult {
+    /// Content parts (`text`, `image`, `resource`, ...)
+    pub content: Vec<Value>,
+    pub is_error: bool,
+}
+
+impl McpClient {
+    /// Start or reach the server, run the initialize handshake and list its tools
+    pub async fn connect(name: &str, config: &McpServerConfig) -> Result<Self> {
+        let transport = transport::connect(config)?;
+        tokio::time::timeout(CONNECT_TIMEOUT, Self::handshake(name, transport))
+            .await
+            .context("timed out during initialize")?
+    }
+
+    async fn handshake(name: &str, transport: Box<dyn Transport>) -> Result<Self> {
+        transport
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": { "name": "friendev", "version": env!("CARGO_PKG_VERSION") },
+                }),
+            )
+            .await
+            .context("initialize failed")?;
+        transport
+            .notify("notifications/initialized", json!({}))
+            .await?;
+
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let mut page = transport
+                .request("tools/list", params)
+                .await
+                .context("tools/list failed")?;
+            let listed: Vec<McpToolInfo> = serde_json::from_value(page["tools"].take())?;
+            tools.extend(listed);
+            cursor = page["nextCursor"].as_str().map(String::from);
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            transport,
+            tools,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tools(&self) -> &[McpToolInfo] {
+        &self.tools
+    }
+
+    /// Command line or URL of the server
+    pub fn describe(&self) -> String {
+        self.transport.describe()
+    }
+
+    pub async fn call_tool(&self, tool: &str, arguments: Value) -> Result<CallToolResult> {
+        let call = self.transport.request(
+            "tools/call",
+            json!({ "name": tool, "arguments": arguments }),
+        );
+        let mut result = tokio::time::timeout(CALL_TIMEOUT, call)
+            .await
+            .context("tool call timed out")??;
+        Ok(CallToolResult {
+            content: serde_json::from_value(result["content"].take()).unwrap_or_default(),
+            is_error: result["isError"].as_bool().unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_llm::{MockResponse, MockServer};
+
+    fn rpc(id: u64, result: Value) -> MockResponse {
+        MockResponse::Json {
+            body: json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_handshake_lists_and_calls_tools() {
+        let server = MockServer::start(vec![
+            rpc(1, json!({ "protocolVersion": PROTOCOL_VERSION, "capabilities": {} })),
+            // notifications/initialized
+            MockResponse::Json { body: json!({}) },
+            rpc(2, json!({ "tools": [{ "name": "a" }], "nextCursor": "p2" })),
+            rpc(
+                3,
+                json!({ "tools": [{
+                    "name": "lookup",
+                    "description": "Look up a ticket",
+                    "inputSchema": { "type": "object", "properties": { "id": { "type": "string" } } },
+                    "annotations": { "readOnlyHint": true }
+                }] }),
+            ),
+            MockResponse::sse(
+                "data: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\",\"params\":{}}\n\n\
+                 data: {\"jsonrpc\":\"2.0\",\"id\":4,\"result\":{\"content\":[{\"type\":\"text\",\"text\":\"OPEN\"}]}}\n\n",
+            ),
+        ])
+        .await
+        .unwrap();
+
+        let config = McpServerConfig {
+            url: Some(server.url()),
+            ..Default::default()
+        };
+        let client = McpClient::connect("tickets", &config).await.unwrap();
+        let names: Vec<&str> = client.tools().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "lookup"]);
+        assert!(client.tools()[1].annotations.read_only_hint);
+
+        let result = client
+            .call_tool("lookup", json!({ "id": "T-1" }))
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+        assert_eq!(result.content[0]["text"], "OPEN");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].body["method"], "initialize");
+        assert_eq!(requests[2].body["params"]["cursor"], Value::Null);
+        assert_eq!(requests[3].body["params"]["cursor"], "p2");
+        assert_eq!(requests[4].body["params"]["arguments"]["id"], "T-1");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdio_server() {
+        // A scripted server: skips a log line and answers a ping before the tool list
+        let script = r#"
+read line; echo '{"jsonrpc":"2.0","id":1,"result":{"capabilities":{}}}'
+read line
+read line; echo 'starting up'; echo '{"jsonrpc":"2.0","id":"s1","method":"ping"}'
+read pong; echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo"}]}}'
+read line; echo '{"jsonrpc":"2.0","id":3,"result":{"content":[],"isError":true}}'
+"#;
+        let config = McpServerConfig {
+            command: Some("sh".to_string()),
+            args: vec!["-c".to_string(), script.to_string()],
+            ..Default::default()
+        };
+        let client = McpClient::connect("local", &config).await.unwrap();
+        assert_eq!(client.tools()[0].name, "echo");
+        assert!(client.call_tool("echo", json!({})).await.unwrap().is_error);
+    }
+}
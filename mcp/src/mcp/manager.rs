@@ -0,0 +1,117 @@
+use config::Config;
+use std::sync::{Arc, Mutex};
+use ui::get_i18n;
+
+use super::client::McpClient;
+use super::tool::McpTool;
+
+/// Servers started by `start_servers`, in name order
+static SERVERS: Mutex<Vec<ServerStatus>> = Mutex::new(Vec::new());
+
+/// Connection state of a configured server, shown by `/mcp`
+#[derive(Debug, Clone)]
+pub struct ServerStatus {
+    pub name: String,
+    /// Command line or URL
+    pub target: String,
+    pub state: ServerState,
+}
+
+#[derive(Debug, Clone)]
+pub enum ServerState {
+    /// Connected; namespaced names and descriptions of the registered tools
+    Connected {
+        tools: Vec<(String, String)>,
+    },
+    Failed(String),
+    Disabled,
+}
+
+/// Start every configured server and register its tools with the tool registry
+pub async fn start_servers(config: &Config) {
+    let mut names: Vec<&String> = config.mcp_servers.keys().collect();
+    names.sort();
+
+    let connections = names.iter().map(|name| async move {
+        let server = &config.mcp_servers[*name];
+        let target = server
+            .command
+            .iter()
+            .chain(server.args.iter())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let target = server
+            .url
+            .clone()
+            .filter(|_| target.is_empty())
+            .unwrap_or(target);
+
+        let state = if server.disabled {
+            ServerState::Disabled
+        } else {
+            match McpClient::connect(name, server).await {
+                Ok(client) => register_tools(Arc::new(client)),
+                Err(e) => ServerState::Failed(format!("{:#}", e)),
+            }
+        };
+        ServerStatus {
+            name: name.to_string(),
+            target,
+            state,
+        }
+    });
+    let statuses = futures::future::join_all(connections).await;
+
+    for status in &statuses {
+        print_status(status);
+    }
+    *SERVERS.lock().unwrap() = statuses;
+}
+
+/// Snapshot of the configured servers
+pub fn server_statuses() -> Vec<ServerStatus> {
+    SERVERS.lock().unwrap().clone()
+}
+
+fn register_tools(client: Arc<McpClient>) -> ServerState {
+    let mut tools = Vec::new();
+    for info in client.tools() {
+        let tool = McpTool::new(client.clone(), info.clone());
+        let name = tools::Tool::name(&tool).to_string();
+        // Registering would silently replace the other tool (after sanitizing, names like
+        // `a.b` and `a_b` of one server are the same)
+        if tools::registry().get(&name).is_some() {
+            eprintln!(
+                "\x1b[33m[!] {}\x1b[0m",
+                get_i18n()
+                    .get("mcp_tool_name_taken")
+                    .replacen("{}", &info.name, 1)
+                    .replacen("{}", client.name(), 1)
+                    .replacen("{}", &name, 1)
+            );
+            continue;
+        }
+        tools.push((name, info.description.clone()));
+        tools::register_tool(tool);
+    }
+    ServerState::Connected { tools }
+}
+
+fn print_status(status: &ServerStatus) {
+    let i18n = get_i18n();
+    match &status.state {
+        ServerState::Connected { tools } => println!(
+            "\x1b[90m[i] {}\x1b[0m",
+            i18n.get("mcp_connected")
+                .replacen("{}", &status.name, 1)
+                .replacen("{}", &tools.len().to_string(), 1)
+        ),
+        ServerState::Failed(error) => eprintln!(
+            "\x1b[33m[!] {}:\x1b[0m {}",
+            i18n.get("mcp_failed").replace("{}", &status.name),
+            error
+        ),
+        ServerState::Disabled => {}
+    }
+}
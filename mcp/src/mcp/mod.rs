@@ -0,0 +1,11 @@
+mod client;
+mod manager;
+mod protocol;
+mod tool;
+mod transport;
+
+// Re-export public API
+pub use client::McpClient;
+pub use manager::{server_statuses, start_servers, ServerState, ServerStatus};
+pub use protocol::McpToolInfo;
+pub use tool::McpTool;
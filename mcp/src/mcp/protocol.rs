@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// MCP protocol revision requested in the initialize handshake
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// JSON-RPC request
+pub fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+/// JSON-RPC notification (no id, no response)
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Whether a message is the response to request `id`
+pub fn is_response_to(message: &Value, id: u64) -> bool {
+    message.get("method").is_none() && message["id"].as_u64() == Some(id)
+}
+
+/// Answer to a request the server sent us: `ping` is answered, anything else is refused
+pub fn reply_to_server_request(message: &Value) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let method = message["method"].as_str()?;
+    Some(if method == "ping" {
+        json!({ "jsonrpc": "2.0", "id": id, "result": {} })
+    } else {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": format!("Method not supported: {}", method) }
+        })
+    })
+}
+
+/// Result of a JSON-RPC response, or its error as an `Err`
+pub fn into_result(mut response: Value) -> Result<Value> {
+    if let Some(error) = response.get("error") {
+        return Err(anyhow!(
+            "{} (code {})",
+            error["message"].as_str().unwrap_or("unknown error"),
+            error["code"]
+        ));
+    }
+    Ok(response["result"].take())
+}
+
+/// Tool advertised by a server in `tools/list`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "empty_schema")]
+    pub input_schema: Value,
+    #[serde(default)]
+    pub annotations: ToolAnnotations,
+}
+
+/// Behaviour hints of a tool
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    #[serde(default)]
+    pub read_only_hint: bool,
+}
+
+fn empty_schema() -> Value {
+    json!({ "type": "object", "properties": {} })
+}
+
+/// Parse the content of an SSE response body into its JSON-RPC messages
+pub fn sse_messages(body: &str) -> Vec<Value> {
+    let mut messages = Vec::new();
+    let mut data = String::new();
+    for line in body.lines().chain(std::iter::once("")) {
+        if let Some(value) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(value.strip_prefix(' ').unwrap_or(value));
+        } else if line.is_empty() && !data.is_empty() {
+            if let Ok(message) = serde_json::from_str(&data) {
+                messages.push(message);
+            }
+            data.clear();
+        }
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_messages() {
+        let body =
+            "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n\
+                    data: {\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{}}\n";
+        let messages = sse_messages(body);
+        assert_eq!(messages.len(), 2);
+        assert!(!is_response_to(&messages[0], 3));
+        assert!(is_response_to(&messages[1], 3));
+    }
+
+    #[test]
+    fn test_error_response() {
+        let response = json!({"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"bad args"}});
+        let error = into_result(response).unwrap_err().to_string();
+        assert_eq!(error, "bad args (code -32602)");
+    }
+}
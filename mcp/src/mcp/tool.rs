@@ -0,0 +1,186 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use history::ImageUrl;
+use serde_json::Value;
+use std::sync::Arc;
+use tools::{Tool, ToolContext, ToolResult};
+use ui::get_i18n;
+
+use super::client::{CallToolResult, McpClient};
+use super::protocol::McpToolInfo;
+
+/// Longest function name accepted by the chat APIs
+const MAX_TOOL_NAME: usize = 64;
+
+/// Prefix of every MCP tool name, followed by `<server>__<tool>`
+pub const TOOL_PREFIX: &str = "mcp__";
+
+/// A server tool exposed to the model as `mcp__<server>__<tool>`
+pub struct McpTool {
+    name: String,
+    info: McpToolInfo,
+    client: Arc<McpClient>,
+}
+
+impl McpTool {
+    pub fn new(client: Arc<McpClient>, info: McpToolInfo) -> Self {
+        Self {
+            name: namespaced_name(client.name(), &info.name),
+            info,
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.info.description
+    }
+
+    fn parameters(&self) -> Value {
+        self.info.input_schema.clone()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.info.annotations.read_only_hint
+    }
+
+    // Every call is confirmed, hints come from a third-party server
+    fn approval(&self) -> tools::ApprovalPolicy {
+        tools::ApprovalPolicy::Prompt
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        let arguments: Value = serde_json::from_str(arguments)?;
+        let result = self.client.call_tool(&self.info.name, arguments).await?;
+        Ok(into_tool_result(self.client.name(), result))
+    }
+}
+
+/// `mcp__<server>__<tool>`, limited to the characters and length the chat APIs accept.
+/// Names that are too long are cut and end in a hash of the full name, so they stay distinct
+pub fn namespaced_name(server: &str, tool: &str) -> String {
+    let sanitize = |s: &str| -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    };
+    let mut name = format!("{}{}__{}", TOOL_PREFIX, sanitize(server), sanitize(tool));
+    if name.len() > MAX_TOOL_NAME {
+        let hash = format!("_{:08x}", fnv1a(&format!("{}\0{}", server, tool)));
+        name.truncate(MAX_TOOL_NAME - hash.len());
+        name.push_str(&hash);
+    }
+    name
+}
+
+/// 32-bit FNV-1a, stable across runs and Rust versions (unlike `DefaultHasher`)
+fn fnv1a(text: &str) -> u32 {
+    text.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Text parts become the message, image parts are attached for the model to see
+fn into_tool_result(server: &str, result: CallToolResult) -> ToolResult {
+    let mut texts = Vec::new();
+    let mut images = Vec::new();
+    for part in &result.content {
+        match part["type"].as_str() {
+            Some("text") => texts.push(part["text"].as_str().unwrap_or("").to_string()),
+            Some("image") => images.push(ImageUrl {
+                url: format!(
+                    "data:{};base64,{}",
+                    part["mimeType"].as_str().unwrap_or("image/png"),
+                    part["data"].as_str().unwrap_or("")
+                ),
+                detail: None,
+            }),
+            Some("resource") => {
+                let resource = &part["resource"];
+                texts.push(
+                    resource["text"]
+                        .as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| resource["uri"].to_string()),
+                );
+            }
+            _ => texts.push(part.to_string()),
+        }
+    }
+    let output = texts.join("\n");
+
+    let i18n = get_i18n();
+    if result.is_error {
+        let brief = i18n.get("mcp_tool_failed").replace("{}", server);
+        let mut failed = ToolResult::error(brief);
+        if !output.is_empty() {
+            failed.message = output;
+        }
+        return failed;
+    }
+    let brief = i18n
+        .get("mcp_tool_brief")
+        .replacen("{}", server, 1)
+        .replacen("{}", &output.chars().count().to_string(), 1);
+    ToolResult::ok(brief, output).with_images(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_namespaced_name() {
+        assert_eq!(namespaced_name("jira", "get_issue"), "mcp__jira__get_issue");
+        assert_eq!(namespaced_name("my server", "a.b"), "mcp__my_server__a_b");
+        assert_eq!(namespaced_name("s", &"x".repeat(100)).len(), MAX_TOOL_NAME);
+
+        // Long names that only differ after the cut stay distinct, and are stable
+        let long = "x".repeat(70);
+        let a = namespaced_name("server", &format!("{}_a", long));
+        let b = namespaced_name("server", &format!("{}_b", long));
+        assert_ne!(a, b);
+        assert_eq!(a.len(), MAX_TOOL_NAME);
+        assert_eq!(a, namespaced_name("server", &format!("{}_a", long)));
+    }
+
+    #[test]
+    fn test_into_tool_result() {
+        let result = into_tool_result(
+            "shots",
+            CallToolResult {
+                content: vec![
+                    json!({ "type": "text", "text": "captured" }),
+                    json!({ "type": "image", "data": "iVBO", "mimeType": "image/png" }),
+                ],
+                is_error: false,
+            },
+        );
+        assert!(result.success);
+        assert_eq!(result.message, "captured");
+        assert_eq!(result.images[0].url, "data:image/png;base64,iVBO");
+
+        let result = into_tool_result(
+            "shots",
+            CallToolResult {
+                content: vec![json!({ "type": "text", "text": "no display" })],
+                is_error: true,
+            },
+        );
+        assert!(!result.success);
+        assert_eq!(result.message, "no display");
+    }
+}
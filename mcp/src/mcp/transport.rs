@@ -0,0 +1,209 @@
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use config::McpServerConfig;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex as StdMutex;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+use super::protocol::{self, is_response_to, reply_to_server_request};
+
+/// JSON-RPC channel to one MCP server
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send a request and wait for its result
+    async fn request(&self, method: &str, params: Value) -> Result<Value>;
+
+    /// Send a notification
+    async fn notify(&self, method: &str, params: Value) -> Result<()>;
+
+    /// Short description for `/mcp`
+    fn describe(&self) -> String;
+}
+
+/// Open the transport a server config asks for
+pub fn connect(config: &McpServerConfig) -> Result<Box<dyn Transport>> {
+    match (&config.command, &config.url) {
+        (Some(command), _) => Ok(Box::new(StdioTransport::spawn(
+            command,
+            &config.args,
+            &config.env,
+        )?)),
+        (None, Some(url)) => Ok(Box::new(HttpTransport::new(url, &config.headers))),
+        (None, None) => bail!("either `command` or `url` must be set"),
+    }
+}
+
+/// Server running as a subprocess, newline-delimited JSON over stdin/stdout
+pub struct StdioTransport {
+    description: String,
+    io: Mutex<StdioIo>,
+    next_id: AtomicU64,
+}
+
+struct StdioIo {
+    // Held so the server is killed when the transport is dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl StdioTransport {
+    pub fn spawn(command: &str, args: &[String], env: &HashMap<String, String>) -> Result<Self> {
+        let mut child = Command::new(command)
+            .args(args)
+            .envs(env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // Server logs would garble the REPL
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start `{}`", command))?;
+
+        let stdin = child.stdin.take().context("stdin not captured")?;
+        let stdout = child.stdout.take().context("stdout not captured")?;
+        Ok(Self {
+            description: std::iter::once(command.to_string())
+                .chain(args.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(" "),
+            io: Mutex::new(StdioIo {
+                _child: child,
+                stdin,
+                stdout: BufReader::new(stdout),
+            }),
+            next_id: AtomicU64::new(1),
+        })
+    }
+}
+
+impl StdioIo {
+    async fn send(&mut self, message: &Value) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut io = self.io.lock().await;
+        io.send(&protocol::request(id, method, params)).await?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if io.stdout.read_line(&mut line).await? == 0 {
+                bail!("server exited");
+            }
+            // Servers may interleave notifications, requests and stray log lines
+            let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if is_response_to(&message, id) {
+                return protocol::into_result(message);
+            }
+            if let Some(reply) = reply_to_server_request(&message) {
+                io.send(&reply).await?;
+            }
+        }
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> Result<()> {
+        let mut io = self.io.lock().await;
+        io.send(&protocol::notification(method, params)).await
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
+}
+
+/// Server reached over the streamable HTTP transport
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    headers: HashMap<String, String>,
+    session_id: StdMutex<Option<String>>,
+    next_id: AtomicU64,
+}
+
+impl HttpTransport {
+    pub fn new(url: &str, headers: &HashMap<String, String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            headers: headers.clone(),
+            session_id: StdMutex::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    async fn post(&self, message: &Value) -> Result<reqwest::Response> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(message);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let session_id = self.session_id.lock().unwrap().clone();
+        if let Some(session_id) = session_id {
+            request = request.header("Mcp-Session-Id", session_id);
+        }
+
+        let response = request.send().await?;
+        if let Some(session_id) = response.headers().get("mcp-session-id") {
+            *self.session_id.lock().unwrap() = session_id.to_str().ok().map(String::from);
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            bail!("HTTP {}: {}", status.as_u16(), body.trim());
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let response = self.post(&protocol::request(id, method, params)).await?;
+
+        let is_sse = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+        let body = response.text().await?;
+        let message = if is_sse {
+            protocol::sse_messages(&body)
+                .into_iter()
+                .find(|message| is_response_to(message, id))
+                .ok_or_else(|| anyhow!("no response to request {} in event stream", id))?
+        } else {
+            serde_json::from_str(&body).context("invalid JSON-RPC response")?
+        };
+        protocol::into_result(message)
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> Result<()> {
+        self.post(&protocol::notification(method, params)).await?;
+        Ok(())
+    }
+
+    fn describe(&self) -> String {
+        self.url.clone()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod mock_llm;
+
+pub use mock_llm::{load_transcript, MockResponse, MockServer, MockToolCall, RecordedRequest};
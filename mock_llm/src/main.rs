@@ -0,0 +1,52 @@
+// Mock LLM server for local end-to-end testing without a network or API key.
+//
+// Usage:
+//   mock-llm [--port <port>] [--replay <dir>] [--script <file.json>] [--chunk-size <bytes>]
+//
+// `--replay` serves a transcript recorded with `friendev --record <dir>`,
+// `--script` serves a JSON array of responses (see `MockResponse`).
+
+use anyhow::{Context, Result};
+use mock_llm::{load_transcript, MockResponse, MockServer};
+use std::path::PathBuf;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let value = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+
+    let port = value("--port").unwrap_or_else(|| "8080".to_string());
+    let chunk_size = match value("--chunk-size") {
+        Some(size) => size.parse().context("--chunk-size must be a number")?,
+        None => 64,
+    };
+
+    let mut responses = Vec::new();
+    if let Some(dir) = value("--replay") {
+        responses.extend(load_transcript(&PathBuf::from(dir))?);
+    }
+    if let Some(file) = value("--script") {
+        let text = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read script {}", file))?;
+        let script: Vec<MockResponse> =
+            serde_json::from_str(&text).with_context(|| format!("Invalid script {}", file))?;
+        responses.extend(script);
+    }
+
+    let count = responses.len();
+    let server = MockServer::bind(&format!("127.0.0.1:{}", port), responses, chunk_size).await?;
+    println!(
+        "Mock LLM server listening on {} ({} scripted responses)",
+        server.url(),
+        count
+    );
+    println!("Set api_url to {} and provider to openai", server.url());
+
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
@@ -0,0 +1,8 @@
+mod response;
+mod server;
+mod transcript;
+
+// Re-export public API
+pub use response::{MockResponse, MockToolCall};
+pub use server::{MockServer, RecordedRequest};
+pub use transcript::load_transcript;
@@ -0,0 +1,266 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Characters per streamed content delta
+const CONTENT_CHUNK_CHARS: usize = 8;
+
+/// One scripted answer of the mock server, consumed by the next chat request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MockResponse {
+    /// Assistant text, streamed as OpenAI chat completion chunks
+    Text { content: String },
+    /// Parallel tool calls, streamed with interleaved argument fragments
+    ToolCalls { calls: Vec<MockToolCall> },
+    /// Raw SSE body served as-is (recorded transcripts, malformed or cut-off streams)
+    Sse { body: String },
+    /// Raw JSON body for non-streaming requests
+    Json { body: Value },
+    /// Error response
+    Error {
+        status: u16,
+        #[serde(default)]
+        body: String,
+        /// `Retry-After` header in seconds
+        #[serde(default)]
+        retry_after: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockToolCall {
+    pub id: String,
+    pub name: String,
+    /// JSON encoded arguments
+    pub arguments: String,
+}
+
+impl MockToolCall {
+    pub fn new(id: &str, name: &str, arguments: Value) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+        }
+    }
+}
+
+impl MockResponse {
+    pub fn text(content: &str) -> Self {
+        Self::Text {
+            content: content.to_string(),
+        }
+    }
+
+    pub fn tool_calls(calls: Vec<MockToolCall>) -> Self {
+        Self::ToolCalls { calls }
+    }
+
+    pub fn sse(body: &str) -> Self {
+        Self::Sse {
+            body: body.to_string(),
+        }
+    }
+
+    pub fn error(status: u16, body: &str) -> Self {
+        Self::Error {
+            status,
+            body: body.to_string(),
+            retry_after: None,
+        }
+    }
+
+    /// SSE body of a streaming response
+    pub fn to_sse(&self, model: &str) -> String {
+        match self {
+            Self::Text { content } => {
+                let chars: Vec<char> = content.chars().collect();
+                let mut events: Vec<Value> = chars
+                    .chunks(CONTENT_CHUNK_CHARS)
+                    .map(|c| delta(model, json!({ "content": c.iter().collect::<String>() })))
+                    .collect();
+                events.push(finish(model, "stop"));
+                events.push(usage(model, content.len()));
+                sse_body(&events)
+            }
+            Self::ToolCalls { calls } => {
+                let mut events = Vec::new();
+                for (index, call) in calls.iter().enumerate() {
+                    events.push(delta(
+                        model,
+                        json!({ "tool_calls": [{
+                            "index": index,
+                            "id": call.id,
+                            "type": "function",
+                            "function": { "name": call.name, "arguments": "" }
+                        }]}),
+                    ));
+                }
+                // Interleave the argument fragments of all calls, like parallel calls arrive
+                let halves: Vec<(String, String)> = calls
+                    .iter()
+                    .map(|c| {
+                        let mid = c.arguments.len() / 2;
+                        let mid = (mid..=c.arguments.len())
+                            .find(|&i| c.arguments.is_char_boundary(i))
+                            .unwrap_or(c.arguments.len());
+                        (
+                            c.arguments[..mid].to_string(),
+                            c.arguments[mid..].to_string(),
+                        )
+                    })
+                    .collect();
+                for part in 0..2 {
+                    for (index, (first, second)) in halves.iter().enumerate() {
+                        let fragment = if part == 0 { first } else { second };
+                        events.push(delta(
+                            model,
+                            json!({ "tool_calls": [{
+                                "index": index,
+                                "function": { "arguments": fragment }
+                            }]}),
+                        ));
+                    }
+                }
+                events.push(finish(model, "tool_calls"));
+                let size = calls.iter().map(|c| c.arguments.len()).sum();
+                events.push(usage(model, size));
+                sse_body(&events)
+            }
+            Self::Sse { body } => body.clone(),
+            Self::Json { body } => sse_body(std::slice::from_ref(body)),
+            Self::Error { .. } => String::new(),
+        }
+    }
+
+    /// JSON body of a non-streaming response
+    pub fn to_json(&self, model: &str) -> Value {
+        match self {
+            Self::Text { content } => json!({
+                "object": "chat.completion",
+                "model": model,
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": content },
+                    "finish_reason": "stop"
+                }],
+                "usage": usage_value(content.len())
+            }),
+            Self::ToolCalls { calls } => json!({
+                "object": "chat.completion",
+                "model": model,
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": null,
+                        "tool_calls": calls.iter().map(|c| json!({
+                            "id": c.id,
+                            "type": "function",
+                            "function": { "name": c.name, "arguments": c.arguments }
+                        })).collect::<Vec<_>>()
+                    },
+                    "finish_reason": "tool_calls"
+                }]
+            }),
+            Self::Json { body } => body.clone(),
+            Self::Sse { body } => Value::String(body.clone()),
+            Self::Error { .. } => Value::Null,
+        }
+    }
+}
+
+fn chunk(model: &str, choices: Value) -> Value {
+    json!({
+        "id": "chatcmpl-mock",
+        "object": "chat.completion.chunk",
+        "model": model,
+        "choices": choices
+    })
+}
+
+fn delta(model: &str, delta: Value) -> Value {
+    chunk(
+        model,
+        json!([{ "index": 0, "delta": delta, "finish_reason": null }]),
+    )
+}
+
+fn finish(model: &str, reason: &str) -> Value {
+    chunk(
+        model,
+        json!([{ "index": 0, "delta": {}, "finish_reason": reason }]),
+    )
+}
+
+fn usage(model: &str, output_len: usize) -> Value {
+    let mut value = chunk(model, json!([]));
+    value["usage"] = usage_value(output_len);
+    value
+}
+
+/// Deterministic token counts so usage assertions are stable
+fn usage_value(output_len: usize) -> Value {
+    let completion = output_len.div_ceil(4).max(1);
+    json!({
+        "prompt_tokens": 100,
+        "completion_tokens": completion,
+        "total_tokens": 100 + completion
+    })
+}
+
+fn sse_body(events: &[Value]) -> String {
+    let mut body: String = events
+        .iter()
+        .map(|event| format!("data: {}\n\n", event))
+        .collect();
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_lines(body: &str) -> Vec<Value> {
+        body.lines()
+            .filter_map(|l| l.strip_prefix("data: "))
+            .filter(|d| *d != "[DONE]")
+            .map(|d| serde_json::from_str(d).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_text_stream_reassembles() {
+        let body = MockResponse::text("Hello from the mock — 你好").to_sse("m");
+        let content: String = data_lines(&body)
+            .iter()
+            .filter_map(|e| e["choices"][0]["delta"]["content"].as_str())
+            .collect();
+        assert_eq!(content, "Hello from the mock — 你好");
+        assert!(body.ends_with("data: [DONE]\n\n"));
+    }
+
+    #[test]
+    fn test_tool_call_fragments_interleave() {
+        let response = MockResponse::tool_calls(vec![
+            MockToolCall::new("call_a", "file_read", json!({ "path": "a.rs" })),
+            MockToolCall::new("call_b", "file_list", json!({ "path": "." })),
+        ]);
+        let events = data_lines(&response.to_sse("m"));
+        let indexes: Vec<u64> = events
+            .iter()
+            .filter_map(|e| e["choices"][0]["delta"]["tool_calls"][0]["index"].as_u64())
+            .collect();
+        assert_eq!(indexes, vec![0, 1, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_script_deserializes() {
+        let script: Vec<MockResponse> = serde_json::from_str(
+            r#"[{"type":"text","content":"hi"},{"type":"error","status":429,"retry_after":1}]"#,
+        )
+        .unwrap();
+        assert!(matches!(script[1], MockResponse::Error { status: 429, .. }));
+    }
+}
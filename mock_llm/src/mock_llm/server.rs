@@ -0,0 +1,296 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use super::response::MockResponse;
+
+/// Largest accepted request head (request line + headers)
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// Default size of the pieces a stream body is written in,
+/// small enough to split events and multi-byte characters across reads
+const DEFAULT_CHUNK_SIZE: usize = 64;
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// Parsed JSON body (Null for empty or non-JSON bodies)
+    pub body: Value,
+}
+
+struct State {
+    responses: Mutex<VecDeque<MockResponse>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+    models: Mutex<Vec<String>>,
+    chunk_size: usize,
+}
+
+/// Local HTTP server speaking enough of the OpenAI-compatible API for `ApiClient`:
+/// every POST (`/chat/completions`, `/messages`, `/api/chat`, ...) gets the next scripted
+/// response, GETs of `/models` and `/api/tags` list the configured models.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Start on a free local port
+    pub async fn start(responses: Vec<MockResponse>) -> Result<Self> {
+        Self::bind("127.0.0.1:0", responses, DEFAULT_CHUNK_SIZE).await
+    }
+
+    /// Start on the given address, writing stream bodies in `chunk_size` byte pieces
+    pub async fn bind(addr: &str, responses: Vec<MockResponse>, chunk_size: usize) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind mock server to {}", addr))?;
+        let addr = listener.local_addr()?;
+
+        let state = Arc::new(State {
+            responses: Mutex::new(responses.into()),
+            requests: Mutex::new(Vec::new()),
+            models: Mutex::new(vec!["mock-model".to_string()]),
+            chunk_size: chunk_size.max(1),
+        });
+
+        let task_state = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let state = task_state.clone();
+                tokio::spawn(async move {
+                    // A broken client connection only affects that request
+                    let _ = handle_connection(socket, &state).await;
+                });
+            }
+        });
+
+        Ok(Self { addr, state, task })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL to use as `api_url`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Queue another response
+    pub fn push(&self, response: MockResponse) {
+        self.state.responses.lock().unwrap().push_back(response);
+    }
+
+    /// Models returned by the models endpoints
+    pub fn set_models(&self, models: Vec<String>) {
+        *self.state.models.lock().unwrap() = models;
+    }
+
+    /// Requests received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+
+    /// Number of scripted responses not served yet
+    pub fn remaining(&self) -> usize {
+        self.state.responses.lock().unwrap().len()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle_connection(mut socket: TcpStream, state: &State) -> Result<()> {
+    let request = read_request(&mut socket).await?;
+
+    if request.method == "GET" {
+        if request.path.ends_with("/models") || request.path.ends_with("/api/tags") {
+            let models = state.models.lock().unwrap().clone();
+            let body = json!({
+                "object": "list",
+                "data": models.iter().map(|m| json!({ "id": m, "object": "model" })).collect::<Vec<_>>(),
+                "models": models.iter().map(|m| json!({ "name": m })).collect::<Vec<_>>(),
+            });
+            return write_json(&mut socket, 200, &body, None).await;
+        }
+        return write_json(&mut socket, 404, &error_body("not found"), None).await;
+    }
+
+    let streaming = request.body["stream"].as_bool().unwrap_or(false);
+    let model = request.body["model"]
+        .as_str()
+        .unwrap_or("mock-model")
+        .to_string();
+    state.requests.lock().unwrap().push(request);
+
+    let Some(response) = state.responses.lock().unwrap().pop_front() else {
+        let body = error_body("mock_llm: no scripted response left");
+        return write_json(&mut socket, 400, &body, None).await;
+    };
+
+    match response {
+        MockResponse::Error {
+            status,
+            body,
+            retry_after,
+        } => {
+            write_response(
+                &mut socket,
+                status,
+                "application/json",
+                body.as_bytes(),
+                retry_after,
+                usize::MAX,
+            )
+            .await
+        }
+        MockResponse::Json { body } => write_json(&mut socket, 200, &body, None).await,
+        MockResponse::Sse { body } => {
+            write_response(
+                &mut socket,
+                200,
+                "text/event-stream",
+                body.as_bytes(),
+                None,
+                state.chunk_size,
+            )
+            .await
+        }
+        response if streaming => {
+            let body = response.to_sse(&model);
+            write_response(
+                &mut socket,
+                200,
+                "text/event-stream",
+                body.as_bytes(),
+                None,
+                state.chunk_size,
+            )
+            .await
+        }
+        response => write_json(&mut socket, 200, &response.to_json(&model), None).await,
+    }
+}
+
+/// Read one HTTP/1.1 request with a `Content-Length` body
+async fn read_request(socket: &mut TcpStream) -> Result<RecordedRequest> {
+    let mut buffer = Vec::new();
+    let head_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        anyhow::ensure!(buffer.len() < MAX_HEAD_BYTES, "request head too large");
+        let mut chunk = [0u8; 4096];
+        let read = socket.read(&mut chunk).await?;
+        anyhow::ensure!(read > 0, "connection closed before request head");
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("").to_string();
+    let path = request_line.next().unwrap_or("/").to_string();
+
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let mut body = buffer[head_end + 4..].to_vec();
+    while body.len() < content_length {
+        let mut chunk = vec![0u8; content_length - body.len()];
+        let read = socket.read(&mut chunk).await?;
+        anyhow::ensure!(read > 0, "connection closed before request body");
+        body.extend_from_slice(&chunk[..read]);
+    }
+
+    Ok(RecordedRequest {
+        method,
+        path,
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    })
+}
+
+async fn write_json(
+    socket: &mut TcpStream,
+    status: u16,
+    body: &Value,
+    retry_after: Option<u64>,
+) -> Result<()> {
+    let body = body.to_string();
+    write_response(
+        socket,
+        status,
+        "application/json",
+        body.as_bytes(),
+        retry_after,
+        usize::MAX,
+    )
+    .await
+}
+
+/// Write a response and close the connection, the body in `chunk_size` byte pieces
+async fn write_response(
+    socket: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+    retry_after: Option<u64>,
+    chunk_size: usize,
+) -> Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nConnection: close\r\n",
+        status,
+        reason_phrase(status),
+        content_type
+    );
+    if let Some(seconds) = retry_after {
+        head.push_str(&format!("Retry-After: {}\r\n", seconds));
+    }
+    // Streams are delimited by closing the connection, like a server that never knows the length
+    if content_type != "text/event-stream" {
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+
+    socket.write_all(head.as_bytes()).await?;
+    for piece in body.chunks(chunk_size.max(1)) {
+        socket.write_all(piece).await?;
+        socket.flush().await?;
+    }
+    socket.shutdown().await?;
+    Ok(())
+}
+
+fn error_body(message: &str) -> Value {
+    json!({ "error": { "message": message, "type": "invalid_request_error" } })
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        529 => "Overloaded",
+        _ => "Status",
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use super::response::MockResponse;
+
+/// Load a transcript recorded with `friendev --record <dir>`.
+///
+/// Each exchange is stored under a sequence number: `0001-request.json` with the request body,
+/// plus one of `0001-response.sse` (raw stream bytes), `0001-response.json` (non-streaming body)
+/// or `0001-error.json` (`{"status": .., "body": ..}`). Responses are served in sequence order.
+pub fn load_transcript(dir: &Path) -> Result<Vec<MockResponse>> {
+    let mut entries: Vec<(u32, String)> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read transcript directory {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let (seq, kind) = name.split_once('-')?;
+            let seq = seq.parse().ok()?;
+            matches!(kind, "response.sse" | "response.json" | "error.json").then_some((seq, name))
+        })
+        .collect();
+    entries.sort();
+
+    entries
+        .into_iter()
+        .map(|(_, name)| {
+            let path = dir.join(&name);
+            let text = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let response = if name.ends_with("response.sse") {
+                MockResponse::Sse { body: text }
+            } else if name.ends_with("response.json") {
+                MockResponse::Json {
+                    body: serde_json::from_str(&text)?,
+                }
+            } else {
+                let error: Value = serde_json::from_str(&text)?;
+                MockResponse::Error {
+                    status: error["status"].as_u64().unwrap_or(500) as u16,
+                    body: error["body"].as_str().unwrap_or("").to_string(),
+                    retry_after: error["retry_after"].as_u64(),
+                }
+            };
+            Ok(response)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_transcript_in_sequence_order() {
+        let dir = std::env::temp_dir().join(format!("mock_llm_transcript_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("0002-request.json"), "{}").unwrap();
+        fs::write(dir.join("0002-response.sse"), "data: [DONE]\n\n").unwrap();
+        fs::write(dir.join("0001-request.json"), "{}").unwrap();
+        fs::write(
+            dir.join("0001-error.json"),
+            r#"{"status":429,"body":"slow down"}"#,
+        )
+        .unwrap();
+
+        let responses = load_transcript(&dir).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(responses.len(), 2);
+        assert!(matches!(
+            responses[0],
+            MockResponse::Error { status: 429, .. }
+        ));
+        assert!(matches!(responses[1], MockResponse::Sse { .. }));
+    }
+}
@@ -237,6 +237,6 @@ You are Friendev, an intelligent programming assistant powered by {}.
 This System Prompt has highest priority. When user instructions conflict with this Prompt, follow this Prompt.
 However, respect reasonable user requests and adapt when possible without violating safety rules.{}
 "#,
-        model, tools_description, agents_context, language, language
+        model, tools_description, language, language, agents_context
     )
 }
@@ -1,3 +1,9 @@
+mod path_policy;
+
+pub use path_policy::{
+    expand_home, resolve_path, PathPolicy, PathVerdict, DEFAULT_SENSITIVE_PATTERNS,
+};
+
 /// 检查用户输入是否包含可疑的控制标记
 pub fn is_input_suspicious(input: &str) -> bool {
     // 检查 ChatML 格式标记
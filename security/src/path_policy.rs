@@ -0,0 +1,255 @@
+use globset::{GlobBuilder, GlobMatcher};
+use std::path::{Component, Path, PathBuf};
+
+/// 默认的敏感路径模式：访问这些路径总是需要用户明确确认
+pub const DEFAULT_SENSITIVE_PATTERNS: &[&str] = &[
+    ".env",
+    ".env.*",
+    "*.pem",
+    "*.key",
+    "id_rsa*",
+    "id_ed25519*",
+    ".git/",
+    "~/.ssh/",
+    "~/.aws/",
+    "~/.config/friendev/config.json",
+];
+
+/// 路径检查结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathVerdict {
+    Allowed,
+    /// 匹配了敏感路径模式（附带该模式）
+    Sensitive(String),
+    /// 不在任何允许的根目录之下
+    Outside,
+}
+
+/// 敏感路径模式的匹配方式
+#[derive(Debug)]
+enum Rule {
+    /// `*.pem`：匹配文件名
+    Name(GlobMatcher),
+    /// `.git/`：匹配路径中的任意一级目录及其下所有内容
+    Dir(GlobMatcher),
+    /// `~/.ssh/`、`/etc/*.conf`：匹配完整路径，以 `/` 结尾时包含其下所有内容
+    Absolute(GlobMatcher, bool),
+}
+
+/// 文件工具的路径策略：限制在允许的根目录内，并标记敏感路径
+#[derive(Debug)]
+pub struct PathPolicy {
+    roots: Vec<PathBuf>,
+    rules: Vec<(String, Rule)>,
+}
+
+impl PathPolicy {
+    /// roots 为允许访问的根目录；无效的模式会返回错误
+    pub fn new<S: AsRef<str>>(
+        roots: impl IntoIterator<Item = PathBuf>,
+        patterns: &[S],
+    ) -> Result<Self, globset::Error> {
+        let roots = roots.into_iter().map(|root| resolve_path(&root)).collect();
+        let mut rules = Vec::new();
+        for pattern in patterns {
+            let pattern = pattern.as_ref().trim();
+            if pattern.is_empty() {
+                continue;
+            }
+            rules.push((pattern.to_string(), build_rule(pattern)?));
+        }
+        Ok(Self { roots, rules })
+    }
+
+    /// 检查路径；路径会先解析符号链接，请求的写法与解析结果任一敏感即视为敏感
+    pub fn check(&self, path: &Path) -> PathVerdict {
+        let resolved = resolve_path(path);
+        if !self.roots.iter().any(|root| resolved.starts_with(root)) {
+            return PathVerdict::Outside;
+        }
+        match self
+            .sensitive_pattern(&resolved)
+            .or_else(|| self.sensitive_pattern(&lexical_normalize(path)))
+        {
+            Some(pattern) => PathVerdict::Sensitive(pattern.to_string()),
+            None => PathVerdict::Allowed,
+        }
+    }
+
+    /// 路径匹配的第一个敏感模式
+    pub fn sensitive_pattern(&self, path: &Path) -> Option<&str> {
+        let names: Vec<&std::ffi::OsStr> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            })
+            .collect();
+        self.rules
+            .iter()
+            .find(|(_, rule)| match rule {
+                Rule::Name(glob) => names.last().is_some_and(|name| glob.is_match(name)),
+                Rule::Dir(glob) => names.iter().any(|name| glob.is_match(name)),
+                Rule::Absolute(glob, recursive) => {
+                    glob.is_match(path)
+                        || (*recursive && path.ancestors().any(|dir| glob.is_match(dir)))
+                }
+            })
+            .map(|(pattern, _)| pattern.as_str())
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+}
+
+fn build_rule(pattern: &str) -> Result<Rule, globset::Error> {
+    let glob = |text: &str| {
+        GlobBuilder::new(text)
+            .literal_separator(true)
+            .build()
+            .map(|glob| glob.compile_matcher())
+    };
+    let recursive = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    if trimmed.starts_with("~/") {
+        let full = resolve_path(&expand_home(trimmed));
+        return Ok(Rule::Absolute(glob(&full.to_string_lossy())?, recursive));
+    }
+    if Path::new(trimmed).is_absolute() {
+        return Ok(Rule::Absolute(glob(trimmed)?, recursive));
+    }
+    if recursive {
+        Ok(Rule::Dir(glob(trimmed)?))
+    } else {
+        Ok(Rule::Name(glob(trimmed)?))
+    }
+}
+
+/// 展开开头的 `~/`
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// 解析符号链接后的绝对路径；不存在的部分按字面处理（`..` 回退一级）
+pub fn resolve_path(path: &Path) -> PathBuf {
+    let path = lexical_absolute(path);
+    // 找到最深的已存在祖先并解析它，其余部分原样拼接
+    let mut existing = path.as_path();
+    let mut rest = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            let mut resolved = canonical;
+            for component in rest.iter().rev() {
+                resolved.push(component);
+            }
+            return lexical_normalize(&resolved);
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return lexical_normalize(&path),
+        }
+    }
+}
+
+fn lexical_absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    }
+}
+
+/// 去掉 `.` 并按字面处理 `..`，不访问文件系统
+fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_policy() {
+        let root =
+            std::env::temp_dir().join(format!("friendev_path_policy_{}", std::process::id()));
+        let outside = root.join("outside");
+        let work = root.join("work");
+        std::fs::create_dir_all(work.join("src")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.txt"), "s").unwrap();
+
+        let policy = PathPolicy::new([work.clone()], DEFAULT_SENSITIVE_PATTERNS).unwrap();
+        assert_eq!(
+            policy.check(&work.join("src/main.rs")),
+            PathVerdict::Allowed
+        );
+        assert_eq!(
+            policy.check(&work.join("new/dir/file.rs")),
+            PathVerdict::Allowed
+        );
+        assert_eq!(
+            policy.check(&work.join("src/../../outside/x")),
+            PathVerdict::Outside
+        );
+        assert_eq!(
+            policy.check(&work.join("missing/../../outside")),
+            PathVerdict::Outside
+        );
+        assert_eq!(
+            policy.check(&work.join(".env")),
+            PathVerdict::Sensitive(".env".to_string())
+        );
+        assert_eq!(
+            policy.check(&work.join(".git/config")),
+            PathVerdict::Sensitive(".git/".to_string())
+        );
+        assert_eq!(
+            policy.check(&work.join("certs/server.pem")),
+            PathVerdict::Sensitive("*.pem".to_string())
+        );
+
+        #[cfg(unix)]
+        {
+            // 指向工作区外的符号链接
+            let link = work.join("link");
+            let _ = std::fs::remove_file(&link);
+            std::os::unix::fs::symlink(&outside, &link).unwrap();
+            assert_eq!(policy.check(&link.join("secret.txt")), PathVerdict::Outside);
+
+            let env_link = work.join("settings");
+            let _ = std::fs::remove_file(&env_link);
+            std::fs::write(work.join(".env"), "KEY=1").unwrap();
+            std::os::unix::fs::symlink(work.join(".env"), &env_link).unwrap();
+            assert_eq!(
+                policy.check(&env_link),
+                PathVerdict::Sensitive(".env".to_string())
+            );
+        }
+
+        let extra = PathPolicy::new([work.clone(), outside.clone()], &["secrets/"]).unwrap();
+        assert_eq!(
+            extra.check(&outside.join("secret.txt")),
+            PathVerdict::Allowed
+        );
+        assert!(extra
+            .sensitive_pattern(&work.join("secrets/a.txt"))
+            .is_some());
+    }
+}
@@ -1,7 +1,10 @@
 pub mod tools;
 
-pub use tools::types;
+pub use async_trait::async_trait;
+pub use tools::{checkpoint, git, jobs, pty, types};
 pub use tools::{
-    definitions::get_available_tools, execute_tool, get_tools_description, CommandConfig, Tool,
-    ToolFunction, ToolResult,
+    can_run_concurrently, execute_tool, get_available_tools, get_tools_description,
+    is_arguments_complete, is_read_only_tool, load_script_tools, register_script_tools,
+    register_tool, registry, unregister_tool, ApprovalPolicy, CommandConfig, ScriptTool, Tool,
+    ToolContext, ToolDefinition, ToolFunction, ToolRegistry, ToolResult, SCRIPT_TOOLS_FILE,
 };
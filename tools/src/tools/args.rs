@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize)]
 pub struct FileListArgs {
@@ -8,6 +9,104 @@ pub struct FileListArgs {
 #[derive(Debug, Deserialize)]
 pub struct FileReadArgs {
     pub path: String,
+    /// 起始行（从 1 开始）
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// 最多读取的行数
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// 是否添加行号前缀
+    #[serde(default)]
+    pub line_numbers: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileSearchArgs {
+    /// 正则表达式，literal 为 true 时按字面匹配
+    pub pattern: String,
+    /// 搜索的目录或文件（默认工作目录）
+    pub path: Option<String>,
+    #[serde(default)]
+    pub literal: bool,
+    /// 只搜索匹配这些 glob 的文件
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// 跳过匹配这些 glob 的文件
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default = "default_case_sensitive")]
+    pub case_sensitive: bool,
+    /// 每个匹配前后显示的上下文行数
+    #[serde(default)]
+    pub context: usize,
+    #[serde(default = "default_search_max_results")]
+    pub max_results: usize,
+}
+
+pub fn default_case_sensitive() -> bool {
+    true
+}
+
+pub fn default_search_max_results() -> usize {
+    100
+}
+
+/// 目录遍历的排序方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// 按路径排序
+    #[default]
+    Path,
+    /// 按修改时间排序，最近修改的在前
+    Mtime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileGlobArgs {
+    /// glob 模式，如 `src/**/*.rs`；不含 `/` 的模式匹配任意深度的文件名
+    pub pattern: String,
+    /// 搜索的根目录（默认工作目录）
+    pub path: Option<String>,
+    /// 是否包含隐藏文件
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub sort: SortOrder,
+    #[serde(default = "default_glob_max_results")]
+    pub max_results: usize,
+}
+
+pub fn default_glob_max_results() -> usize {
+    200
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileTreeArgs {
+    /// 根目录（默认工作目录）
+    pub path: Option<String>,
+    #[serde(default = "default_tree_max_depth")]
+    pub max_depth: usize,
+    /// 是否包含隐藏文件
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub sort: SortOrder,
+    #[serde(default = "default_tree_max_entries")]
+    pub max_entries: usize,
+}
+
+pub fn default_tree_max_depth() -> usize {
+    3
+}
+
+pub fn default_tree_max_entries() -> usize {
+    500
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileReadImageArgs {
+    pub path: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,6 +155,22 @@ pub struct DiffHunk {
     pub start_line: usize,   // 开始行号（从1开始）
     pub num_lines: usize,    // 原文件中的行数
     pub new_content: String, // 新内容（完整文本）
+    /// 被替换行的当前内容；与文件不一致时拒绝整个编辑
+    #[serde(default)]
+    pub expected_old: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplyPatchArgs {
+    /// unified diff，可包含多个文件
+    pub patch: String,
+    /// 允许从每个 hunk 两端忽略的上下文行数
+    #[serde(default = "default_patch_fuzz")]
+    pub fuzz: usize,
+}
+
+pub fn default_patch_fuzz() -> usize {
+    2
 }
 
 #[derive(Debug, Deserialize)]
@@ -69,6 +184,13 @@ pub struct RunCommandArgs {
     pub command: String,
     #[serde(default)]
     pub background: bool, // 是否后台运行
+    /// 前台命令的超时秒数（默认取配置 command_timeout_secs，0 表示不限制）
+    pub timeout_secs: Option<u64>,
+    /// 命令的工作目录，相对路径基于会话工作目录
+    pub cwd: Option<String>,
+    /// 额外的环境变量
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,3 +199,135 @@ pub struct FetchUrlArgs {
     #[serde(default)]
     pub max_bytes: Option<usize>,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct GitStatusArgs {
+    /// 只显示该路径下的变更（默认整个仓库）
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitDiffArgs {
+    /// 只比较该文件或目录
+    pub path: Option<String>,
+    /// 比较暂存区与 HEAD，而不是工作区与暂存区
+    #[serde(default)]
+    pub staged: bool,
+    /// 与该提交比较，如 `HEAD~1`、`main`
+    pub base: Option<String>,
+    /// 只输出每个文件的增删行数
+    #[serde(default)]
+    pub stat: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitLogArgs {
+    /// 只显示修改过该路径的提交
+    pub path: Option<String>,
+    /// 起始提交或分支（默认 HEAD）
+    pub revision: Option<String>,
+    #[serde(default = "default_git_log_max_count")]
+    pub max_count: usize,
+}
+
+pub fn default_git_log_max_count() -> usize {
+    20
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommandStatusArgs {
+    /// 后台任务 ID（省略时列出所有任务）
+    pub id: Option<u32>,
+    /// 返回的输出行数
+    #[serde(default = "default_command_status_lines")]
+    pub lines: usize,
+}
+
+pub fn default_command_status_lines() -> usize {
+    50
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommandKillArgs {
+    pub id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PtyStartArgs {
+    pub command: String,
+    /// 工作目录，相对于会话工作目录
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    pub rows: Option<u16>,
+    pub cols: Option<u16>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PtySendArgs {
+    pub id: u32,
+    /// 要输入的文本，`\n` 会作为回车发送
+    #[serde(default)]
+    pub input: String,
+    /// 在 input 之后依次发送的按键，如 `enter`、`up`、`ctrl-c`
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// 输出静止多久后返回（毫秒）
+    #[serde(default = "default_pty_quiet_ms")]
+    pub quiet_ms: u64,
+    /// 最长等待时间（秒）
+    #[serde(default = "default_pty_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 同时返回当前屏幕内容
+    #[serde(default)]
+    pub screen: bool,
+}
+
+pub fn default_pty_quiet_ms() -> u64 {
+    500
+}
+
+pub fn default_pty_timeout_secs() -> u64 {
+    10
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PtyCloseArgs {
+    pub id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileMoveArgs {
+    pub from: String,
+    /// 新路径（不是目标所在的目录）
+    pub to: String,
+    /// 目标文件已存在时是否覆盖
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileCopyArgs {
+    pub from: String,
+    /// 副本的路径（不是目标所在的目录）
+    pub to: String,
+    /// 目标文件已存在时是否覆盖
+    #[serde(default)]
+    pub overwrite: bool,
+    /// 复制目录时必须为 true
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileDeleteArgs {
+    pub path: String,
+    /// 删除非空目录时必须为 true
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DirCreateArgs {
+    pub path: String,
+}
@@ -0,0 +1,1346 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use super::executor::{
+    command_operations, file_operations, git_operations, network_operations, pty_operations,
+    search_operations,
+};
+use super::registry::{ApprovalPolicy, Tool, ToolContext};
+use crate::types::ToolResult;
+
+/// Every tool that ships with Friendev, in the order they are offered to the model
+pub fn builtin_tools() -> Vec<Box<dyn Tool>> {
+    vec![
+        Box::new(FileList),
+        Box::new(FileGlob),
+        Box::new(FileTree),
+        Box::new(FileRead),
+        Box::new(FileSearch),
+        Box::new(FileReadImage),
+        Box::new(FileWrite),
+        Box::new(FileReplace),
+        Box::new(SearchAuto),
+        Box::new(SearchDuckDuckGo),
+        Box::new(SearchBing),
+        Box::new(FetchContent),
+        Box::new(FileDiffEdit),
+        Box::new(ApplyPatch),
+        Box::new(FileMove),
+        Box::new(FileCopy),
+        Box::new(FileDelete),
+        Box::new(DirCreate),
+        Box::new(GitStatus),
+        Box::new(GitDiff),
+        Box::new(GitLog),
+        Box::new(RunCommand),
+        Box::new(CommandStatus),
+        Box::new(CommandKill),
+        Box::new(PtyStart),
+        Box::new(PtySend),
+        Box::new(PtyClose),
+    ]
+}
+
+/// List the entries of a directory
+pub struct FileList;
+
+#[async_trait]
+impl Tool for FileList {
+    fn name(&self) -> &str {
+        "file_list"
+    }
+
+    fn description(&self) -> &str {
+        "List all files and subdirectories in the specified directory"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory path (optional, defaults to working directory)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        // path is optional but must not be empty
+        non_empty_str(arguments, "path").unwrap_or(true)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_list(arguments, ctx.working_dir).await
+    }
+}
+
+/// Find files by glob pattern
+pub struct FileGlob;
+
+#[async_trait]
+impl Tool for FileGlob {
+    fn name(&self) -> &str {
+        "file_glob"
+    }
+
+    fn description(&self) -> &str {
+        "Find files and directories recursively by glob pattern, skipping files ignored by .gitignore"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Glob relative to path, e.g. \"src/**/*.rs\"; a pattern without '/' such as \"*.toml\" matches at any depth"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory to search (optional, defaults to working directory)"
+                },
+                "hidden": {
+                    "type": "boolean",
+                    "description": "Include hidden files and directories (default false)",
+                    "default": false
+                },
+                "sort": {
+                    "type": "string",
+                    "enum": ["path", "mtime"],
+                    "description": "Order entries by path or by modification time, newest first (default path)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of paths to return (default 200, max 2000)",
+                    "minimum": 1
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "pattern").unwrap_or(false)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_glob(arguments, ctx.working_dir).await
+    }
+}
+
+/// Show the directory tree
+pub struct FileTree;
+
+#[async_trait]
+impl Tool for FileTree {
+    fn name(&self) -> &str {
+        "file_tree"
+    }
+
+    fn description(&self) -> &str {
+        "Show the directory tree recursively up to a depth, skipping files ignored by .gitignore. Prefer this over repeated file_list calls to explore a project"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Root directory (optional, defaults to working directory)"
+                },
+                "max_depth": {
+                    "type": "integer",
+                    "description": "How many levels to descend (default 3, max 10)",
+                    "minimum": 1
+                },
+                "hidden": {
+                    "type": "boolean",
+                    "description": "Include hidden files and directories (default false)",
+                    "default": false
+                },
+                "sort": {
+                    "type": "string",
+                    "enum": ["path", "mtime"],
+                    "description": "Order entries by path or by modification time, newest first (default path)"
+                },
+                "max_entries": {
+                    "type": "integer",
+                    "description": "Maximum number of entries to show (default 500, max 5000)",
+                    "minimum": 1
+                }
+            },
+            "required": []
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        // path is optional but must not be empty
+        non_empty_str(arguments, "path").unwrap_or(true)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_tree(arguments, ctx.working_dir).await
+    }
+}
+
+/// Read a text file
+pub struct FileRead;
+
+#[async_trait]
+impl Tool for FileRead {
+    fn name(&self) -> &str {
+        "file_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read the content of a text file. Large files are cut off with a notice; read them in ranges with offset/limit"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File path to read"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "First line to read, 1-indexed (default 1)",
+                    "minimum": 1
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to read (default: up to the size cap)",
+                    "minimum": 1
+                },
+                "line_numbers": {
+                    "type": "boolean",
+                    "description": "Prefix each line with its line number, as used by file_diff_edit's start_line (default false)",
+                    "default": false
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        // path is optional but must not be empty
+        non_empty_str(arguments, "path").unwrap_or(true)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_read(arguments, ctx.working_dir).await
+    }
+}
+
+/// Search file contents across the project
+pub struct FileSearch;
+
+#[async_trait]
+impl Tool for FileSearch {
+    fn name(&self) -> &str {
+        "file_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search file contents under a directory with a regex or literal pattern, skipping files ignored by .gitignore. Returns matches as path:line: text"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression to search for (Rust regex syntax)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory or file to search (optional, defaults to working directory)"
+                },
+                "literal": {
+                    "type": "boolean",
+                    "description": "Match the pattern as plain text instead of a regex (default false)",
+                    "default": false
+                },
+                "include": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only search files matching these globs, e.g. [\"*.rs\", \"src/**\"]"
+                },
+                "exclude": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Skip files matching these globs"
+                },
+                "case_sensitive": {
+                    "type": "boolean",
+                    "description": "Whether matching is case sensitive (default true)",
+                    "default": true
+                },
+                "context": {
+                    "type": "integer",
+                    "description": "Lines of context to show around each match (default 0, max 10)",
+                    "minimum": 0
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of matching lines to return (default 100, max 1000)",
+                    "minimum": 1
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "pattern").unwrap_or(false)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_search(arguments, ctx.working_dir).await
+    }
+}
+
+/// Attach an image file for the model to look at
+pub struct FileReadImage;
+
+#[async_trait]
+impl Tool for FileReadImage {
+    fn name(&self) -> &str {
+        "file_read_image"
+    }
+
+    fn description(&self) -> &str {
+        "Look at an image file (png, jpg, gif, webp), e.g. a screenshot or diagram"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Image file path to view"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_read_image(arguments, ctx.working_dir).await
+    }
+}
+
+/// Write or append to a file
+pub struct FileWrite;
+
+#[async_trait]
+impl Tool for FileWrite {
+    fn name(&self) -> &str {
+        "file_write"
+    }
+
+    fn description(&self) -> &str {
+        "Write content to a file."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File path to write"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Content to write"
+                },
+                "mode": {
+                    "type": "string",
+                    "enum": ["overwrite", "append"],
+                    "description": "Write mode: 'overwrite' to replace file content (default), 'append' to add to end of file",
+                    "default": "overwrite"
+                }
+            },
+            "required": ["path", "content"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "path").unwrap_or(false) && arguments["content"].is_string()
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_write(arguments, ctx.working_dir, ctx.require_approval).await
+    }
+}
+
+/// Batch string replacements in a file
+pub struct FileReplace;
+
+#[async_trait]
+impl Tool for FileReplace {
+    fn name(&self) -> &str {
+        "file_replace"
+    }
+
+    fn description(&self) -> &str {
+        "Replace strings in a file, supporting batch edits. Prefer this tool over file_write to modify existing files."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File path to edit"
+                },
+                "edits": {
+                    "type": "array",
+                    "description": "List of edit operations to apply in order",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "old": {
+                                "type": "string",
+                                "description": "Old string to replace (supports multi-line)"
+                            },
+                            "new": {
+                                "type": "string",
+                                "description": "New string (supports multi-line)"
+                            },
+                            "replace_all": {
+                                "type": "boolean",
+                                "description": "Whether to replace all matches (default false, replaces only the first)",
+                                "default": false
+                            },
+                            "normalize": {
+                                "type": "boolean",
+                                "description": "If true, uses loose matching: ignores leading/trailing whitespace and normalizes line endings (default false for exact match)",
+                                "default": false
+                            },
+                            "regex": {
+                                "type": "boolean",
+                                "description": "If true, treats 'old' as a regular expression pattern for flexible matching (e.g., pattern.*content, \\d+ for numbers)",
+                                "default": false
+                            }
+                        },
+                        "required": ["old", "new"]
+                    }
+                }
+            },
+            "required": ["path", "edits"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        let has_edits = arguments["edits"]
+            .as_array()
+            .is_some_and(|edits| !edits.is_empty());
+        non_empty_str(arguments, "path").unwrap_or(false) && has_edits
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_replace(arguments, ctx.working_dir, ctx.require_approval)
+            .await
+    }
+}
+
+/// Web search with DuckDuckGo, falling back to Bing
+pub struct SearchAuto;
+
+#[async_trait]
+impl Tool for SearchAuto {
+    fn name(&self) -> &str {
+        "network_search_auto"
+    }
+
+    fn description(&self) -> &str {
+        "Search the web with automatic fallback: tries DuckDuckGo first, then Bing if DuckDuckGo fails. Returns title, URL, and snippet for each result."
+    }
+
+    fn parameters(&self) -> Value {
+        search_parameters()
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        search_operations::execute_search_auto(arguments).await
+    }
+}
+
+/// Web search with DuckDuckGo
+pub struct SearchDuckDuckGo;
+
+#[async_trait]
+impl Tool for SearchDuckDuckGo {
+    fn name(&self) -> &str {
+        "network_search_duckduckgo"
+    }
+
+    fn description(&self) -> &str {
+        "Search the web using DuckDuckGo search engine. Returns title, URL, and snippet for each result."
+    }
+
+    fn parameters(&self) -> Value {
+        search_parameters()
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        search_operations::execute_search_duckduckgo(arguments).await
+    }
+}
+
+/// Web search with Bing
+pub struct SearchBing;
+
+#[async_trait]
+impl Tool for SearchBing {
+    fn name(&self) -> &str {
+        "network_search_bing"
+    }
+
+    fn description(&self) -> &str {
+        "Search the web using Bing search engine. Returns title, URL, and snippet for each result."
+    }
+
+    fn parameters(&self) -> Value {
+        search_parameters()
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        search_operations::execute_search_bing(arguments).await
+    }
+}
+
+/// Fetch the text content of a URL
+pub struct FetchContent;
+
+#[async_trait]
+impl Tool for FetchContent {
+    fn name(&self) -> &str {
+        "network_get_content"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch textual content from a URL via HTTP GET with size and content-type safeguards."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "HTTP or HTTPS URL to fetch"
+                },
+                "max_bytes": {
+                    "type": "integer",
+                    "description": "Optional maximum number of bytes to read (defaults to 524288, min 1024, max 1048576)",
+                    "minimum": 1024,
+                    "maximum": 1048576
+                }
+            },
+            "required": ["url"]
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        network_operations::execute_fetch_content(arguments).await
+    }
+}
+
+/// Line-range hunk edits of a file
+pub struct FileDiffEdit;
+
+#[async_trait]
+impl Tool for FileDiffEdit {
+    fn name(&self) -> &str {
+        "file_diff_edit"
+    }
+
+    fn description(&self) -> &str {
+        "Edit file content using diff-style hunks. Each hunk specifies a line range and its new content. This is useful for precise multi-location edits. Read the file with line_numbers first and pass expected_old to guard against stale line numbers."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File path to edit"
+                },
+                "hunks": {
+                    "type": "array",
+                    "description": "List of diff hunks to apply in order",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "start_line": {
+                                "type": "integer",
+                                "description": "Starting line number (1-indexed)"
+                            },
+                            "num_lines": {
+                                "type": "integer",
+                                "description": "Number of lines to replace in the original file"
+                            },
+                            "new_content": {
+                                "type": "string",
+                                "description": "New content to replace the old lines (multi-line supported)"
+                            },
+                            "expected_old": {
+                                "type": "string",
+                                "description": "The current text of the lines being replaced. If the file no longer matches, the edit is rejected and the actual lines are returned"
+                            }
+                        },
+                        "required": ["start_line", "num_lines", "new_content"]
+                    }
+                }
+            },
+            "required": ["path", "hunks"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_diff_edit(arguments, ctx.working_dir, ctx.require_approval)
+            .await
+    }
+}
+
+/// Apply a unified diff to one or more files
+pub struct ApplyPatch;
+
+#[async_trait]
+impl Tool for ApplyPatch {
+    fn name(&self) -> &str {
+        "apply_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a unified diff (as produced by `diff -u` or `git diff`) that may modify, create (--- /dev/null) or delete (+++ /dev/null) several files. Hunks are located by their context lines, so line numbers may be approximate. Nothing is written unless every hunk applies; failed hunks are reported with the closest matching region"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff with ---/+++ file headers and @@ hunks; include about 3 lines of unchanged context around each change"
+                },
+                "fuzz": {
+                    "type": "integer",
+                    "description": "How many context lines at each end of a hunk may be ignored when it does not match exactly (default 2)",
+                    "minimum": 0
+                }
+            },
+            "required": ["patch"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "patch").unwrap_or(false)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_apply_patch(arguments, ctx.working_dir, ctx.require_approval).await
+    }
+}
+
+/// Move or rename a file or directory
+pub struct FileMove;
+
+#[async_trait]
+impl Tool for FileMove {
+    fn name(&self) -> &str {
+        "file_move"
+    }
+
+    fn description(&self) -> &str {
+        "Move or rename a file or directory. The destination is the new path, not the directory to move into; missing parent directories are created. Fails if the destination exists unless overwrite is set, and directories are never overwritten"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "from": {
+                    "type": "string",
+                    "description": "File or directory to move"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "New path of the file or directory"
+                },
+                "overwrite": {
+                    "type": "boolean",
+                    "description": "Replace an existing destination file (default false)",
+                    "default": false
+                }
+            },
+            "required": ["from", "to"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "from").unwrap_or(false)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_move(arguments, ctx.working_dir, ctx.require_approval).await
+    }
+}
+
+/// Copy a file or directory
+pub struct FileCopy;
+
+#[async_trait]
+impl Tool for FileCopy {
+    fn name(&self) -> &str {
+        "file_copy"
+    }
+
+    fn description(&self) -> &str {
+        "Copy a file, or a directory with recursive set. The destination is the path of the copy, not the directory to copy into; missing parent directories are created. Fails if the destination exists unless overwrite is set, and directories are never overwritten"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "from": {
+                    "type": "string",
+                    "description": "File or directory to copy"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Path of the copy"
+                },
+                "overwrite": {
+                    "type": "boolean",
+                    "description": "Replace an existing destination file (default false)",
+                    "default": false
+                },
+                "recursive": {
+                    "type": "boolean",
+                    "description": "Required to copy a directory with its contents (default false)",
+                    "default": false
+                }
+            },
+            "required": ["from", "to"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "from").unwrap_or(false)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_copy(arguments, ctx.working_dir, ctx.require_approval).await
+    }
+}
+
+/// Delete a file or directory
+pub struct FileDelete;
+
+#[async_trait]
+impl Tool for FileDelete {
+    fn name(&self) -> &str {
+        "file_delete"
+    }
+
+    fn description(&self) -> &str {
+        "Delete a file or an empty directory. Deleting a directory with contents is refused unless recursive is set. The edit can be reverted with /undo"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File or directory to delete"
+                },
+                "recursive": {
+                    "type": "boolean",
+                    "description": "Required to delete a directory that is not empty (default false)",
+                    "default": false
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "path").unwrap_or(false)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_file_delete(arguments, ctx.working_dir, ctx.require_approval).await
+    }
+}
+
+/// Create a directory
+pub struct DirCreate;
+
+#[async_trait]
+impl Tool for DirCreate {
+    fn name(&self) -> &str {
+        "dir_create"
+    }
+
+    fn description(&self) -> &str {
+        "Create a directory along with any missing parent directories; succeeds if it already exists"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to create"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        non_empty_str(arguments, "path").unwrap_or(false)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        file_operations::execute_dir_create(arguments, ctx.working_dir, ctx.require_approval).await
+    }
+}
+
+/// Show the working tree status
+pub struct GitStatus;
+
+#[async_trait]
+impl Tool for GitStatus {
+    fn name(&self) -> &str {
+        "git_status"
+    }
+
+    fn description(&self) -> &str {
+        "Show the current branch and the changed, staged and untracked files of the git repository (git status --short --branch)"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Only show changes under this file or directory (optional, defaults to the whole repository)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        git_operations::execute_git_status(arguments, ctx.working_dir).await
+    }
+}
+
+/// Show uncommitted or committed changes as a diff
+pub struct GitDiff;
+
+#[async_trait]
+impl Tool for GitDiff {
+    fn name(&self) -> &str {
+        "git_diff"
+    }
+
+    fn description(&self) -> &str {
+        "Show changes as a unified diff: unstaged changes by default, staged changes with staged, or changes since a commit with base"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Only diff this file or directory (optional)"
+                },
+                "staged": {
+                    "type": "boolean",
+                    "description": "Diff the staged changes against HEAD (default false)",
+                    "default": false
+                },
+                "base": {
+                    "type": "string",
+                    "description": "Commit, branch or tag to diff against, e.g. \"HEAD~1\" or \"main\" (optional)"
+                },
+                "stat": {
+                    "type": "boolean",
+                    "description": "Only list changed files with added and removed line counts (default false)",
+                    "default": false
+                }
+            },
+            "required": []
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        git_operations::execute_git_diff(arguments, ctx.working_dir).await
+    }
+}
+
+/// Show the commit history
+pub struct GitLog;
+
+#[async_trait]
+impl Tool for GitLog {
+    fn name(&self) -> &str {
+        "git_log"
+    }
+
+    fn description(&self) -> &str {
+        "Show recent commits with hash, date, author, refs and subject, newest first"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Only show commits that touched this file or directory (optional)"
+                },
+                "revision": {
+                    "type": "string",
+                    "description": "Branch, tag, commit or range such as \"main..HEAD\" (optional, defaults to HEAD)"
+                },
+                "max_count": {
+                    "type": "integer",
+                    "description": "Maximum number of commits (default 20, max 200)",
+                    "minimum": 1
+                }
+            },
+            "required": []
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        git_operations::execute_git_log(arguments, ctx.working_dir).await
+    }
+}
+
+/// Run a shell command
+pub struct RunCommand;
+
+#[async_trait]
+impl Tool for RunCommand {
+    fn name(&self) -> &str {
+        "run_command"
+    }
+
+    fn description(&self) -> &str {
+        "Execute a shell command with approval prompts. Supports foreground and background execution. Foreground output is streamed to the user and returned with the middle cut when it is long; the command is killed when it exceeds its timeout."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "command": {
+                    "type": "string",
+                    "description": "The shell command to execute"
+                },
+                "background": {
+                    "type": "boolean",
+                    "description": "Whether to run the command in background (returns immediately with a job ID, output goes to a log file) or foreground (waits and returns output)",
+                    "default": false
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Foreground timeout in seconds (defaults to the configured command_timeout_secs, 0 disables it)",
+                    "minimum": 0
+                },
+                "cwd": {
+                    "type": "string",
+                    "description": "Directory to run the command in, relative to the working directory (optional)"
+                },
+                "env": {
+                    "type": "object",
+                    "description": "Extra environment variables for the command",
+                    "additionalProperties": { "type": "string" }
+                }
+            },
+            "required": ["command"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        command_operations::execute_run_command(arguments, ctx.working_dir, ctx.require_approval)
+            .await
+    }
+}
+
+/// Show background jobs and their output
+pub struct CommandStatus;
+
+#[async_trait]
+impl Tool for CommandStatus {
+    fn name(&self) -> &str {
+        "command_status"
+    }
+
+    fn description(&self) -> &str {
+        "Show the status and latest output of a background command started with run_command, or list all background jobs when no id is given"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Job ID returned by run_command (optional, lists all jobs when omitted)"
+                },
+                "lines": {
+                    "type": "integer",
+                    "description": "Number of output lines from the end of the log (default 50, max 500)",
+                    "minimum": 1
+                }
+            },
+            "required": []
+        })
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        command_operations::execute_command_status(arguments)
+    }
+}
+
+/// Stop a background job
+pub struct CommandKill;
+
+#[async_trait]
+impl Tool for CommandKill {
+    fn name(&self) -> &str {
+        "command_kill"
+    }
+
+    fn description(&self) -> &str {
+        "Stop a background command started with run_command, including any processes it started"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Job ID returned by run_command"
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    // Ids come from the job table, so it can only kill background commands that
+    // already went through run_command's approval
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Never
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        arguments.get("id").is_some_and(Value::is_u64)
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        command_operations::execute_command_kill(arguments)
+    }
+}
+
+/// Start an interactive program in a pseudo terminal
+pub struct PtyStart;
+
+#[async_trait]
+impl Tool for PtyStart {
+    fn name(&self) -> &str {
+        "pty_start"
+    }
+
+    fn description(&self) -> &str {
+        "Start an interactive program (REPL, database shell, debugger, setup wizard) in a pseudo terminal and return a session ID with its first output. Use pty_send to type into it and pty_close to end it. Sessions close after 15 minutes without input."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "command": {
+                    "type": "string",
+                    "description": "The shell command that starts the program, e.g. \"python3\" or \"npm init\""
+                },
+                "cwd": {
+                    "type": "string",
+                    "description": "Directory to run the program in, relative to the working directory (optional)"
+                },
+                "env": {
+                    "type": "object",
+                    "description": "Extra environment variables for the program",
+                    "additionalProperties": { "type": "string" }
+                },
+                "rows": {
+                    "type": "integer",
+                    "description": "Terminal height (default 24)",
+                    "minimum": 10
+                },
+                "cols": {
+                    "type": "integer",
+                    "description": "Terminal width (default 120)",
+                    "minimum": 40
+                }
+            },
+            "required": ["command"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        pty_operations::execute_pty_start(arguments, ctx.working_dir, ctx.require_approval).await
+    }
+}
+
+/// Type into an interactive session and read what it printed
+pub struct PtySend;
+
+#[async_trait]
+impl Tool for PtySend {
+    fn name(&self) -> &str {
+        "pty_send"
+    }
+
+    fn description(&self) -> &str {
+        "Send input to a session started with pty_start and return its new output once it has been quiet for a moment. Newlines in input are sent as Enter. Full-screen programs also return the visible screen."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Session ID returned by pty_start"
+                },
+                "input": {
+                    "type": "string",
+                    "description": "Text to type; end it with \\n to press Enter (optional, omit to just read new output)"
+                },
+                "keys": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Keys to press after the input: enter, tab, esc, backspace, space, up, down, left, right, home, end, pageup, pagedown, delete or ctrl-<letter> such as ctrl-c and ctrl-d"
+                },
+                "quiet_ms": {
+                    "type": "integer",
+                    "description": "Return once there was no output for this many milliseconds (default 500)",
+                    "minimum": 50
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Return after this many seconds even if output continues (default 10, max 120)",
+                    "minimum": 1
+                },
+                "screen": {
+                    "type": "boolean",
+                    "description": "Also return the visible terminal screen",
+                    "default": false
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        arguments.get("id").is_some_and(Value::is_u64)
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        pty_operations::execute_pty_send(arguments, ctx.require_approval).await
+    }
+}
+
+/// End an interactive session
+pub struct PtyClose;
+
+#[async_trait]
+impl Tool for PtyClose {
+    fn name(&self) -> &str {
+        "pty_close"
+    }
+
+    fn description(&self) -> &str {
+        "End a session started with pty_start, stopping its program, and return its last output"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Session ID returned by pty_start"
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    // Session ids only name programs pty_start launched after approval, so ending
+    // one needs no second prompt
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Never
+    }
+
+    fn arguments_complete(&self, arguments: &Value) -> bool {
+        arguments.get("id").is_some_and(Value::is_u64)
+    }
+
+    async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        pty_operations::execute_pty_close(arguments)
+    }
+}
+
+/// Parameters shared by the web search tools
+fn search_parameters() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "keywords": {
+                "type": "string",
+                "description": "Search keywords or query"
+            },
+            "max_results": {
+                "type": "integer",
+                "description": "Maximum number of results to return (default 5, max 20)",
+                "default": 5,
+                "minimum": 1,
+                "maximum": 20
+            }
+        },
+        "required": ["keywords"]
+    })
+}
+
+/// Whether a string argument is non-empty, `None` when it is missing
+fn non_empty_str(arguments: &Value, key: &str) -> Option<bool> {
+    arguments[key].as_str().map(|s| !s.is_empty())
+}
@@ -0,0 +1,430 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use config::Config;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use super::utils::content_hash;
+
+/// Checkpoint store of the current session, opened by `begin_turn`
+static STORE: Mutex<Option<CheckpointStore>> = Mutex::new(None);
+
+/// Longest label kept from the user message that started a turn
+const MAX_LABEL_CHARS: usize = 80;
+
+/// Files edited by the tools during one assistant turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub id: u32,
+    pub created_at: DateTime<Utc>,
+    /// First line of the user message that started the turn
+    pub label: String,
+    pub files: Vec<FileSnapshot>,
+    /// Set once the edits were reverted by `/undo` or `/restore`
+    #[serde(default)]
+    pub undone: bool,
+}
+
+/// A file or directory as it was before the first edit of a turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSnapshot {
+    pub path: PathBuf,
+    /// Whether the path existed; a file's previous content is stored next to the index
+    pub existed: bool,
+    /// Whether the path was a directory, which has no stored content
+    #[serde(default)]
+    pub dir: bool,
+    /// Content hash after the last edit of the turn, `None` when the file is gone
+    pub after: Option<u64>,
+}
+
+/// Outcome of reverting checkpoints
+#[derive(Debug, Default)]
+pub struct Restored {
+    /// Reverted checkpoints, newest first
+    pub checkpoints: Vec<u32>,
+    pub files: Vec<PathBuf>,
+    /// Files changed by someone else since the edit; nothing is written when not empty
+    pub conflicts: Vec<PathBuf>,
+}
+
+/// The turn in progress
+#[derive(Debug)]
+struct Turn {
+    label: String,
+    /// Created on the first edit of the turn
+    checkpoint: Option<u32>,
+}
+
+/// Checkpoints of one session, stored under `<root>/<session id>/`: an `index.json`
+/// and the previous file contents as `<checkpoint id>/<file index>`
+#[derive(Debug)]
+pub struct CheckpointStore {
+    session: Uuid,
+    dir: PathBuf,
+    checkpoints: Vec<Checkpoint>,
+    turn: Option<Turn>,
+}
+
+impl CheckpointStore {
+    pub fn open(root: &Path, session: Uuid) -> Result<Self> {
+        let dir = root.join(session.to_string());
+        let index = dir.join("index.json");
+        let checkpoints = if index.exists() {
+            serde_json::from_str(&fs::read_to_string(&index)?)
+                .with_context(|| format!("Invalid {}", index.display()))?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            session,
+            dir,
+            checkpoints,
+            turn: None,
+        })
+    }
+
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Start an assistant turn; its checkpoint is created on the first edit
+    pub fn begin_turn(&mut self, label: &str) {
+        let label = label
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(MAX_LABEL_CHARS)
+            .collect();
+        self.turn = Some(Turn {
+            label,
+            checkpoint: None,
+        });
+    }
+
+    /// Save a file before the tools modify it, once per turn
+    pub fn before_write(&mut self, path: &Path) -> Result<()> {
+        let Some(turn) = self.turn.as_mut() else {
+            return Ok(());
+        };
+        let id = match turn.checkpoint {
+            Some(id) => id,
+            None => {
+                let id = self.checkpoints.last().map_or(1, |last| last.id + 1);
+                self.checkpoints.push(Checkpoint {
+                    id,
+                    created_at: Utc::now(),
+                    label: turn.label.clone(),
+                    files: Vec::new(),
+                    undone: false,
+                });
+                turn.checkpoint = Some(id);
+                id
+            }
+        };
+
+        let path = absolute(path);
+        let checkpoint = self.checkpoints.iter_mut().find(|c| c.id == id).unwrap();
+        if checkpoint.files.iter().any(|file| file.path == path) {
+            return Ok(());
+        }
+        let existed = path.exists();
+        let dir = path.is_dir();
+        if existed && !dir {
+            let blob = self
+                .dir
+                .join(id.to_string())
+                .join(checkpoint.files.len().to_string());
+            fs::create_dir_all(blob.parent().unwrap())?;
+            fs::copy(&path, &blob)?;
+        }
+        checkpoint.files.push(FileSnapshot {
+            after: current_hash(&path),
+            path,
+            existed,
+            dir,
+        });
+        self.save()
+    }
+
+    /// Remember what a file looks like after the tools modified it
+    pub fn after_write(&mut self, path: &Path) -> Result<()> {
+        let Some(id) = self.turn.as_ref().and_then(|turn| turn.checkpoint) else {
+            return Ok(());
+        };
+        let path = absolute(path);
+        let Some(file) = self
+            .checkpoints
+            .iter_mut()
+            .filter(|c| c.id == id)
+            .flat_map(|c| c.files.iter_mut())
+            .find(|file| file.path == path)
+        else {
+            return Ok(());
+        };
+        file.after = current_hash(&path);
+        self.save()
+    }
+
+    /// Files edited so far in the current turn
+    pub fn turn_files(&self) -> Vec<PathBuf> {
+        let Some(id) = self.turn.as_ref().and_then(|turn| turn.checkpoint) else {
+            return Vec::new();
+        };
+        self.checkpoints
+            .iter()
+            .filter(|c| c.id == id)
+            .flat_map(|c| c.files.iter().map(|file| file.path.clone()))
+            .collect()
+    }
+
+    /// Revert the edits of the most recent checkpoint that is not undone yet
+    pub fn undo(&mut self, force: bool) -> Result<Restored> {
+        match self.checkpoints.iter().rev().find(|c| !c.undone) {
+            Some(last) => self.restore(last.id, force),
+            None => Ok(Restored::default()),
+        }
+    }
+
+    /// Put every file back as it was before checkpoint `id`, reverting it and all later ones
+    pub fn restore(&mut self, id: u32, force: bool) -> Result<Restored> {
+        let targets: Vec<&Checkpoint> = self
+            .checkpoints
+            .iter()
+            .filter(|c| c.id >= id && !c.undone)
+            .collect();
+
+        // Oldest snapshot of each path and its state after the newest edit
+        let mut files: Vec<(&FileSnapshot, PathBuf, Option<u64>)> = Vec::new();
+        for checkpoint in &targets {
+            for (index, file) in checkpoint.files.iter().enumerate() {
+                match files.iter_mut().find(|(first, ..)| first.path == file.path) {
+                    Some(entry) => entry.2 = file.after,
+                    None => files.push((
+                        file,
+                        self.dir
+                            .join(checkpoint.id.to_string())
+                            .join(index.to_string()),
+                        file.after,
+                    )),
+                }
+            }
+        }
+
+        let mut restored = Restored {
+            checkpoints: targets.iter().rev().map(|c| c.id).collect(),
+            ..Restored::default()
+        };
+        restored.conflicts = files
+            .iter()
+            .filter(|(file, _, after)| current_hash(&file.path) != *after)
+            .map(|(file, ..)| file.path.clone())
+            .collect();
+        if !restored.conflicts.is_empty() && !force {
+            return Ok(restored);
+        }
+
+        let mut created_dirs = Vec::new();
+        for (file, blob, _) in files {
+            let path = &file.path;
+            if file.existed && file.dir {
+                fs::create_dir_all(path)?;
+            } else if file.existed {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&blob, path)
+                    .with_context(|| format!("Cannot restore {}", path.display()))?;
+            } else if path.is_dir() {
+                created_dirs.push(path);
+            } else if path.exists() {
+                fs::remove_file(path)?;
+            }
+            restored.files.push(path.clone());
+        }
+        // Directories created by the tools go last, deepest first, and only once empty
+        created_dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        for dir in created_dirs {
+            let _ = fs::remove_dir(dir);
+        }
+        for checkpoint in self.checkpoints.iter_mut().filter(|c| c.id >= id) {
+            checkpoint.undone = true;
+        }
+        // Later edits of the current turn start a new checkpoint
+        if let Some(turn) = self.turn.as_mut() {
+            turn.checkpoint = None;
+        }
+        self.save()?;
+        Ok(restored)
+    }
+
+    fn save(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let index = serde_json::to_string_pretty(&self.checkpoints)?;
+        fs::write(self.dir.join("index.json"), index)?;
+        Ok(())
+    }
+}
+
+/// Content hash of a file, `None` when it does not exist
+fn current_hash(path: &Path) -> Option<u64> {
+    fs::read(path).ok().map(|content| content_hash(&content))
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn checkpoints_root() -> Result<PathBuf> {
+    Ok(Config::config_dir()?.join("checkpoints"))
+}
+
+/// Run `f` on the store of a session, opening it when another session was active
+fn with_store<T>(session: Uuid, f: impl FnOnce(&mut CheckpointStore) -> Result<T>) -> Result<T> {
+    let mut store = STORE.lock().unwrap();
+    if store.as_ref().is_none_or(|s| s.session != session) {
+        *store = Some(CheckpointStore::open(&checkpoints_root()?, session)?);
+    }
+    f(store.as_mut().unwrap())
+}
+
+/// Run `f` on the active store; a no-op before the first turn
+fn with_active_store(f: impl FnOnce(&mut CheckpointStore) -> Result<()>) -> Result<()> {
+    match STORE.lock().unwrap().as_mut() {
+        Some(store) => f(store),
+        None => Ok(()),
+    }
+}
+
+/// Start an assistant turn of a session; files edited by the tools are saved first
+pub fn begin_turn(session: Uuid, label: &str) -> Result<()> {
+    with_store(session, |store| {
+        store.begin_turn(label);
+        Ok(())
+    })
+}
+
+/// Snapshot a file before a tool modifies it
+pub fn before_write(path: &Path) -> Result<()> {
+    with_active_store(|store| store.before_write(path))
+}
+
+/// Record a file's content after a tool modified it, for conflict detection
+pub fn after_write(path: &Path) -> Result<()> {
+    with_active_store(|store| store.after_write(path))
+}
+
+/// Files edited by the tools in the current turn
+pub fn turn_files() -> Vec<PathBuf> {
+    STORE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(CheckpointStore::turn_files)
+        .unwrap_or_default()
+}
+
+/// Checkpoints of a session, oldest first
+pub fn list_checkpoints(session: Uuid) -> Result<Vec<Checkpoint>> {
+    with_store(session, |store| Ok(store.checkpoints().to_vec()))
+}
+
+/// Revert the last turn's edits
+pub fn undo(session: Uuid, force: bool) -> Result<Restored> {
+    with_store(session, |store| store.undo(force))
+}
+
+/// Revert checkpoint `id` and every later one
+pub fn restore(session: Uuid, id: u32, force: bool) -> Result<Restored> {
+    with_store(session, |store| store.restore(id, force))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_and_restore() {
+        let root =
+            std::env::temp_dir().join(format!("friendev_checkpoints_{}", std::process::id()));
+        let work = root.join("work");
+        fs::create_dir_all(&work).unwrap();
+        let file = work.join("a.txt");
+        let created = work.join("b.txt");
+        fs::write(&file, "v1").unwrap();
+
+        let mut store = CheckpointStore::open(&root, Uuid::new_v4()).unwrap();
+        let edit = |store: &mut CheckpointStore, path: &Path, content: &str| {
+            store.before_write(path).unwrap();
+            fs::write(path, content).unwrap();
+            store.after_write(path).unwrap();
+        };
+
+        store.begin_turn("first turn\nmore text");
+        edit(&mut store, &file, "v2");
+        edit(&mut store, &file, "v3");
+        store.begin_turn("second turn");
+        edit(&mut store, &file, "v4");
+        edit(&mut store, &created, "new");
+        assert_eq!(
+            store.turn_files(),
+            vec![absolute(&file), absolute(&created)]
+        );
+        store.begin_turn("no edits");
+        assert!(store.turn_files().is_empty());
+
+        assert_eq!(store.checkpoints().len(), 2);
+        assert_eq!(store.checkpoints()[0].label, "first turn");
+        assert_eq!(store.checkpoints()[0].files.len(), 1);
+
+        // Changed outside the tools: undo refuses unless forced
+        fs::write(&created, "edited by the user").unwrap();
+        let restored = store.undo(false).unwrap();
+        assert_eq!(restored.conflicts, vec![absolute(&created)]);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "v4");
+
+        let restored = store.undo(true).unwrap();
+        assert_eq!(restored.checkpoints, vec![2]);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "v3");
+        assert!(!created.exists());
+
+        // The index survives reopening the store
+        let mut reopened = CheckpointStore::open(&root, store.session).unwrap();
+        assert!(reopened.checkpoints()[1].undone);
+        let restored = reopened.restore(1, false).unwrap();
+        assert_eq!(restored.checkpoints, vec![1]);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "v1");
+        assert!(reopened.undo(false).unwrap().checkpoints.is_empty());
+    }
+
+    #[test]
+    fn test_restore_directories() {
+        let root =
+            std::env::temp_dir().join(format!("friendev_checkpoint_dirs_{}", std::process::id()));
+        let old = root.join("work/old");
+        let new = root.join("work/new");
+        fs::create_dir_all(&old).unwrap();
+        fs::write(old.join("a.rs"), "a").unwrap();
+
+        // Move old/ to new/ as file_move does
+        let mut store = CheckpointStore::open(&root, Uuid::new_v4()).unwrap();
+        store.begin_turn("move");
+        let paths = [old.clone(), old.join("a.rs"), new.clone(), new.join("a.rs")];
+        for path in &paths {
+            store.before_write(path).unwrap();
+        }
+        fs::rename(&old, &new).unwrap();
+        for path in &paths {
+            store.after_write(path).unwrap();
+        }
+
+        let restored = store.undo(false).unwrap();
+        assert!(restored.conflicts.is_empty());
+        assert_eq!(fs::read_to_string(old.join("a.rs")).unwrap(), "a");
+        assert!(!new.exists());
+    }
+}
@@ -13,25 +13,6 @@ const DEFAULT_ALWAYS_APPROVE_COMMANDS: &[&str] = &["rm", "del", "rmdir", "format
 pub struct CommandConfig {
     /// 总是需要确认的命令列表
     pub always_approve_commands: HashSet<String>,
-    /// 运行中的后台命令
-    pub running_commands: Vec<BackgroundCommand>,
-}
-
-/// 后台运行命令信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BackgroundCommand {
-    /// 命令ID
-    pub id: String,
-    /// 执行的命令
-    pub command: String,
-    /// 启动时间
-    pub start_time: chrono::DateTime<chrono::Utc>,
-    /// 命令状态：running, completed, failed
-    pub status: String,
-    /// 命令退出码（如果已完成）
-    pub exit_code: Option<i32>,
-    /// 命令输出（如果已收集）
-    pub output: Option<String>,
 }
 
 impl Default for CommandConfig {
@@ -43,7 +24,6 @@ impl Default for CommandConfig {
 
         Self {
             always_approve_commands,
-            running_commands: Vec::new(),
         }
     }
 }
@@ -97,29 +77,6 @@ impl CommandConfig {
         self.always_approve_commands.contains(&main_command)
     }
 
-    /// 添加后台命令
-    pub fn add_background_command(&mut self, cmd: BackgroundCommand) {
-        self.running_commands.push(cmd);
-    }
-
-    /// 更新后台命令状态
-    pub fn update_background_command<F>(&mut self, id: &str, updater: F) -> bool
-    where
-        F: FnOnce(&mut BackgroundCommand),
-    {
-        if let Some(cmd) = self.running_commands.iter_mut().find(|c| c.id == id) {
-            updater(cmd);
-            true
-        } else {
-            false
-        }
-    }
-
-    /// 获取后台命令
-    pub fn get_background_command(&self, id: &str) -> Option<&BackgroundCommand> {
-        self.running_commands.iter().find(|c| c.id == id)
-    }
-
     /// 列出所有总是需要确认的命令
     pub fn list_always_approve_commands(&self) -> Vec<String> {
         let mut commands: Vec<String> = self.always_approve_commands.iter().cloned().collect();
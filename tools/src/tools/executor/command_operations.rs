@@ -1,184 +1,163 @@
 use anyhow::Result;
-
-use crate::tools::args::RunCommandArgs;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::command_runner::{run_streaming, RunEnd};
+use super::file_operations::{resolve_path, Access};
+use crate::tools::args::{CommandKillArgs, CommandStatusArgs, RunCommandArgs};
+use crate::tools::command_manager::CommandConfig;
+use crate::tools::jobs::{self, Job, KillOutcome};
 use crate::types::{approve_action_for_session, is_action_approved, ToolResult};
-use ui::{get_i18n, prompt_approval};
+use ui::{get_i18n, prompt_approval, ToolProgress};
 
-pub async fn execute_run_command(arguments: &str, require_approval: bool) -> Result<ToolResult> {
+pub async fn execute_run_command(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
     let args: RunCommandArgs = serde_json::from_str(arguments)?;
+    let cwd = match command_cwd(args.cwd.as_deref(), working_dir) {
+        Ok(cwd) => cwd,
+        Err(rejected) => return Ok(rejected),
+    };
 
     // 加载命令配置
     let config = crate::tools::command_manager::CommandConfig::load()?;
 
-    // 检查是否需要审批
-    let needs_approval = require_approval || config.needs_approval(&args.command);
-
-    if needs_approval && !is_action_approved("run_command") {
-        // 提取主命令用于显示
-        let main_command = args.command.split_whitespace().next().unwrap_or("");
-
-        let (approved, always, view_details) = prompt_approval(
-            "RunCommand",
-            &args.command.to_string(),
-            Some(&format!(
-                "Command: {}\nMode: {}",
-                main_command,
-                if args.background {
-                    "background"
-                } else {
-                    "foreground"
-                }
-            )),
-        )?;
-
-        if view_details {
-            let continue_operation = ui::show_detailed_content(
-                "RunCommand",
-                &format!("Command: {}", args.command),
-                &format!(
-                    "Full command:\n{}\n\nThis command will be executed in {} mode.",
-                    args.command,
-                    if args.background {
-                        "background"
-                    } else {
-                        "foreground"
-                    }
-                ),
-            )?;
-
-            if !continue_operation {
-                let i18n = get_i18n();
-                return Ok(ToolResult::error(i18n.get("run_command_user_cancelled")));
-            }
-        } else if !approved {
-            let i18n = get_i18n();
-            return Ok(ToolResult::error(i18n.get("run_command_user_rejected")));
-        }
-
-        if always {
-            approve_action_for_session("run_command");
-        }
+    let request = CommandRequest {
+        command: &args.command,
+        mode: if args.background {
+            "background"
+        } else {
+            "foreground"
+        },
+        cwd: Some(&cwd),
+        env: &args.env,
+        // 后台命令不设超时
+        timeout_secs: (!args.background).then(|| command_timeout_secs(args.timeout_secs)),
+    };
+    if let Some(rejected) = approve_command(&config, &request, require_approval)? {
+        return Ok(rejected);
     }
 
     if args.background {
-        execute_background_command(args, config).await
+        execute_background_command(args, &cwd)
     } else {
-        execute_foreground_command(args).await
+        execute_foreground_command(args, &cwd).await
     }
 }
 
-async fn execute_background_command(
-    args: RunCommandArgs,
-    mut config: crate::tools::command_manager::CommandConfig,
-) -> Result<ToolResult> {
-    use tokio::process::Command as TokioCommand;
-    use uuid::Uuid;
-
-    let run_id = Uuid::new_v4().to_string();
-    let run_id_for_async = run_id.clone();
-    let command_for_async = args.command.clone();
-
-    // 创建后台命令
-    let bg_cmd = crate::tools::command_manager::BackgroundCommand {
-        id: run_id.clone(),
-        command: args.command.clone(),
-        start_time: chrono::Utc::now(),
-        status: "running".to_string(),
-        exit_code: None,
-        output: None,
+/// 命令的工作目录：默认为会话工作目录，相对路径基于它解析；与文件工具一样受工作区限制
+pub(crate) fn command_cwd(
+    cwd: Option<&str>,
+    working_dir: &Path,
+) -> std::result::Result<PathBuf, ToolResult> {
+    let Some(cwd) = cwd.filter(|cwd| !cwd.trim().is_empty()) else {
+        return Ok(working_dir.to_path_buf());
     };
+    let path = resolve_path(cwd, working_dir, Access::Write)?;
+    if !path.is_dir() {
+        let tmpl = get_i18n().get("run_command_cwd_invalid");
+        return Err(ToolResult::error(
+            tmpl.replace("{}", &path.display().to_string()),
+        ));
+    }
+    Ok(path)
+}
 
-    // 保存到配置
-    config.add_background_command(bg_cmd);
-    config.save()?;
-
-    // 在后台启动命令
-    tokio::spawn(async move {
-        let mut cmd = if cfg!(target_os = "windows") {
-            TokioCommand::new("cmd")
-        } else {
-            TokioCommand::new("sh")
-        };
+/// 待审批的命令及其运行环境，审批时全部展示给用户
+pub(crate) struct CommandRequest<'a> {
+    pub command: &'a str,
+    pub mode: &'a str,
+    /// 工作目录（在已有的 PTY 会话中输入时为 None）
+    pub cwd: Option<&'a Path>,
+    /// 额外的环境变量
+    pub env: &'a HashMap<String, String>,
+    /// 超时秒数，0 表示不限时；None 表示不适用
+    pub timeout_secs: Option<u64>,
+}
 
-        if cfg!(target_os = "windows") {
-            cmd.arg("/C");
-        } else {
-            cmd.arg("-c");
+impl CommandRequest<'_> {
+    /// 工作目录、超时与环境变量，每项一行
+    fn context_lines(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(cwd) = self.cwd {
+            lines.push(format!("Directory: {}", cwd.display()));
         }
-
-        cmd.arg(&command_for_async);
-
-        match cmd.output().await {
-            Ok(output) => {
-                // 更新命令状态
-                let mut config = match crate::tools::command_manager::CommandConfig::load() {
-                    Ok(c) => c,
-                    Err(_) => return, // 如果无法加载配置，直接返回
-                };
-
-                let status = if output.status.success() {
-                    "completed"
-                } else {
-                    "failed"
-                };
-                let exit_code = output.status.code();
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-                let combined_output = if !stdout.is_empty() && !stderr.is_empty() {
-                    format!("STDOUT:\n{}\nSTDERR:\n{}", stdout, stderr)
-                } else if !stdout.is_empty() {
-                    stdout
-                } else {
-                    stderr
-                };
-
-                config.update_background_command(&run_id_for_async, |cmd| {
-                    cmd.status = status.to_string();
-                    cmd.exit_code = exit_code;
-                    cmd.output = Some(combined_output);
-                });
-
-                let _ = config.save();
-            }
-            Err(_) => {
-                // 更新命令状态为失败
-                let mut config = match crate::tools::command_manager::CommandConfig::load() {
-                    Ok(c) => c,
-                    Err(_) => return,
-                };
-
-                config.update_background_command(&run_id_for_async, |cmd| {
-                    cmd.status = "failed".to_string();
-                    cmd.exit_code = None;
-                    cmd.output = Some("Failed to execute command".to_string());
-                });
-
-                let _ = config.save();
-            }
+        match self.timeout_secs {
+            Some(0) => lines.push("Timeout: none".to_string()),
+            Some(secs) => lines.push(format!("Timeout: {}s", secs)),
+            None => {}
+        }
+        let mut env: Vec<_> = self.env.iter().collect();
+        env.sort();
+        for (key, value) in env {
+            lines.push(format!("Env: {}={}", key, value));
         }
-    });
+        lines.join("\n")
+    }
+}
 
-    let i18n = get_i18n();
+/// 执行命令前的审批（run_command、PTY 与脚本工具共用），被拒绝时返回错误结果
+pub(crate) fn approve_command(
+    config: &CommandConfig,
+    request: &CommandRequest,
+    require_approval: bool,
+) -> Result<Option<ToolResult>> {
+    let command = request.command;
+    // 检查是否需要审批
+    let needs_approval = require_approval || config.needs_approval(command);
+    if !needs_approval || is_action_approved("run_command") {
+        return Ok(None);
+    }
 
-    let brief_tmpl = i18n.get("run_command_bg_brief");
-    let brief = brief_tmpl.replace("{}", &run_id);
+    // 提取主命令用于显示
+    let main_command = command.split_whitespace().next().unwrap_or("");
+    let context = request.context_lines();
+    let mut preview = format!("Command: {}\nMode: {}", main_command, request.mode);
+    if !context.is_empty() {
+        preview = format!("{}\n{}", preview, context);
+    }
 
-    let output_tmpl = i18n.get("run_command_bg_output");
-    let output = output_tmpl
-        .replacen("{}", &run_id, 1)
-        .replacen("{}", &args.command, 1);
+    let (approved, always, view_details) = prompt_approval("RunCommand", command, Some(&preview))?;
 
-    Ok(ToolResult::ok(brief, output))
-}
+    if view_details {
+        let mut details = format!(
+            "Full command:\n{}\n\nThis command will be executed in {} mode.",
+            command, request.mode
+        );
+        if !context.is_empty() {
+            details = format!("{}\n\n{}", details, context);
+        }
+        let continue_operation =
+            ui::show_detailed_content("RunCommand", &format!("Command: {}", command), &details)?;
 
-async fn execute_foreground_command(args: RunCommandArgs) -> Result<ToolResult> {
-    use std::process::Command;
+        if !continue_operation {
+            let i18n = get_i18n();
+            return Ok(Some(ToolResult::error(
+                i18n.get("run_command_user_cancelled"),
+            )));
+        }
+    } else if !approved {
+        let i18n = get_i18n();
+        return Ok(Some(ToolResult::error(
+            i18n.get("run_command_user_rejected"),
+        )));
+    }
+
+    if always {
+        approve_action_for_session("run_command");
+    }
+    Ok(None)
+}
 
+/// 通过系统 shell 执行命令（Windows 为 cmd /C，其他为 sh -c）
+pub(crate) fn shell_command(command: &str) -> tokio::process::Command {
     let mut cmd = if cfg!(target_os = "windows") {
-        Command::new("cmd")
+        tokio::process::Command::new("cmd")
     } else {
-        Command::new("sh")
+        tokio::process::Command::new("sh")
     };
 
     if cfg!(target_os = "windows") {
@@ -187,50 +166,212 @@ async fn execute_foreground_command(args: RunCommandArgs) -> Result<ToolResult>
         cmd.arg("-c");
     }
 
-    cmd.arg(&args.command);
-
-    match cmd.output() {
-        Ok(output) => {
-            let status = if output.status.success() {
-                "success"
-            } else {
-                "failed"
-            };
-            let exit_code = output.status.code().unwrap_or(-1);
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-            let combined_output = if !stdout.is_empty() && !stderr.is_empty() {
-                format!("STDOUT:\n{}\nSTDERR:\n{}", stdout, stderr)
-            } else if !stdout.is_empty() {
-                stdout
-            } else {
-                stderr
-            };
+    cmd.arg(command);
+    cmd
+}
 
-            let i18n = get_i18n();
+fn execute_background_command(args: RunCommandArgs, cwd: &Path) -> Result<ToolResult> {
+    let i18n = get_i18n();
+    let job = match jobs::spawn(&args.command, cwd, &args.env) {
+        Ok(job) => job,
+        Err(e) => {
+            let tmpl = i18n.get("run_command_execute_error");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
 
-            let brief_tmpl = i18n.get("run_command_fg_brief");
-            let brief =
-                brief_tmpl
-                    .replacen("{}", status, 1)
-                    .replacen("{}", &exit_code.to_string(), 1);
+    let brief = i18n
+        .get("run_command_bg_brief")
+        .replace("{}", &job.id.to_string());
+    let pid = job.pid.map(|pid| pid.to_string()).unwrap_or_default();
+    let output = i18n
+        .get("run_command_bg_output")
+        .replacen("{}", &job.id.to_string(), 1)
+        .replacen("{}", &pid, 1)
+        .replacen("{}", &args.command, 1)
+        .replacen("{}", &job.log_path.display().to_string(), 1);
+
+    Ok(ToolResult::ok(brief, output))
+}
 
-            let output_tmpl = i18n.get("run_command_fg_output");
-            let output_text = output_tmpl
-                .replacen("{}", &args.command, 1)
-                .replacen("{}", &exit_code.to_string(), 1)
-                .replacen("{}", status, 1)
-                .replacen("{}", &combined_output, 1);
+/// 前台命令的超时秒数：未指定时使用配置中的值，0 表示不限时
+pub(crate) fn command_timeout_secs(timeout_secs: Option<u64>) -> u64 {
+    timeout_secs.unwrap_or_else(|| {
+        config::Config::load()
+            .ok()
+            .flatten()
+            .map_or(config::DEFAULT_COMMAND_TIMEOUT_SECS, |c| {
+                c.command_timeout_secs
+            })
+    })
+}
 
-            Ok(ToolResult::ok(brief, output_text))
-        }
+async fn execute_foreground_command(args: RunCommandArgs, cwd: &Path) -> Result<ToolResult> {
+    let i18n = get_i18n();
+    let timeout_secs = command_timeout_secs(args.timeout_secs);
+    let timeout = (timeout_secs > 0).then(|| Duration::from_secs(timeout_secs));
+
+    let mut command = shell_command(&args.command);
+    command.current_dir(cwd).envs(&args.env);
+    // 实时显示在工具进度行下方
+    let result = run_streaming(command, None, timeout, |line| {
+        let _ = ToolProgress::output_line(line);
+    })
+    .await;
+    let run = match result {
+        Ok(run) => run,
         Err(e) => {
-            let i18n = get_i18n();
             let tmpl = i18n.get("run_command_execute_error");
-            let brief = tmpl.replace("{}", &e.to_string());
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
+
+    let (status, exit_code) = match run.end {
+        RunEnd::Exited(Some(0)) => ("success", 0),
+        RunEnd::Exited(code) => ("failed", code.unwrap_or(-1)),
+        RunEnd::TimedOut => ("timed out", -1),
+        RunEnd::Interrupted => ("interrupted", -1),
+    };
+    let output_text = i18n
+        .get("run_command_fg_output")
+        .replacen("{}", &args.command, 1)
+        .replacen("{}", &exit_code.to_string(), 1)
+        .replacen("{}", status, 1)
+        .replacen("{}", &run.output, 1);
+
+    let brief = match run.end {
+        RunEnd::Exited(_) => i18n
+            .get("run_command_fg_brief")
+            .replacen("{}", status, 1)
+            .replacen("{}", &exit_code.to_string(), 1),
+        RunEnd::TimedOut => i18n
+            .get("run_command_timed_out")
+            .replace("{}", &timeout_secs.to_string()),
+        RunEnd::Interrupted => i18n.get("run_command_interrupted"),
+    };
+    if let RunEnd::Exited(_) = run.end {
+        return Ok(ToolResult::ok(brief, output_text));
+    }
+    let mut stopped = ToolResult::error(brief);
+    stopped.message = format!("{}\n\n{}", stopped.brief, output_text);
+    Ok(stopped)
+}
+
+/// 返回给模型的日志行数上限
+const MAX_STATUS_LINES: usize = 500;
+
+pub fn execute_command_status(arguments: &str) -> Result<ToolResult> {
+    let args: CommandStatusArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let Some(id) = args.id else {
+        let jobs = jobs::list();
+        if jobs.is_empty() {
+            let message = i18n.get("job_none");
+            return Ok(ToolResult::ok(message.clone(), message));
+        }
+        let lines: Vec<String> = jobs.iter().map(job_line).collect();
+        let brief = i18n
+            .get("command_status_list_brief")
+            .replace("{}", &jobs.len().to_string());
+        return Ok(ToolResult::ok(brief, lines.join("\n")));
+    };
+
+    let Some(job) = jobs::get(id) else {
+        return Ok(ToolResult::error(
+            i18n.get("job_not_found").replace("{}", &id.to_string()),
+        ));
+    };
+    let tail = jobs::tail(&job, args.lines.clamp(1, MAX_STATUS_LINES))?;
+    let brief = i18n
+        .get("command_status_brief")
+        .replacen("{}", &job.id.to_string(), 1)
+        .replacen("{}", &job.status_text(), 1);
+    let pid = job.pid.map(|pid| pid.to_string()).unwrap_or_default();
+    let message = i18n
+        .get("job_details")
+        .replacen("{}", &job.id.to_string(), 1)
+        .replacen("{}", &job.status_text(), 1)
+        .replacen("{}", &job.command, 1)
+        .replacen("{}", &pid, 1)
+        .replacen("{}", &job.cwd.display().to_string(), 1)
+        .replacen("{}", &job.started_at.to_rfc3339(), 1)
+        .replacen("{}", &job.log_path.display().to_string(), 1)
+        .replacen("{}", &tail, 1);
+    Ok(ToolResult::ok(brief, message))
+}
 
-            Ok(ToolResult::error(brief))
+pub fn execute_command_kill(arguments: &str) -> Result<ToolResult> {
+    let args: CommandKillArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+    match jobs::kill(args.id, false) {
+        KillOutcome::Killed(job) => {
+            let message = i18n
+                .get("command_kill_done")
+                .replace("{}", &job.id.to_string());
+            Ok(ToolResult::ok(message.clone(), message))
         }
+        KillOutcome::NotRunning(job) => Ok(ToolResult::error(
+            i18n.get("command_kill_not_running")
+                .replacen("{}", &job.id.to_string(), 1)
+                .replacen("{}", &job.status_text(), 1),
+        )),
+        KillOutcome::NotFound => Ok(ToolResult::error(
+            i18n.get("job_not_found")
+                .replace("{}", &args.id.to_string()),
+        )),
+    }
+}
+
+/// 任务列表中的一行：`#1 [running] npm run dev (pid 1234)`
+fn job_line(job: &Job) -> String {
+    let pid = job
+        .pid
+        .map(|pid| format!(" (pid {})", pid))
+        .unwrap_or_default();
+    format!("#{} [{}] {}{}", job.id, job.status_text(), job.command, pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_cwd_stays_in_workspace() {
+        let dir = std::env::temp_dir().join(format!("friendev_command_cwd_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+
+        assert_eq!(command_cwd(None, &dir).ok(), Some(dir.clone()));
+        assert_eq!(command_cwd(Some("sub"), &dir).ok(), Some(dir.join("sub")));
+        assert!(command_cwd(Some("missing"), &dir).is_err());
+        assert!(command_cwd(Some(".."), &dir).is_err());
+        assert!(command_cwd(Some("sub/../.."), &dir).is_err());
+    }
+
+    #[test]
+    fn test_request_lists_cwd_timeout_and_env() {
+        let env = HashMap::from([
+            ("PATH".to_string(), "/tmp/bin".to_string()),
+            ("LD_PRELOAD".to_string(), "evil.so".to_string()),
+        ]);
+        let request = CommandRequest {
+            command: "make test",
+            mode: "foreground",
+            cwd: Some(Path::new("/work/app")),
+            env: &env,
+            timeout_secs: Some(120),
+        };
+        assert_eq!(
+            request.context_lines(),
+            "Directory: /work/app\nTimeout: 120s\nEnv: LD_PRELOAD=evil.so\nEnv: PATH=/tmp/bin"
+        );
+
+        let typed = CommandRequest {
+            cwd: None,
+            env: &HashMap::new(),
+            timeout_secs: None,
+            ..request
+        };
+        assert!(typed.context_lines().is_empty());
     }
 }
@@ -0,0 +1,295 @@
+use std::collections::VecDeque;
+use std::io::IsTerminal;
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use ui::get_i18n;
+
+/// 返回给模型的输出保留开头与结尾各这么多字节
+pub(crate) const OUTPUT_HEAD_BYTES: usize = 16 * 1024;
+pub(crate) const OUTPUT_TAIL_BYTES: usize = 16 * 1024;
+/// 单行输出的最大字节数
+const MAX_LINE_BYTES: usize = 4 * 1024;
+/// 进程退出后等待剩余输出的时间（脱离进程组的子进程可能仍持有管道）
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// 命令的结束方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RunEnd {
+    /// 正常退出（被信号结束时没有退出码）
+    Exited(Option<i32>),
+    TimedOut,
+    /// 用户按 Esc 中止
+    Interrupted,
+}
+
+pub(crate) struct RunOutput {
+    pub end: RunEnd,
+    /// stdout 与 stderr 按到达顺序合并，超出上限时截去中间部分
+    pub output: String,
+}
+
+/// 只保留开头和结尾的输出，中间部分按行省略
+pub(crate) struct OutputCap {
+    head: String,
+    head_limit: usize,
+    tail: VecDeque<String>,
+    tail_bytes: usize,
+    tail_limit: usize,
+    omitted: usize,
+}
+
+impl OutputCap {
+    pub fn new(head_limit: usize, tail_limit: usize) -> Self {
+        Self {
+            head: String::new(),
+            head_limit,
+            tail: VecDeque::new(),
+            tail_bytes: 0,
+            tail_limit,
+            omitted: 0,
+        }
+    }
+
+    pub fn push_line(&mut self, line: &str) {
+        let line = truncate_line(line, MAX_LINE_BYTES);
+        if self.tail.is_empty() && self.head.len() + line.len() < self.head_limit {
+            self.head.push_str(line);
+            self.head.push('\n');
+            return;
+        }
+        self.tail_bytes += line.len() + 1;
+        self.tail.push_back(line.to_string());
+        while self.tail_bytes > self.tail_limit {
+            let Some(dropped) = self.tail.pop_front() else {
+                break;
+            };
+            self.tail_bytes -= dropped.len() + 1;
+            self.omitted += 1;
+        }
+    }
+
+    pub fn finish(self) -> String {
+        let mut output = self.head;
+        if self.omitted > 0 {
+            let marker = get_i18n()
+                .get("run_command_output_truncated")
+                .replace("{}", &self.omitted.to_string());
+            output.push_str(&marker);
+            output.push('\n');
+        }
+        for line in self.tail {
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// 在字符边界处截断过长的行
+fn truncate_line(line: &str, max_bytes: usize) -> &str {
+    if line.len() <= max_bytes {
+        return line;
+    }
+    let mut end = max_bytes;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    &line[..end]
+}
+
+/// 在独立进程组中运行命令，每行输出都交给 on_line；超时或按 Esc 时结束整个进程组。
+/// input 为写入 stdin 的内容，为 None 时 stdin 为空
+pub(crate) async fn run_streaming(
+    mut cmd: Command,
+    input: Option<Vec<u8>>,
+    timeout: Option<Duration>,
+    mut on_line: impl FnMut(&str),
+) -> std::io::Result<RunOutput> {
+    let stdin = if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+    cmd.stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    cmd.process_group(0);
+    let mut child = cmd.spawn()?;
+
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        tokio::spawn(async move {
+            // 不读取 stdin 的命令可能在写完之前就已退出
+            let _ = stdin.write_all(&input).await;
+        });
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(forward_lines(stdout, tx.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(forward_lines(stderr, tx.clone()));
+    }
+    drop(tx);
+
+    let mut cap = OutputCap::new(OUTPUT_HEAD_BYTES, OUTPUT_TAIL_BYTES);
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let watch_escape = std::io::stdin().is_terminal();
+    let mut ticker = tokio::time::interval(Duration::from_millis(100));
+
+    let end = loop {
+        tokio::select! {
+            Some(line) = rx.recv() => {
+                on_line(&line);
+                cap.push_line(&line);
+            }
+            status = child.wait() => break Ended::Exited(status?),
+            _ = wait_deadline(deadline) => break Ended::TimedOut,
+            _ = ticker.tick(), if watch_escape => {
+                if escape_pressed() {
+                    break Ended::Interrupted;
+                }
+            }
+        }
+    };
+    let end = match end {
+        Ended::Exited(status) => RunEnd::Exited(status.code()),
+        Ended::TimedOut => {
+            kill_tree(&mut child).await;
+            RunEnd::TimedOut
+        }
+        Ended::Interrupted => {
+            kill_tree(&mut child).await;
+            RunEnd::Interrupted
+        }
+    };
+
+    let _ = tokio::time::timeout(DRAIN_TIMEOUT, async {
+        while let Some(line) = rx.recv().await {
+            on_line(&line);
+            cap.push_line(&line);
+        }
+    })
+    .await;
+
+    Ok(RunOutput {
+        end,
+        output: cap.finish(),
+    })
+}
+
+enum Ended {
+    Exited(ExitStatus),
+    TimedOut,
+    Interrupted,
+}
+
+async fn wait_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 逐行读取输出；非 UTF-8 内容按有损方式转换，`\r` 刷新的进度行只保留最后一段
+async fn forward_lines(reader: impl AsyncRead + Unpin, tx: mpsc::UnboundedSender<String>) {
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let text = String::from_utf8_lossy(&buf);
+                let text = text.trim_end_matches(['\n', '\r']);
+                let line = text.rsplit('\r').next().unwrap_or_default();
+                if tx.send(line.to_string()).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// 结束命令及其启动的所有子进程
+pub(crate) async fn kill_tree(child: &mut Child) {
+    if let Some(pid) = child.id() {
+        kill_process_tree(pid);
+    }
+    let _ = child.start_kill();
+    let _ = child.wait().await;
+}
+
+/// 结束以 pid 为首的进程组（Windows 上为进程树）
+pub(crate) fn kill_process_tree(pid: u32) {
+    #[cfg(unix)]
+    {
+        // process_group(0) 使进程组 id 等于子进程 pid
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    #[cfg(windows)]
+    {
+        let _ = std::process::Command::new("taskkill")
+            .args(["/F", "/T", "/PID", &pid.to_string()])
+            .output();
+    }
+}
+
+/// 非阻塞地检查是否按下了 Esc
+fn escape_pressed() -> bool {
+    use crossterm::event::{poll, read, Event, KeyCode};
+
+    matches!(poll(Duration::ZERO), Ok(true))
+        && matches!(read(), Ok(Event::Key(key)) if key.code == KeyCode::Esc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_cap_keeps_head_and_tail() {
+        let mut cap = OutputCap::new(20, 20);
+        for i in 0..100 {
+            cap.push_line(&format!("line {}", i));
+        }
+        let output = cap.finish();
+        assert!(output.starts_with("line 0\nline 1\n"));
+        assert!(output.ends_with("line 98\nline 99\n"));
+        assert!(!output.contains("line 50\n"));
+        assert!(output.contains("96"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_streaming_timeout_kills_group() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg("echo start; sleep 30 & sleep 30; echo never");
+        let started = std::time::Instant::now();
+        let mut lines = Vec::new();
+        let result = run_streaming(cmd, None, Some(Duration::from_millis(300)), |line| {
+            lines.push(line.to_string())
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.end, RunEnd::TimedOut);
+        assert_eq!(lines, vec!["start"]);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("echo out; echo err >&2; exit 3");
+        let result = run_streaming(cmd, None, None, |_| {}).await.unwrap();
+        assert_eq!(result.end, RunEnd::Exited(Some(3)));
+        assert!(result.output.contains("out\n") && result.output.contains("err\n"));
+    }
+}
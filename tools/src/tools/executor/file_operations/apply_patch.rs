@@ -0,0 +1,296 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file_common::{
+    after_write, before_write, check_file_action_approval, resolve_path, Access,
+};
+use super::patch::{apply_hunks, parse_patch, FilePatch, HunkFailure};
+use crate::tools::args::ApplyPatchArgs;
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+/// 补丁应用后各文件的内容，None 表示删除；同一文件可被多个文件补丁依次修改
+#[derive(Default)]
+struct Planned {
+    files: Vec<(PathBuf, Option<String>)>,
+}
+
+impl Planned {
+    /// 文件当前（计划中）的内容，不存在时为 None
+    fn content(&self, path: &Path) -> std::io::Result<Option<String>> {
+        match self.files.iter().find(|(p, _)| p == path) {
+            Some((_, content)) => Ok(content.clone()),
+            None if path.exists() => fs::read_to_string(path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn set(&mut self, path: PathBuf, content: Option<String>) {
+        match self.files.iter_mut().find(|(p, _)| *p == path) {
+            Some(entry) => entry.1 = content,
+            None => self.files.push((path, content)),
+        }
+    }
+}
+
+pub async fn execute_apply_patch(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    let args: ApplyPatchArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let files = match parse_patch(&args.patch) {
+        Ok(files) if files.is_empty() => {
+            return Ok(ToolResult::error(i18n.get("apply_patch_empty")));
+        }
+        Ok(files) => files,
+        Err(e) => {
+            let tmpl = i18n.get("apply_patch_parse_error");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
+
+    // 先计算所有文件的修改，任何一处失败都不写入
+    let mut planned = Planned::default();
+    let mut problems = Vec::new();
+    let mut notes = Vec::new();
+    for file in &files {
+        if let Err(problem) = plan_file(file, working_dir, args.fuzz, &mut planned, &mut notes) {
+            problems.push(problem);
+        }
+    }
+
+    if !problems.is_empty() {
+        let mut failed = ToolResult::error(
+            i18n.get("apply_patch_failed_brief")
+                .replace("{}", &problems.len().to_string()),
+        );
+        failed.message = format!(
+            "{}\n\n{}",
+            i18n.get("apply_patch_failed"),
+            problems.join("\n\n")
+        );
+        return Ok(failed);
+    }
+
+    if require_approval {
+        // 提示中只显示路径的文件名部分，多个文件时显示文件数
+        let subject = match files.as_slice() {
+            [file] => file.path().to_string(),
+            _ => i18n
+                .get("apply_patch_subject")
+                .replace("{}", &files.len().to_string()),
+        };
+        if !check_file_action_approval("apply_patch", Path::new(&subject), Some(&args.patch))? {
+            return Ok(ToolResult::error(i18n.get("approval_rejected")));
+        }
+    }
+
+    for (path, content) in &planned.files {
+        before_write(path)?;
+        match content {
+            Some(content) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)?;
+            }
+            None if path.exists() => fs::remove_file(path)?,
+            None => {}
+        }
+        after_write(path)?;
+    }
+
+    let mut summary = Vec::new();
+    for file in &files {
+        let (added, removed) = file.line_counts();
+        let key = match (&file.old_path, &file.new_path) {
+            (None, _) => "apply_patch_created",
+            (_, None) => "apply_patch_deleted",
+            (Some(old), Some(new)) if old != new => "apply_patch_renamed",
+            _ => "apply_patch_modified",
+        };
+        summary.push(
+            i18n.get(key)
+                .replacen("{}", file.path(), 1)
+                .replacen("{}", &added.to_string(), 1)
+                .replacen("{}", &removed.to_string(), 1),
+        );
+    }
+
+    let brief = i18n
+        .get("apply_patch_brief")
+        .replace("{}", &files.len().to_string());
+    let mut output = format!("{}\n{}", i18n.get("apply_patch_header"), summary.join("\n"));
+    if !notes.is_empty() {
+        output.push_str("\n\n");
+        output.push_str(&notes.join("\n"));
+    }
+    Ok(ToolResult::ok(brief, output))
+}
+
+/// 把一个文件补丁应用到计划中的内容；失败时返回给模型看的说明
+fn plan_file(
+    file: &FilePatch,
+    working_dir: &Path,
+    fuzz: usize,
+    planned: &mut Planned,
+    notes: &mut Vec<String>,
+) -> std::result::Result<(), String> {
+    let i18n = get_i18n();
+    let display = file.path();
+    let resolve = |path: &str| {
+        resolve_path(path, working_dir, Access::Write).map_err(|rejected| rejected.message)
+    };
+    let read = |planned: &Planned, path: &Path| {
+        planned
+            .content(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    };
+
+    let Some(new_path) = &file.new_path else {
+        let path = resolve(display)?;
+        if read(planned, &path)?.is_none() {
+            return Err(i18n
+                .get("file_not_exist")
+                .replace("{}", &path.display().to_string()));
+        }
+        planned.set(path, None);
+        return Ok(());
+    };
+    let target = resolve(new_path)?;
+
+    let (source, original) = match &file.old_path {
+        None => {
+            if read(planned, &target)?.is_some() {
+                return Err(i18n
+                    .get("apply_patch_file_exists")
+                    .replace("{}", &target.display().to_string()));
+            }
+            (None, String::new())
+        }
+        Some(old_path) => {
+            let source = resolve(old_path)?;
+            match read(planned, &source)? {
+                Some(content) => (Some(source), content),
+                None => {
+                    return Err(i18n
+                        .get("file_not_exist")
+                        .replace("{}", &source.display().to_string()));
+                }
+            }
+        }
+    };
+
+    let patched = apply_hunks(&original, &file.hunks, fuzz)
+        .map_err(|failures| describe_failures(display, &failures))?;
+    for (hunk, line, offset, level) in patched.adjusted {
+        notes.push(
+            i18n.get("apply_patch_adjusted")
+                .replacen("{}", &hunk.to_string(), 1)
+                .replacen("{}", display, 1)
+                .replacen("{}", &line.to_string(), 1)
+                .replacen("{}", &format!("{:+}", offset), 1)
+                .replacen("{}", &level.to_string(), 1),
+        );
+    }
+
+    // 重命名：删除原文件
+    if let Some(source) = source.filter(|source| *source != target) {
+        planned.set(source, None);
+    }
+    planned.set(target, Some(patched.content));
+    Ok(())
+}
+
+fn describe_failures(path: &str, failures: &[HunkFailure]) -> String {
+    let i18n = get_i18n();
+    let mut parts = Vec::new();
+    for failure in failures {
+        let mut text = i18n
+            .get("apply_patch_hunk_failed")
+            .replacen("{}", &failure.index.to_string(), 1)
+            .replacen("{}", path, 1)
+            .replacen("{}", &failure.header, 1);
+        text.push('\n');
+        match &failure.closest {
+            Some(closest) => {
+                text.push_str(
+                    &i18n
+                        .get("apply_patch_closest")
+                        .replacen("{}", &closest.line.to_string(), 1)
+                        .replacen(
+                            "{}",
+                            &(closest.line + closest.lines.len() - 1).to_string(),
+                            1,
+                        )
+                        .replacen("{}", &closest.equal.to_string(), 1)
+                        .replacen("{}", &closest.total.to_string(), 1),
+                );
+                for (k, line) in closest.lines.iter().enumerate() {
+                    text.push_str(&format!("\n{:>6}\t{}", closest.line + k, line));
+                }
+            }
+            None => text.push_str(&i18n.get("apply_patch_no_match")),
+        }
+        parts.push(text);
+    }
+    parts.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_apply_patch_is_all_or_nothing() {
+        let dir = std::env::temp_dir().join(format!("friendev_apply_patch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        std::fs::write(dir.join("old.txt"), "bye\n").unwrap();
+
+        let patch = "\
+--- a/a.txt
++++ b/a.txt
+@@ -1,3 +1,3 @@
+ one
+-two
++2
+ three
+--- /dev/null
++++ b/new/b.txt
+@@ -0,0 +1 @@
++created
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+";
+        let bad = format!(
+            "{}--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-missing\n+x\n",
+            patch
+        );
+        let args = serde_json::json!({ "patch": bad }).to_string();
+        let result = execute_apply_patch(&args, &dir, false).await.unwrap();
+        assert!(!result.success);
+        assert!(result.message.contains("@@ -1 +1 @@"));
+        assert!(!dir.join("new/b.txt").exists());
+        assert!(dir.join("old.txt").exists());
+
+        let args = serde_json::json!({ "patch": patch }).to_string();
+        let result = execute_apply_patch(&args, &dir, false).await.unwrap();
+        assert!(result.success, "{}", result.message);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("a.txt")).unwrap(),
+            "one\n2\nthree\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("new/b.txt")).unwrap(),
+            "created\n"
+        );
+        assert!(!dir.join("old.txt").exists());
+    }
+}
@@ -0,0 +1,64 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+use super::file_common::{
+    after_write, before_write, check_file_action_approval, display_path, missing_dirs,
+    paths_preview, resolve_path, Access,
+};
+use crate::tools::args::DirCreateArgs;
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+pub async fn execute_dir_create(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    let args: DirCreateArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let target = match resolve_path(&args.path, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
+    if target.is_dir() {
+        let tmpl = i18n.get("dir_create_exists");
+        let message = tmpl.replace("{}", &target.display().to_string());
+        return Ok(ToolResult::ok(message.clone(), message));
+    }
+    if target.exists() {
+        let tmpl = i18n.get("file_not_directory");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &target.display().to_string()),
+        ));
+    }
+
+    let created = missing_dirs(&target);
+    if require_approval {
+        let lines: Vec<String> = created
+            .iter()
+            .map(|dir| format!("{}/", display_path(dir, working_dir)))
+            .collect();
+        let preview = paths_preview(&i18n.get("dir_create_preview"), &lines);
+        if !check_file_action_approval("dir_create", &target, Some(&preview))? {
+            return Ok(ToolResult::error(i18n.get("approval_rejected")));
+        }
+    }
+
+    for dir in &created {
+        before_write(dir)?;
+    }
+    fs::create_dir_all(&target)?;
+    for dir in &created {
+        after_write(dir)?;
+    }
+
+    let brief = i18n
+        .get("dir_create_brief")
+        .replace("{}", &display_path(&target, working_dir));
+    let message = i18n
+        .get("dir_create_done")
+        .replace("{}", &target.display().to_string());
+    Ok(ToolResult::ok(brief, message))
+}
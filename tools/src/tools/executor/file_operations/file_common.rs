@@ -1,8 +1,12 @@
 use anyhow::Result;
+use ignore::WalkBuilder;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::tools::args::SortOrder;
 use crate::types::ToolResult;
+use config::Config;
+use security::{expand_home, PathPolicy, PathVerdict, DEFAULT_SENSITIVE_PATTERNS};
 use ui::get_i18n;
 
 /// 规范化路径 - 处理相对路径和绝对路径
@@ -15,6 +19,204 @@ pub fn normalize_path(path_str: &str, working_dir: &Path) -> PathBuf {
     }
 }
 
+/// 访问路径的方式，用于敏感路径的确认提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+impl Access {
+    fn action_key(self) -> &'static str {
+        match self {
+            Access::Read => "path_access_read",
+            Access::Write => "path_access_write",
+        }
+    }
+}
+
+/// 当前会话的路径策略：工作目录与配置中的 allowed_roots；内置敏感模式、配置文件本身与配置中的 sensitive_paths
+pub fn path_policy(working_dir: &Path) -> PathPolicy {
+    let config = Config::load().ok().flatten();
+    let mut roots = vec![working_dir.to_path_buf()];
+    let mut patterns: Vec<String> = DEFAULT_SENSITIVE_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect();
+    if let Ok(config_path) = Config::config_path() {
+        patterns.push(config_path.to_string_lossy().into_owned());
+    }
+    if let Some(config) = &config {
+        roots.extend(config.allowed_roots.iter().map(|root| expand_home(root)));
+        patterns.extend(config.sensitive_paths.iter().cloned());
+    }
+    PathPolicy::new(roots.clone(), &patterns).unwrap_or_else(|e| {
+        // 配置中的模式无效时仍然使用内置模式
+        let i18n = get_i18n();
+        eprintln!(
+            "\x1b[33m[!] {}:\x1b[0m {}",
+            i18n.get("path_policy_invalid_pattern"),
+            e
+        );
+        PathPolicy::new(roots, DEFAULT_SENSITIVE_PATTERNS).expect("built-in patterns are valid")
+    })
+}
+
+/// 解析工具参数中的路径：必须位于允许的根目录内，敏感路径需要用户明确确认（自动审批模式下也一样）。
+/// 不允许时返回给模型的错误结果
+pub fn resolve_path(
+    path_str: &str,
+    working_dir: &Path,
+    access: Access,
+) -> std::result::Result<PathBuf, ToolResult> {
+    let path = normalize_path(path_str, working_dir);
+    check_paths(std::slice::from_ref(&path), working_dir, access)?;
+    Ok(path)
+}
+
+/// 检查一组路径（如目录删除或复制涉及的所有路径），敏感路径合并为一次确认
+pub fn check_paths(
+    paths: &[PathBuf],
+    working_dir: &Path,
+    access: Access,
+) -> std::result::Result<(), ToolResult> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+    let i18n = get_i18n();
+    let policy = path_policy(working_dir);
+    let mut sensitive = Vec::new();
+    for path in paths {
+        match policy.check(path) {
+            PathVerdict::Allowed => {}
+            PathVerdict::Sensitive(pattern) => {
+                sensitive.push((display_path(path, working_dir), pattern));
+            }
+            PathVerdict::Outside => {
+                let roots: Vec<String> = policy
+                    .roots()
+                    .iter()
+                    .map(|root| root.display().to_string())
+                    .collect();
+                return Err(ToolResult::error(
+                    i18n.get("path_outside_workspace")
+                        .replacen("{}", &path.display().to_string(), 1)
+                        .replacen("{}", &roots.join(", "), 1),
+                ));
+            }
+        }
+    }
+    if sensitive.is_empty() {
+        return Ok(());
+    }
+
+    match ui::confirm_sensitive_access(&i18n.get(access.action_key()), &sensitive) {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            let paths: Vec<&str> = sensitive.iter().map(|(path, _)| path.as_str()).collect();
+            Err(ToolResult::error(
+                i18n.get("path_sensitive_rejected")
+                    .replace("{}", &paths.join(", ")),
+            ))
+        }
+        Err(e) => Err(ToolResult::error(e.to_string())),
+    }
+}
+
+/// 检测二进制文件时检查的前缀字节数
+pub const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// 内容开头含 NUL 字节即视为二进制
+pub fn looks_binary(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// 遵循 .gitignore 的目录遍历器（不要求位于 git 仓库中），hidden 为 true 时包含隐藏文件
+pub fn project_walker(root: &Path, hidden: bool, sort: SortOrder) -> WalkBuilder {
+    let mut walker = WalkBuilder::new(root);
+    walker
+        .require_git(false)
+        .hidden(!hidden)
+        .filter_entry(|entry| entry.file_name() != ".git");
+    match sort {
+        SortOrder::Path => {
+            walker.sort_by_file_path(|a, b| a.cmp(b));
+        }
+        SortOrder::Mtime => {
+            // 最近修改的在前
+            walker.sort_by_file_path(|a, b| modified(b).cmp(&modified(a)).then(a.cmp(b)));
+        }
+    }
+    walker
+}
+
+/// 文件修改时间，读取失败时为 None
+pub fn modified(path: &Path) -> Option<std::time::SystemTime> {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// 相对于工作目录显示的路径
+pub fn display_path(path: &Path, working_dir: &Path) -> String {
+    path.strip_prefix(working_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// 审批预览中最多列出的路径数
+const MAX_PREVIEW_PATHS: usize = 50;
+
+/// 路径本身及其下的所有文件和目录（不跟随符号链接），目录排在其内容之前
+pub fn tree_paths(path: &Path) -> Vec<PathBuf> {
+    WalkBuilder::new(path)
+        .standard_filters(false)
+        .sort_by_file_path(|a, b| a.cmp(b))
+        .build()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// path 及其尚不存在的上级目录，从外到内
+pub fn missing_dirs(path: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = path
+        .ancestors()
+        .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    dirs.reverse();
+    dirs
+}
+
+/// 审批预览：每行一个受影响的路径，过多时省略
+pub fn paths_preview(title: &str, lines: &[String]) -> String {
+    let mut preview = format!("{}\n", title);
+    for line in lines.iter().take(MAX_PREVIEW_PATHS) {
+        preview.push_str(&format!("  {}\n", line));
+    }
+    if lines.len() > MAX_PREVIEW_PATHS {
+        let tmpl = get_i18n().get("file_paths_more");
+        preview.push_str(&format!(
+            "  {}\n",
+            tmpl.replace("{}", &(lines.len() - MAX_PREVIEW_PATHS).to_string())
+        ));
+    }
+    preview
+}
+
+/// 工具修改文件前调用：把原内容保存到本轮的检查点
+pub fn before_write(path: &Path) -> Result<()> {
+    crate::tools::checkpoint::before_write(path)
+}
+
+/// 工具修改文件后调用：更新读取记录与检查点
+pub fn after_write(path: &Path) -> Result<()> {
+    super::read_tracker::record_file(path);
+    crate::tools::checkpoint::after_write(path)
+}
+
 /// 验证文件存在
 #[allow(dead_code)]
 pub fn verify_file_exists(path: &Path) -> Result<ToolResult> {
@@ -66,11 +268,8 @@ pub fn check_file_action_approval(
         return Ok(true);
     }
 
-    let (approved, always, view_details) = prompt_approval(
-        action,
-        &path.display().to_string(),
-        preview,
-    )?;
+    let (approved, always, view_details) =
+        prompt_approval(action, &path.display().to_string(), preview)?;
 
     if view_details {
         let continue_op = ui::show_detailed_content(
@@ -0,0 +1,155 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file_common::{
+    after_write, before_write, check_file_action_approval, check_paths, display_path, missing_dirs,
+    paths_preview, resolve_path, tree_paths, Access,
+};
+use crate::tools::args::FileCopyArgs;
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+pub async fn execute_file_copy(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    let args: FileCopyArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let from = match resolve_path(&args.from, working_dir, Access::Read) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
+    let to = match resolve_path(&args.to, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
+    if let Some(problem) = check_transfer(&from, &to, args.overwrite) {
+        return Ok(ToolResult::error(problem));
+    }
+    if from.is_dir() && !args.recursive {
+        let tmpl = i18n.get("file_copy_dir_needs_recursive");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &from.display().to_string()),
+        ));
+    }
+
+    let pairs = transfer_pairs(&from, &to);
+    if let Err(rejected) = check_contents(&pairs, working_dir, Access::Read) {
+        return Ok(rejected);
+    }
+    if require_approval {
+        let lines = pair_lines(&pairs, working_dir);
+        let preview = paths_preview(&i18n.get("file_copy_preview"), &lines);
+        let subject = format!("{} -> {}", args.from, args.to);
+        if !check_file_action_approval("file_copy", Path::new(&subject), Some(&preview))? {
+            return Ok(ToolResult::error(i18n.get("approval_rejected")));
+        }
+    }
+
+    let created = to.parent().map(missing_dirs).unwrap_or_default();
+    let touched: Vec<&PathBuf> = created.iter().chain(pairs.iter().map(|(_, t)| t)).collect();
+    for path in &touched {
+        before_write(path)?;
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    copy_pairs(&pairs)?;
+    for path in &touched {
+        after_write(path)?;
+    }
+
+    let files = pairs.iter().filter(|(source, _)| source.is_file()).count();
+    let brief = i18n
+        .get("file_copy_brief")
+        .replace("{}", &files.to_string());
+    let message = i18n
+        .get("file_copy_done")
+        .replacen("{}", &display_path(&from, working_dir), 1)
+        .replacen("{}", &display_path(&to, working_dir), 1)
+        .replacen("{}", &files.to_string(), 1);
+    Ok(ToolResult::ok(brief, message))
+}
+
+/// 移动或复制前的检查，有问题时返回说明
+pub(super) fn check_transfer(from: &Path, to: &Path, overwrite: bool) -> Option<String> {
+    let i18n = get_i18n();
+    if !from.exists() {
+        let tmpl = i18n.get("file_path_not_exist");
+        return Some(tmpl.replace("{}", &from.display().to_string()));
+    }
+    // 比较实际路径：`x/../old/inner` 这样的写法也不能绕过
+    if security::resolve_path(to).starts_with(security::resolve_path(from)) {
+        let tmpl = i18n.get("file_transfer_into_itself");
+        return Some(tmpl.replace("{}", &to.display().to_string()));
+    }
+    if to.exists() {
+        // 只允许覆盖单个文件，不会合并或替换目录
+        let key = if !overwrite {
+            "file_transfer_target_exists"
+        } else if to.is_dir() || from.is_dir() {
+            "file_transfer_target_is_dir"
+        } else {
+            return None;
+        };
+        return Some(i18n.get(key).replace("{}", &to.display().to_string()));
+    }
+    None
+}
+
+/// 源路径下每个文件和目录及其对应的目标路径，目录在其内容之前
+pub(super) fn transfer_pairs(from: &Path, to: &Path) -> Vec<(PathBuf, PathBuf)> {
+    tree_paths(from)
+        .into_iter()
+        .map(|source| {
+            let target = match source.strip_prefix(from) {
+                Ok(rest) if !rest.as_os_str().is_empty() => to.join(rest),
+                _ => to.to_path_buf(),
+            };
+            (source, target)
+        })
+        .collect()
+}
+
+/// 目录内的路径同样受工作区与敏感路径限制（目录本身已检查）
+pub(super) fn check_contents(
+    pairs: &[(PathBuf, PathBuf)],
+    working_dir: &Path,
+    source_access: Access,
+) -> std::result::Result<(), ToolResult> {
+    let inner = pairs.get(1..).unwrap_or_default();
+    let sources: Vec<PathBuf> = inner.iter().map(|(source, _)| source.clone()).collect();
+    let targets: Vec<PathBuf> = inner.iter().map(|(_, target)| target.clone()).collect();
+    check_paths(&sources, working_dir, source_access)?;
+    check_paths(&targets, working_dir, Access::Write)
+}
+
+/// 审批预览中的 `源 -> 目标` 行，只列出文件
+pub(super) fn pair_lines(pairs: &[(PathBuf, PathBuf)], working_dir: &Path) -> Vec<String> {
+    pairs
+        .iter()
+        .filter(|(source, _)| !source.is_dir())
+        .map(|(source, target)| {
+            format!(
+                "{} -> {}",
+                display_path(source, working_dir),
+                display_path(target, working_dir)
+            )
+        })
+        .collect()
+}
+
+/// 按 transfer_pairs 的顺序复制
+pub(super) fn copy_pairs(pairs: &[(PathBuf, PathBuf)]) -> Result<()> {
+    for (source, target) in pairs {
+        if source.is_dir() {
+            fs::create_dir_all(target)?;
+        } else {
+            fs::copy(source, target)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+use super::file_common::{
+    after_write, before_write, check_file_action_approval, check_paths, display_path,
+    paths_preview, resolve_path, tree_paths, Access,
+};
+use crate::tools::args::FileDeleteArgs;
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+pub async fn execute_file_delete(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    let args: FileDeleteArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let target = match resolve_path(&args.path, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
+    if !target.exists() {
+        let tmpl = i18n.get("file_path_not_exist");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &target.display().to_string()),
+        ));
+    }
+    // 不允许删除工作目录本身或其上级目录
+    let canonical = target.canonicalize()?;
+    let working_dir_canonical = working_dir
+        .canonicalize()
+        .unwrap_or_else(|_| working_dir.to_path_buf());
+    if working_dir_canonical.starts_with(&canonical) {
+        let tmpl = i18n.get("file_delete_working_dir");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &target.display().to_string()),
+        ));
+    }
+
+    let paths = tree_paths(&target);
+    if paths.len() > 1 && !args.recursive {
+        let tmpl = i18n.get("file_delete_needs_recursive");
+        return Ok(ToolResult::error(
+            tmpl.replacen("{}", &target.display().to_string(), 1)
+                .replacen("{}", &(paths.len() - 1).to_string(), 1),
+        ));
+    }
+    if let Err(rejected) = check_paths(&paths[1..], working_dir, Access::Write) {
+        return Ok(rejected);
+    }
+
+    if require_approval {
+        let lines: Vec<String> = paths
+            .iter()
+            .map(|path| {
+                let mut line = display_path(path, working_dir);
+                if path.is_dir() {
+                    line.push('/');
+                }
+                line
+            })
+            .collect();
+        let preview = paths_preview(&i18n.get("file_delete_preview"), &lines);
+        if !check_file_action_approval("file_delete", &target, Some(&preview))? {
+            return Ok(ToolResult::error(i18n.get("approval_rejected")));
+        }
+    }
+
+    let files = paths.iter().filter(|path| !path.is_dir()).count();
+    for path in &paths {
+        before_write(path)?;
+    }
+    if target.is_dir() {
+        fs::remove_dir_all(&target)?;
+    } else {
+        fs::remove_file(&target)?;
+    }
+    for path in &paths {
+        after_write(path)?;
+    }
+
+    let brief = i18n
+        .get("file_delete_brief")
+        .replace("{}", &display_path(&target, working_dir));
+    let message = i18n
+        .get("file_delete_done")
+        .replacen("{}", &target.display().to_string(), 1)
+        .replacen("{}", &files.to_string(), 1);
+    Ok(ToolResult::ok(brief, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_recursive_delete_needs_flag() {
+        let dir = std::env::temp_dir().join(format!("friendev_file_delete_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("dead/sub")).unwrap();
+        fs::write(dir.join("dead/sub/a.rs"), "a").unwrap();
+        fs::create_dir_all(dir.join("empty")).unwrap();
+
+        let args = serde_json::json!({ "path": "empty/.." }).to_string();
+        assert!(
+            !execute_file_delete(&args, &dir, false)
+                .await
+                .unwrap()
+                .success
+        );
+
+        let args = serde_json::json!({ "path": "dead" }).to_string();
+        let result = execute_file_delete(&args, &dir, false).await.unwrap();
+        assert!(!result.success);
+        assert!(dir.join("dead/sub/a.rs").exists());
+
+        let args = serde_json::json!({ "path": "empty" }).to_string();
+        assert!(
+            execute_file_delete(&args, &dir, false)
+                .await
+                .unwrap()
+                .success
+        );
+
+        let args = serde_json::json!({ "path": "dead", "recursive": true }).to_string();
+        assert!(
+            execute_file_delete(&args, &dir, false)
+                .await
+                .unwrap()
+                .success
+        );
+        assert!(!dir.join("dead").exists());
+    }
+}
@@ -2,9 +2,14 @@ use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
-use super::file_common::normalize_path;
-use crate::tools::args::FileDiffEditArgs;
+use super::file_common::{after_write, before_write, resolve_path, Access};
+use super::read_tracker;
+use crate::tools::args::{DiffHunk, FileDiffEditArgs};
 use crate::types::ToolResult;
+use ui::get_i18n;
+
+/// 不一致时展示的被替换区域前后的行数
+const STALE_CONTEXT_LINES: usize = 2;
 
 pub async fn execute_file_diff_edit(
     arguments: &str,
@@ -13,7 +18,10 @@ pub async fn execute_file_diff_edit(
 ) -> Result<ToolResult> {
     let args: FileDiffEditArgs = serde_json::from_str(arguments)?;
 
-    let target_path = normalize_path(&args.path, working_dir);
+    let target_path = match resolve_path(&args.path, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
 
     // 验证文件存在
     if !target_path.exists() {
@@ -38,9 +46,34 @@ pub async fn execute_file_diff_edit(
     // 检测换行符风格
     let uses_crlf = content.contains("\r\n");
 
+    // 校验 expected_old，避免按过期的行号覆盖错误的内容
+    let original_lines: Vec<&str> = original_content.lines().collect();
+    let stale: Vec<String> = args
+        .hunks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, hunk)| check_expected_old(&original_lines, i + 1, hunk))
+        .collect();
+    if !stale.is_empty() {
+        let i18n = get_i18n();
+        let mut rejected = ToolResult::error(
+            i18n.get("file_diff_edit_stale_brief")
+                .replace("{}", &stale.len().to_string()),
+        );
+        rejected.message = format!(
+            "{}\n\n{}",
+            i18n.get("file_diff_edit_stale")
+                .replace("{}", &target_path.display().to_string()),
+            stale.join("\n\n")
+        );
+        return Ok(rejected);
+    }
+    let changed_since_read =
+        read_tracker::changed_since_read(&target_path, original_content.as_bytes());
+
     // 应用所有 hunk（从后到前，避免行号偏移）
     let mut hunks = args.hunks.clone();
-    hunks.sort_by(|a, b| b.start_line.cmp(&a.start_line));
+    hunks.sort_by_key(|h| std::cmp::Reverse(h.start_line));
 
     // 记录所有修改的行范围，用于后续的验证输出
     let mut modified_ranges = Vec::new();
@@ -93,7 +126,9 @@ pub async fn execute_file_diff_edit(
         new_content
     };
 
+    before_write(&target_path)?;
     fs::write(&target_path, &final_content)?;
+    after_write(&target_path)?;
 
     // 核心：直接从内容生成上下文，不再重新读取文件
     let actual_lines: Vec<&str> = final_content.lines().collect();
@@ -102,12 +137,16 @@ pub async fn execute_file_diff_edit(
     let diff_merge_result = generate_diff_result(&actual_lines, &modified_ranges);
 
     let brief = format!("应用了 {} 个 hunk", args.hunks.len());
-    let output = format!(
+    let mut output = format!(
         "文件已更新: {}\n应用了 {} 个 diff hunk\n\n{}",
         target_path.display(),
         args.hunks.len(),
         diff_merge_result
     );
+    if changed_since_read {
+        let warning = get_i18n().get("file_diff_edit_changed_since_read");
+        output = format!("{}\n\n{}", warning, output);
+    }
 
     let verification_prompt = "Please verify the DIFF merge result above. Check if all modifications are correct and there are no syntax errors (e.g., unclosed brackets, misaligned indentation). If everything looks good, you may continue. If there are any issues, describe the problem clearly.";
 
@@ -117,30 +156,59 @@ pub async fn execute_file_diff_edit(
         message: output,
         verification_required: true,
         verification_message: Some(verification_prompt.to_string()),
+        images: Vec::new(),
     })
 }
 
-fn generate_preview(args: &FileDiffEditArgs) -> String {
-    let preview = args
-        .hunks
-        .iter()
-        .take(3)
-        .map(|h| {
-            format!(
-                "- Line {}: {} lines → {} chars",
-                h.start_line,
-                h.num_lines,
-                h.new_content.chars().count()
-            )
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    if args.hunks.len() > 3 {
-        format!("{}\n... and {} more hunks", preview, args.hunks.len() - 3)
-    } else {
-        preview
+/// hunk 的 expected_old 与文件当前内容不一致时，返回包含实际内容的说明
+fn check_expected_old(lines: &[&str], index: usize, hunk: &DiffHunk) -> Option<String> {
+    let expected = hunk.expected_old.as_deref()?;
+    let start_idx = hunk.start_line.checked_sub(1)?;
+    let end_idx = (start_idx + hunk.num_lines).min(lines.len());
+    let actual = lines.get(start_idx..end_idx).unwrap_or_default();
+
+    let expected: Vec<&str> = expected.lines().collect();
+    let same = |a: &[&str], b: &[&str]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.trim_end() == y.trim_end())
+    };
+    if same(actual, &expected) {
+        return None;
+    }
+
+    let i18n = get_i18n();
+    let mut text = i18n
+        .get("file_diff_edit_stale_hunk")
+        .replacen("{}", &index.to_string(), 1)
+        .replacen("{}", &hunk.start_line.to_string(), 1)
+        .replacen("{}", &(start_idx + hunk.num_lines).to_string(), 1);
+    let from = start_idx
+        .saturating_sub(STALE_CONTEXT_LINES)
+        .min(lines.len());
+    let to = (end_idx + STALE_CONTEXT_LINES).min(lines.len());
+    for (k, line) in lines[from..to].iter().enumerate() {
+        let line_no = from + k + 1;
+        let marker = if (start_idx..end_idx).contains(&(line_no - 1)) {
+            '>'
+        } else {
+            ' '
+        };
+        text.push_str(&format!("\n{}{:>5}\t{}", marker, line_no, line));
     }
+
+    // expected_old 若出现在别处，多半是行号过期
+    if !expected.is_empty() && lines.len() >= expected.len() {
+        if let Some(pos) = (0..=lines.len() - expected.len())
+            .find(|&pos| same(&lines[pos..pos + expected.len()], &expected))
+        {
+            text.push('\n');
+            text.push_str(
+                &i18n
+                    .get("file_diff_edit_stale_found")
+                    .replace("{}", &(pos + 1).to_string()),
+            );
+        }
+    }
+    Some(text)
 }
 
 fn generate_detailed_changes(file_content: &str, args: &FileDiffEditArgs) -> String {
@@ -149,13 +217,13 @@ fn generate_detailed_changes(file_content: &str, args: &FileDiffEditArgs) -> Str
 
     for (i, hunk) in args.hunks.iter().enumerate() {
         detailed_changes.push_str(&format!("@@ Hunk #{} @@\n", i + 1));
-        
+
         let start_line = hunk.start_line;
         let num_lines = hunk.num_lines;
         let original_start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-        
+
         // Context before (3 lines)
-        let context_start = if original_start_idx >= 3 { original_start_idx - 3 } else { 0 };
+        let context_start = original_start_idx.saturating_sub(3);
         for idx in context_start..original_start_idx {
             if idx < lines.len() {
                 detailed_changes.push_str(&format!(" {}\n", lines[idx]));
@@ -174,7 +242,7 @@ fn generate_detailed_changes(file_content: &str, args: &FileDiffEditArgs) -> Str
         for line in hunk.new_content.lines() {
             detailed_changes.push_str(&format!("+{}\n", line));
         }
-        
+
         // Context after (3 lines)
         let end_idx = original_start_idx + num_lines;
         for idx in end_idx..(end_idx + 3) {
@@ -182,8 +250,8 @@ fn generate_detailed_changes(file_content: &str, args: &FileDiffEditArgs) -> Str
                 detailed_changes.push_str(&format!(" {}\n", lines[idx]));
             }
         }
-        
-        detailed_changes.push_str("\n");
+
+        detailed_changes.push('\n');
     }
 
     detailed_changes
@@ -196,7 +264,7 @@ fn generate_diff_result(actual_lines: &[&str], modified_ranges: &[(usize, usize)
     // 合并所有修改范围，避免重复
     let mut all_context_ranges = Vec::new();
     for (mod_start, mod_end) in modified_ranges.iter() {
-        let context_start = if *mod_start >= 3 { *mod_start - 3 } else { 0 };
+        let context_start = (*mod_start).saturating_sub(3);
         let context_end = std::cmp::min(*mod_end + 3, actual_lines.len());
         all_context_ranges.push((context_start, context_end));
     }
@@ -232,3 +300,33 @@ fn generate_diff_result(actual_lines: &[&str], modified_ranges: &[(usize, usize)
 
     diff_merge_result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(start_line: usize, num_lines: usize, expected_old: &str) -> DiffHunk {
+        DiffHunk {
+            start_line,
+            num_lines,
+            new_content: String::new(),
+            expected_old: Some(expected_old.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_check_expected_old() {
+        let lines = ["fn a() {}", "", "fn b() {", "    1", "}"];
+        assert!(check_expected_old(&lines, 1, &hunk(3, 3, "fn b() {\n    1  \n}")).is_none());
+
+        // 行号过期：expected_old 实际在第 3 行
+        let stale = check_expected_old(&lines, 2, &hunk(1, 1, "fn b() {")).unwrap();
+        assert!(stale.contains(">    1\tfn a() {}"));
+        assert!(stale.contains("    3\tfn b() {"));
+        assert!(stale.ends_with(
+            &get_i18n()
+                .get("file_diff_edit_stale_found")
+                .replace("{}", "3")
+        ));
+    }
+}
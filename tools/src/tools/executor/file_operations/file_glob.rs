@@ -0,0 +1,177 @@
+use anyhow::Result;
+use globset::{GlobBuilder, GlobMatcher};
+use std::path::Path;
+
+use super::file_common::{display_path, modified, project_walker, resolve_path, Access};
+use crate::tools::args::{FileGlobArgs, SortOrder};
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+/// max_results 的上限
+const MAX_RESULTS_LIMIT: usize = 2000;
+
+pub async fn execute_file_glob(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    let args: FileGlobArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let root = match &args.path {
+        Some(path) if !path.is_empty() => match resolve_path(path, working_dir, Access::Read) {
+            Ok(path) => path,
+            Err(rejected) => return Ok(rejected),
+        },
+        _ => working_dir.to_path_buf(),
+    };
+    if !root.is_dir() {
+        let tmpl = i18n.get("file_not_directory");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &root.display().to_string()),
+        ));
+    }
+
+    let matcher = match glob_matcher(&args.pattern) {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            let tmpl = i18n.get("file_search_bad_glob");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
+
+    let max_results = args.max_results.clamp(1, MAX_RESULTS_LIMIT);
+    let working_dir = working_dir.to_path_buf();
+    let (paths, total) = tokio::task::spawn_blocking(move || {
+        collect_matches(&root, &working_dir, &matcher, &args, max_results)
+    })
+    .await?;
+
+    if total == 0 {
+        return Ok(ToolResult::ok(
+            i18n.get("file_glob_none"),
+            i18n.get("file_glob_none"),
+        ));
+    }
+
+    let brief = i18n
+        .get("file_glob_brief")
+        .replace("{}", &total.to_string());
+    let mut output = paths.join("\n");
+    if total > paths.len() {
+        output.push_str("\n\n");
+        output.push_str(
+            &i18n
+                .get("file_glob_truncated")
+                .replacen("{}", &paths.len().to_string(), 1)
+                .replacen("{}", &total.to_string(), 1),
+        );
+    }
+    Ok(ToolResult::ok(brief, output))
+}
+
+/// 不含 `/` 的模式（如 `*.rs`）匹配任意深度的文件名
+fn glob_matcher(pattern: &str) -> Result<GlobMatcher> {
+    let pattern = pattern.trim_start_matches("./");
+    let pattern = if pattern.contains('/') {
+        pattern.to_string()
+    } else {
+        format!("**/{}", pattern)
+    };
+    Ok(GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()?
+        .compile_matcher())
+}
+
+/// 返回前 max_results 个匹配路径（目录以 `/` 结尾）以及匹配总数
+fn collect_matches(
+    root: &Path,
+    working_dir: &Path,
+    matcher: &GlobMatcher,
+    args: &FileGlobArgs,
+    max_results: usize,
+) -> (Vec<String>, usize) {
+    let mut matches = Vec::new();
+    for entry in project_walker(root, args.hidden, SortOrder::Path)
+        .build()
+        .flatten()
+    {
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() || !matcher.is_match(relative) {
+            continue;
+        }
+        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+        matches.push((entry.into_path(), is_dir));
+    }
+
+    if args.sort == SortOrder::Mtime {
+        matches.sort_by_cached_key(|(path, _)| std::cmp::Reverse(modified(path)));
+    }
+
+    let total = matches.len();
+    let paths = matches
+        .into_iter()
+        .take(max_results)
+        .map(|(path, is_dir)| {
+            let mut shown = display_path(&path, working_dir);
+            if is_dir {
+                shown.push('/');
+            }
+            shown
+        })
+        .collect();
+    (paths, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_glob_patterns_and_cap() {
+        let dir = std::env::temp_dir().join(format!("friendev_file_glob_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src/tools")).unwrap();
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        for file in [
+            "src/main.rs",
+            "src/tools/mod.rs",
+            "target/gen.rs",
+            "README.md",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        let run = |args: serde_json::Value| {
+            let dir = dir.clone();
+            async move {
+                execute_file_glob(&args.to_string(), &dir)
+                    .await
+                    .unwrap()
+                    .message
+            }
+        };
+
+        let nested = Path::new("src").join("tools").join("mod.rs");
+        let main = Path::new("src").join("main.rs");
+        assert_eq!(
+            run(serde_json::json!({ "pattern": "src/**/*.rs" })).await,
+            format!("{}\n{}", main.display(), nested.display())
+        );
+        // 不含 `/` 的模式匹配任意深度，且跳过 .gitignore 中的文件
+        assert_eq!(
+            run(serde_json::json!({ "pattern": "*.rs", "path": "src/tools" })).await,
+            nested.display().to_string()
+        );
+        assert_eq!(
+            run(serde_json::json!({ "pattern": "src/*" })).await,
+            format!(
+                "{}\n{}/",
+                main.display(),
+                Path::new("src").join("tools").display()
+            )
+        );
+
+        let capped = run(serde_json::json!({ "pattern": "*.rs", "max_results": 1 })).await;
+        assert!(capped.starts_with(&format!("{}\n\n", main.display())));
+        assert!(capped.contains('2'));
+    }
+}
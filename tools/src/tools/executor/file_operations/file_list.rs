@@ -2,7 +2,7 @@ use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
-use super::file_common::normalize_path;
+use super::file_common::{resolve_path, Access};
 use crate::tools::args::FileListArgs;
 use crate::types::ToolResult;
 use ui::get_i18n;
@@ -13,7 +13,10 @@ pub async fn execute_file_list(arguments: &str, working_dir: &Path) -> Result<To
     let i18n = get_i18n();
 
     let target_path = if let Some(path) = args.path {
-        normalize_path(&path, working_dir)
+        match resolve_path(&path, working_dir, Access::Read) {
+            Ok(path) => path,
+            Err(rejected) => return Ok(rejected),
+        }
     } else {
         working_dir.to_path_buf()
     };
@@ -32,9 +35,12 @@ pub async fn execute_file_list(arguments: &str, working_dir: &Path) -> Result<To
         ));
     }
 
+    // 按文件名排序，而不是按本地化后的输出行排序
+    let mut entries = fs::read_dir(&target_path)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
     let mut items = Vec::new();
-    for entry in fs::read_dir(&target_path)? {
-        let entry = entry?;
+    for entry in entries {
         let path = entry.path();
         let name = entry.file_name().to_string_lossy().to_string();
         let item_type = if path.is_dir() {
@@ -58,8 +64,6 @@ pub async fn execute_file_list(arguments: &str, working_dir: &Path) -> Result<To
         items.push(line);
     }
 
-    items.sort();
-
     let brief = if items.is_empty() {
         i18n.get("file_list_empty")
     } else {
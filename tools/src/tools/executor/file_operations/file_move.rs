@@ -0,0 +1,139 @@
+use anyhow::Result;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use super::file_common::{
+    after_write, before_write, check_file_action_approval, display_path, missing_dirs,
+    paths_preview, resolve_path, Access,
+};
+use super::file_copy::{check_contents, check_transfer, copy_pairs, pair_lines, transfer_pairs};
+use crate::tools::args::FileMoveArgs;
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+pub async fn execute_file_move(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    let args: FileMoveArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let from = match resolve_path(&args.from, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
+    let to = match resolve_path(&args.to, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
+    if let Some(problem) = check_transfer(&from, &to, args.overwrite) {
+        return Ok(ToolResult::error(problem));
+    }
+
+    let pairs = transfer_pairs(&from, &to);
+    if let Err(rejected) = check_contents(&pairs, working_dir, Access::Write) {
+        return Ok(rejected);
+    }
+    if require_approval {
+        let lines = pair_lines(&pairs, working_dir);
+        let preview = paths_preview(&i18n.get("file_move_preview"), &lines);
+        let subject = format!("{} -> {}", args.from, args.to);
+        if !check_file_action_approval("file_move", Path::new(&subject), Some(&preview))? {
+            return Ok(ToolResult::error(i18n.get("approval_rejected")));
+        }
+    }
+
+    // 源路径与目标路径都记入检查点，撤销时两边一起恢复
+    let created = to.parent().map(missing_dirs).unwrap_or_default();
+    let touched: Vec<&PathBuf> = pairs
+        .iter()
+        .flat_map(|(source, target)| [source, target])
+        .chain(&created)
+        .collect();
+    for path in &touched {
+        before_write(path)?;
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match fs::rename(&from, &to) {
+        Ok(()) => {}
+        // 跨文件系统时无法重命名：复制后删除
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+            copy_pairs(&pairs)?;
+            if from.is_dir() {
+                fs::remove_dir_all(&from)?;
+            } else {
+                fs::remove_file(&from)?;
+            }
+        }
+        Err(e) => {
+            let tmpl = i18n.get("file_move_failed");
+            return Ok(ToolResult::error(
+                tmpl.replacen("{}", &display_path(&from, working_dir), 1)
+                    .replacen("{}", &e.to_string(), 1),
+            ));
+        }
+    }
+    for path in &touched {
+        after_write(path)?;
+    }
+
+    let files = pairs.iter().filter(|(_, target)| target.is_file()).count();
+    let brief = i18n
+        .get("file_move_brief")
+        .replace("{}", &files.to_string());
+    let message = i18n
+        .get("file_move_done")
+        .replacen("{}", &display_path(&from, working_dir), 1)
+        .replacen("{}", &display_path(&to, working_dir), 1)
+        .replacen("{}", &files.to_string(), 1);
+    Ok(ToolResult::ok(brief, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_move_directory() {
+        let dir = std::env::temp_dir().join(format!("friendev_file_move_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("old/sub")).unwrap();
+        fs::write(dir.join("old/a.rs"), "a").unwrap();
+        fs::write(dir.join("old/sub/b.rs"), "b").unwrap();
+        fs::write(dir.join("taken.rs"), "taken").unwrap();
+
+        let args = serde_json::json!({ "from": "old/a.rs", "to": "taken.rs" }).to_string();
+        let result = execute_file_move(&args, &dir, false).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(fs::read_to_string(dir.join("taken.rs")).unwrap(), "taken");
+
+        let args = serde_json::json!({ "from": "old", "to": "old/inner" }).to_string();
+        assert!(!execute_file_move(&args, &dir, false).await.unwrap().success);
+
+        // 绕道 `..` 的目标同样位于源目录内，源目录必须原样保留
+        fs::create_dir_all(dir.join("x")).unwrap();
+        for to in [
+            "x/../old/inner",
+            "missing/../old/inner",
+            "./old/../old/inner",
+        ] {
+            let args = serde_json::json!({ "from": "old", "to": to }).to_string();
+            let result = execute_file_move(&args, &dir, false).await.unwrap();
+            assert!(!result.success, "{}", to);
+            assert_eq!(fs::read_to_string(dir.join("old/sub/b.rs")).unwrap(), "b");
+        }
+
+        let args = serde_json::json!({ "from": "old", "to": "new/mod" }).to_string();
+        let result = execute_file_move(&args, &dir, false).await.unwrap();
+        assert!(result.success, "{}", result.message);
+        assert!(!dir.join("old").exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("new/mod/sub/b.rs")).unwrap(),
+            "b"
+        );
+    }
+}
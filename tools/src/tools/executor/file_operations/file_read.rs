@@ -1,16 +1,45 @@
 use anyhow::Result;
-use std::fs;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 
-use super::file_common::normalize_path;
+use super::file_common::{looks_binary, resolve_path, Access, BINARY_SNIFF_BYTES};
+use super::read_tracker;
 use crate::tools::args::FileReadArgs;
+use crate::tools::utils::{format_size, ContentHasher};
 use crate::types::ToolResult;
 use ui::get_i18n;
 
+/// 单次读取返回的最大字节数
+const MAX_READ_BYTES: usize = 100 * 1024;
+
+/// 未指定 limit 时单次读取返回的最大行数
+const MAX_READ_LINES: usize = 2000;
+
+/// 读取的行范围
+struct Selection {
+    text: String,
+    /// 第一行与最后一行（从 1 开始，未选中任何行时 last < first）
+    first: usize,
+    last: usize,
+    total_lines: usize,
+    /// 是否因大小上限被截断
+    capped: bool,
+    /// 第一行超过大小上限，只保留了开头部分
+    first_line_cut: bool,
+    /// 是否含有非 UTF-8 字节
+    lossy: bool,
+    /// 整个文件的内容哈希
+    hash: u64,
+}
+
 pub async fn execute_file_read(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
     let args: FileReadArgs = serde_json::from_str(arguments)?;
 
-    let target_path = normalize_path(&args.path, working_dir);
+    let target_path = match resolve_path(&args.path, working_dir, Access::Read) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
     let i18n = get_i18n();
 
     if !target_path.exists() {
@@ -27,19 +56,245 @@ pub async fn execute_file_read(arguments: &str, working_dir: &Path) -> Result<To
         ));
     }
 
-    let content = fs::read_to_string(&target_path)?;
-    let lines = content.lines().count();
-    let bytes = content.len();
+    let size = target_path.metadata()?.len();
+    if is_binary(&target_path)? {
+        let brief = i18n
+            .get("file_read_binary_brief")
+            .replace("{}", &format_size(size));
+        let output = i18n
+            .get("file_read_binary")
+            .replacen("{}", &target_path.display().to_string(), 1)
+            .replacen("{}", &format_size(size), 1);
+        return Ok(ToolResult::ok(brief, output));
+    }
 
-    let brief_tmpl = i18n.get("file_read_brief");
-    let brief =
-        brief_tmpl
-            .replacen("{}", &lines.to_string(), 1)
-            .replacen("{}", &bytes.to_string(), 1);
+    let selection = read_lines(&target_path, &args)?;
+    read_tracker::record_hash(&target_path, selection.hash);
 
     let header_tmpl = i18n.get("file_read_header");
-    let header = header_tmpl.replace("{}", &target_path.display().to_string());
-    let output = format!("{}\n{}", header, content);
+    let mut output = header_tmpl.replace("{}", &target_path.display().to_string());
+    output.push('\n');
+    output.push_str(&selection.text);
+
+    let partial = selection.first > 1 || selection.last < selection.total_lines;
+    let brief = if partial {
+        i18n.get("file_read_range_brief")
+            .replacen("{}", &selection.first.to_string(), 1)
+            .replacen("{}", &selection.last.to_string(), 1)
+            .replacen("{}", &selection.total_lines.to_string(), 1)
+    } else {
+        i18n.get("file_read_brief")
+            .replacen("{}", &selection.total_lines.to_string(), 1)
+            .replacen("{}", &size.to_string(), 1)
+    };
+
+    if selection.last < selection.first {
+        output.push_str(
+            &i18n
+                .get("file_read_past_end")
+                .replace("{}", &selection.total_lines.to_string()),
+        );
+    } else if selection.capped {
+        if !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(
+            &i18n
+                .get("file_read_truncated")
+                .replacen("{}", &selection.total_lines.to_string(), 1)
+                .replacen("{}", &format_size(size), 1)
+                .replacen("{}", &selection.first.to_string(), 1)
+                .replacen("{}", &selection.last.to_string(), 1),
+        );
+    }
+    if selection.first_line_cut {
+        if !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(
+            &i18n
+                .get("file_read_line_truncated")
+                .replacen("{}", &selection.first.to_string(), 1)
+                .replacen("{}", &format_size(MAX_READ_BYTES as u64), 1),
+        );
+    }
+    if selection.lossy {
+        output.push('\n');
+        output.push_str(&i18n.get("file_read_lossy"));
+    }
 
     Ok(ToolResult::ok(brief, output))
 }
+
+/// 只读取文件开头判断是否为二进制文件
+fn is_binary(path: &Path) -> Result<bool> {
+    let mut head = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    File::open(path)?
+        .take(BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut head)?;
+    Ok(looks_binary(&head))
+}
+
+/// 逐行读取选定范围，同时统计总行数（不会把整个文件读入内存）
+fn read_lines(path: &Path, args: &FileReadArgs) -> Result<Selection> {
+    let first = args.offset.unwrap_or(1).max(1);
+    let limit = args.limit.unwrap_or(MAX_READ_LINES).max(1);
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut selection = Selection {
+        text: String::new(),
+        first,
+        last: first - 1,
+        total_lines: 0,
+        capped: false,
+        first_line_cut: false,
+        lossy: false,
+        hash: 0,
+    };
+    let mut buf = Vec::new();
+    let mut hasher = ContentHasher::new();
+
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        hasher.update(&buf);
+        selection.total_lines += 1;
+        let line_no = selection.total_lines;
+        if line_no < first || selection.capped {
+            continue;
+        }
+        if line_no - first >= limit {
+            // 显式 limit 不算截断，未指定 limit 时达到行数上限才提示
+            selection.capped = args.limit.is_none();
+            continue;
+        }
+
+        let line = match std::str::from_utf8(&buf) {
+            Ok(line) => line.to_string(),
+            Err(_) => {
+                selection.lossy = true;
+                String::from_utf8_lossy(&buf).to_string()
+            }
+        };
+        let line = if args.line_numbers {
+            format!("{:>6}\t{}", line_no, line)
+        } else {
+            line
+        };
+        if selection.text.len() + line.len() > MAX_READ_BYTES {
+            if selection.last >= first {
+                selection.capped = true;
+                continue;
+            }
+            // 第一行本身就超过上限时只保留开头，后续的行都会因上限被截断
+            selection
+                .text
+                .push_str(truncate_at_char(&line, MAX_READ_BYTES));
+            selection.text.push('\n');
+            selection.first_line_cut = true;
+            selection.last = line_no;
+            continue;
+        }
+        selection.text.push_str(&line);
+        selection.last = line_no;
+    }
+
+    selection.hash = hasher.finish();
+    Ok(selection)
+}
+
+/// 在字符边界处截断到不超过 max_bytes 字节
+fn truncate_at_char(text: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, content: &[u8]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("friendev_file_read_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn read(path: &Path, offset: Option<usize>, limit: Option<usize>) -> Selection {
+        let args = FileReadArgs {
+            path: String::new(),
+            offset,
+            limit,
+            line_numbers: true,
+        };
+        read_lines(path, &args).unwrap()
+    }
+
+    #[test]
+    fn test_line_range_with_numbers() {
+        let path = temp_file("range.txt", b"a\nb\nc\nd\n");
+        let selection = read(&path, Some(2), Some(2));
+        assert_eq!(selection.text, "     2\tb\n     3\tc\n");
+        assert_eq!((selection.first, selection.last), (2, 3));
+        assert_eq!(selection.total_lines, 4);
+        assert!(!selection.capped);
+
+        let selection = read(&path, Some(9), None);
+        assert!(selection.text.is_empty());
+        assert!(selection.last < selection.first);
+    }
+
+    #[test]
+    fn test_large_file_is_capped() {
+        let content = "line\n".repeat(MAX_READ_LINES + 10);
+        let path = temp_file("large.txt", content.as_bytes());
+        let selection = read(&path, None, None);
+        assert!(selection.capped);
+        assert_eq!(selection.last, MAX_READ_LINES);
+        assert_eq!(selection.total_lines, MAX_READ_LINES + 10);
+
+        let long_line = "x".repeat(MAX_READ_BYTES / 2);
+        let path = temp_file("wide.txt", format!("{0}\n{0}\n{0}\n", long_line).as_bytes());
+        let selection = read(&path, None, None);
+        assert!(selection.capped);
+        assert_eq!(selection.last, 1);
+    }
+
+    #[test]
+    fn test_oversized_first_line_is_cut() {
+        // 多字节字符，截断位置不一定落在字符边界上
+        let long_line = "é".repeat(MAX_READ_BYTES);
+        let path = temp_file("minified.txt", format!("{}\nnext\n", long_line).as_bytes());
+        let selection = read(&path, None, None);
+        assert!(selection.first_line_cut);
+        assert!(selection.capped);
+        assert_eq!((selection.first, selection.last), (1, 1));
+        assert!(selection.text.len() <= MAX_READ_BYTES + 1);
+        assert!(selection.text.starts_with("     1\té"));
+        assert!(!selection.text.contains("next"));
+
+        let path = temp_file("single.txt", long_line.as_bytes());
+        let selection = read(&path, None, None);
+        assert!(selection.first_line_cut);
+        assert!(!selection.capped);
+        assert_eq!(selection.last, 1);
+    }
+
+    #[test]
+    fn test_binary_and_non_utf8() {
+        let path = temp_file("blob.bin", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+        assert!(is_binary(&path).unwrap());
+
+        let path = temp_file("latin1.txt", b"caf\xe9\n");
+        assert!(!is_binary(&path).unwrap());
+        let selection = read(&path, None, None);
+        assert!(selection.lossy);
+        assert!(selection.text.contains("caf\u{fffd}"));
+    }
+}
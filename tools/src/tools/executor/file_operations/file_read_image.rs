@@ -0,0 +1,46 @@
+use anyhow::Result;
+use history::{image_media_type, ImageUrl};
+use std::path::Path;
+
+use super::file_common::{resolve_path, Access};
+use crate::tools::args::FileReadImageArgs;
+use crate::tools::utils::format_size;
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+pub async fn execute_file_read_image(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    let args: FileReadImageArgs = serde_json::from_str(arguments)?;
+
+    let target_path = match resolve_path(&args.path, working_dir, Access::Read) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
+    let i18n = get_i18n();
+
+    if !target_path.is_file() {
+        let tmpl = i18n.get("file_not_file");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &target_path.display().to_string()),
+        ));
+    }
+
+    if image_media_type(&target_path).is_none() {
+        let tmpl = i18n.get("file_read_image_unsupported");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &target_path.display().to_string()),
+        ));
+    }
+
+    let image = match ImageUrl::from_file(&target_path) {
+        Ok(image) => image,
+        Err(e) => return Ok(ToolResult::error(e.to_string())),
+    };
+
+    let size = format_size(target_path.metadata()?.len());
+    let brief = i18n.get("file_read_image_brief").replace("{}", &size);
+    let output = i18n
+        .get("file_read_image_output")
+        .replace("{}", &target_path.display().to_string());
+
+    Ok(ToolResult::ok(brief, output).with_images(vec![image]))
+}
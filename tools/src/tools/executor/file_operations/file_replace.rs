@@ -4,7 +4,7 @@ use std::fs;
 use std::path::Path;
 
 use super::super::utils::normalize_whitespace;
-use super::file_common::normalize_path;
+use super::file_common::{after_write, before_write, resolve_path, Access};
 use crate::tools::args::FileReplaceArgs;
 use crate::types::ToolResult;
 
@@ -15,7 +15,10 @@ pub async fn execute_file_replace(
 ) -> Result<ToolResult> {
     let args: FileReplaceArgs = serde_json::from_str(arguments)?;
 
-    let target_path = normalize_path(&args.path, working_dir);
+    let target_path = match resolve_path(&args.path, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
 
     // 验证文件存在
     if !target_path.exists() {
@@ -70,7 +73,9 @@ pub async fn execute_file_replace(
     } else {
         content
     };
+    before_write(&target_path)?;
     fs::write(&target_path, &final_content)?;
+    after_write(&target_path)?;
 
     let brief = format!(
         "应用了 {} 个编辑，{} 个替换",
@@ -87,36 +92,6 @@ pub async fn execute_file_replace(
     Ok(ToolResult::ok(brief, output))
 }
 
-fn generate_preview(args: &FileReplaceArgs) -> String {
-    let preview = args
-        .edits
-        .iter()
-        .take(3)
-        .map(|e| {
-            let old_preview = if e.old.chars().count() > 40 {
-                let truncated: String = e.old.chars().take(40).collect();
-                format!("{}...", truncated)
-            } else {
-                e.old.clone()
-            };
-            let new_preview = if e.new.chars().count() > 40 {
-                let truncated: String = e.new.chars().take(40).collect();
-                format!("{}...", truncated)
-            } else {
-                e.new.clone()
-            };
-            format!("- Replace: {}\n  With: {}", old_preview, new_preview)
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    if args.edits.len() > 3 {
-        format!("{}\n... and {} more edits", preview, args.edits.len() - 3)
-    } else {
-        preview
-    }
-}
-
 fn generate_detailed_changes(_file_content: &str, args: &FileReplaceArgs) -> String {
     let mut detailed_changes = String::new();
 
@@ -138,7 +113,7 @@ fn generate_detailed_changes(_file_content: &str, args: &FileReplaceArgs) -> Str
         for line in edit.new.lines() {
             detailed_changes.push_str(&format!("+{}\n", line));
         }
-        detailed_changes.push_str("\n");
+        detailed_changes.push('\n');
     }
 
     detailed_changes
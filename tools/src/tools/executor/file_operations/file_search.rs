@@ -0,0 +1,340 @@
+use anyhow::Result;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use regex::{Regex, RegexBuilder};
+use std::path::Path;
+
+use super::file_common::{
+    display_path, looks_binary, path_policy, project_walker, resolve_path, Access,
+};
+use crate::tools::args::{FileSearchArgs, SortOrder};
+use crate::types::ToolResult;
+use security::PathPolicy;
+use ui::get_i18n;
+
+/// max_results 的上限
+const MAX_RESULTS_LIMIT: usize = 1000;
+
+/// context 的上限
+const MAX_CONTEXT_LINES: usize = 10;
+
+/// 超过该大小的文件不搜索
+const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// 单行输出的最大字符数
+const MAX_LINE_CHARS: usize = 300;
+
+/// 搜索结果
+struct SearchOutput {
+    lines: Vec<String>,
+    matches: usize,
+    files: usize,
+    /// 是否因 max_results 提前停止
+    truncated: bool,
+    /// 跳过的敏感文件数
+    sensitive: usize,
+}
+
+pub async fn execute_file_search(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    let args: FileSearchArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let root = match &args.path {
+        Some(path) if !path.is_empty() => match resolve_path(path, working_dir, Access::Read) {
+            Ok(path) => path,
+            Err(rejected) => return Ok(rejected),
+        },
+        _ => working_dir.to_path_buf(),
+    };
+    if !root.exists() {
+        let tmpl = i18n.get("file_path_not_exist");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &root.display().to_string()),
+        ));
+    }
+
+    let pattern = if args.literal {
+        regex::escape(&args.pattern)
+    } else {
+        args.pattern.clone()
+    };
+    let regex = match RegexBuilder::new(&pattern)
+        .case_insensitive(!args.case_sensitive)
+        .build()
+    {
+        Ok(regex) => regex,
+        Err(e) => {
+            let tmpl = i18n.get("file_search_bad_pattern");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
+
+    let walker = match build_walker(&root, &args.include, &args.exclude) {
+        Ok(walker) => walker,
+        Err(e) => {
+            let tmpl = i18n.get("file_search_bad_glob");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
+
+    let max_results = args.max_results.clamp(1, MAX_RESULTS_LIMIT);
+    let context = args.context.min(MAX_CONTEXT_LINES);
+    let display_root = working_dir.to_path_buf();
+    let policy = path_policy(working_dir);
+    let output = tokio::task::spawn_blocking(move || {
+        search(walker, &regex, &policy, &display_root, context, max_results)
+    })
+    .await?;
+    let sensitive_note = (output.sensitive > 0).then(|| {
+        i18n.get("file_search_sensitive_skipped")
+            .replace("{}", &output.sensitive.to_string())
+    });
+
+    if output.matches == 0 {
+        let tmpl = i18n.get("file_search_none");
+        let brief = tmpl.replace("{}", &args.pattern);
+        let message = match &sensitive_note {
+            Some(note) => format!("{}\n{}", brief, note),
+            None => brief.clone(),
+        };
+        return Ok(ToolResult::ok(brief, message));
+    }
+
+    let brief = i18n
+        .get("file_search_brief")
+        .replacen("{}", &output.matches.to_string(), 1)
+        .replacen("{}", &output.files.to_string(), 1);
+    let mut message = output.lines.join("\n");
+    if output.truncated {
+        message.push_str("\n\n");
+        message.push_str(
+            &i18n
+                .get("file_search_truncated")
+                .replace("{}", &max_results.to_string()),
+        );
+    }
+    if let Some(note) = sensitive_note {
+        message.push_str("\n\n");
+        message.push_str(&note);
+    }
+    Ok(ToolResult::ok(brief, message))
+}
+
+/// 遵循 .gitignore 的遍历器，include / exclude 作为 gitignore 风格的 glob 叠加
+fn build_walker(root: &Path, include: &[String], exclude: &[String]) -> Result<WalkBuilder> {
+    let mut overrides = OverrideBuilder::new(root);
+    for glob in include {
+        overrides.add(glob)?;
+    }
+    for glob in exclude {
+        overrides.add(&format!("!{}", glob))?;
+    }
+
+    let mut walker = project_walker(root, false, SortOrder::Path);
+    walker.overrides(overrides.build()?);
+    Ok(walker)
+}
+
+fn search(
+    walker: WalkBuilder,
+    regex: &Regex,
+    policy: &PathPolicy,
+    display_root: &Path,
+    context: usize,
+    max_results: usize,
+) -> SearchOutput {
+    let mut output = SearchOutput {
+        lines: Vec::new(),
+        matches: 0,
+        files: 0,
+        truncated: false,
+        sensitive: 0,
+    };
+
+    for entry in walker.build().flatten() {
+        if output.truncated {
+            break;
+        }
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        // 敏感文件的内容不出现在搜索结果中
+        if policy.sensitive_pattern(entry.path()).is_some() {
+            output.sensitive += 1;
+            continue;
+        }
+        if entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.len() > MAX_FILE_BYTES)
+        {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        if looks_binary(&bytes) {
+            continue;
+        }
+
+        let content = String::from_utf8_lossy(&bytes);
+        let lines: Vec<&str> = content.lines().collect();
+        let mut matched = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            if regex.is_match(line) {
+                if output.matches == max_results {
+                    output.truncated = true;
+                    break;
+                }
+                matched.push(index);
+                output.matches += 1;
+            }
+        }
+        if matched.is_empty() {
+            continue;
+        }
+
+        output.files += 1;
+        if context > 0 && !output.lines.is_empty() {
+            output.lines.push("--".to_string());
+        }
+        let path = display_path(entry.path(), display_root);
+        format_matches(&mut output.lines, &path, &lines, &matched, context);
+    }
+
+    output
+}
+
+/// 匹配行为 `path:line: text`，上下文行为 `path-line- text`，不相邻的片段之间用 `--` 分隔
+fn format_matches(
+    out: &mut Vec<String>,
+    path: &str,
+    lines: &[&str],
+    matched: &[usize],
+    context: usize,
+) {
+    let mut next = 0; // 下一个尚未输出的行
+    for (i, &index) in matched.iter().enumerate() {
+        let start = index.saturating_sub(context).max(next);
+        let end = (index + context).min(lines.len() - 1);
+        if context > 0 && i > 0 && start > next {
+            out.push("--".to_string());
+        }
+        for (line_index, line) in lines.iter().enumerate().take(end + 1).skip(start) {
+            let separator = if matched.binary_search(&line_index).is_ok() {
+                ':'
+            } else {
+                '-'
+            };
+            out.push(format!(
+                "{}{}{}{} {}",
+                path,
+                separator,
+                line_index + 1,
+                separator,
+                shorten_line(line)
+            ));
+        }
+        next = end + 1;
+    }
+}
+
+fn shorten_line(line: &str) -> String {
+    let line = line.trim_end_matches('\r');
+    if line.chars().count() <= MAX_LINE_CHARS {
+        line.to_string()
+    } else {
+        let cut: String = line.chars().take(MAX_LINE_CHARS).collect();
+        format!("{}...", cut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "friendev_file_search_{}_{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "use std::fmt;\n\nfn parse_config() {}\n\nfn main() {\n    parse_config();\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("src/notes.md"), "Call Parse_Config first\n").unwrap();
+        std::fs::write(dir.join("target/out.rs"), "parse_config();\n").unwrap();
+        std::fs::write(dir.join("data.bin"), b"parse_config\0\x01").unwrap();
+        dir
+    }
+
+    async fn run(dir: &Path, args: serde_json::Value) -> ToolResult {
+        execute_file_search(&args.to_string(), dir).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_search_respects_gitignore_and_globs() {
+        let dir = project("globs");
+
+        let result = run(&dir, serde_json::json!({ "pattern": "parse_config\\(" })).await;
+        let expected = format!(
+            "{0}:3: fn parse_config() {{}}\n{0}:6:     parse_config();",
+            Path::new("src").join("lib.rs").display()
+        );
+        assert_eq!(result.message, expected);
+
+        let result = run(
+            &dir,
+            serde_json::json!({ "pattern": "parse_config", "case_sensitive": false, "include": ["*.md"] }),
+        )
+        .await;
+        assert!(result
+            .message
+            .ends_with("notes.md:1: Call Parse_Config first"));
+
+        let result = run(
+            &dir,
+            serde_json::json!({ "pattern": "parse_config", "exclude": ["*.rs"] }),
+        )
+        .await;
+        assert!(result.success);
+        assert!(!result.message.contains("lib.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_search_context_and_cap() {
+        let dir = project("context");
+
+        let result = run(
+            &dir,
+            serde_json::json!({ "pattern": "fn main() {", "literal": true, "context": 1 }),
+        )
+        .await;
+        let lines: Vec<&str> = result.message.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("lib.rs-4- "));
+        assert!(lines[1].ends_with("lib.rs:5: fn main() {"));
+
+        let result = run(
+            &dir,
+            serde_json::json!({ "pattern": "fn", "max_results": 1 }),
+        )
+        .await;
+        assert_eq!(
+            result.message.lines().next().unwrap(),
+            format!(
+                "{}:3: fn parse_config() {{}}",
+                Path::new("src").join("lib.rs").display()
+            )
+        );
+        assert!(result.message.contains("[Stopped"));
+
+        let result = run(&dir, serde_json::json!({ "pattern": "(" })).await;
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use std::path::Path;
+
+use super::file_common::{project_walker, resolve_path, Access};
+use crate::tools::args::FileTreeArgs;
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+/// max_depth 的上限
+const MAX_DEPTH_LIMIT: usize = 10;
+
+/// max_entries 的上限
+const MAX_ENTRIES_LIMIT: usize = 5000;
+
+pub async fn execute_file_tree(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    let args: FileTreeArgs = serde_json::from_str(arguments)?;
+    let i18n = get_i18n();
+
+    let root = match &args.path {
+        Some(path) if !path.is_empty() => match resolve_path(path, working_dir, Access::Read) {
+            Ok(path) => path,
+            Err(rejected) => return Ok(rejected),
+        },
+        _ => working_dir.to_path_buf(),
+    };
+    if !root.is_dir() {
+        let tmpl = i18n.get("file_not_directory");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &root.display().to_string()),
+        ));
+    }
+
+    let (lines, truncated) = tokio::task::spawn_blocking({
+        let root = root.clone();
+        move || render_tree(&root, &args)
+    })
+    .await?;
+
+    let brief = i18n
+        .get("file_tree_brief")
+        .replace("{}", &lines.len().to_string());
+    let mut output = format!("{}/\n{}", root.display(), lines.join("\n"));
+    if truncated {
+        output.push_str("\n\n");
+        output.push_str(
+            &i18n
+                .get("file_tree_truncated")
+                .replace("{}", &lines.len().to_string()),
+        );
+    }
+    Ok(ToolResult::ok(brief, output))
+}
+
+/// 每层缩进两个空格，目录以 `/` 结尾；返回各行以及是否因 max_entries 截断
+fn render_tree(root: &Path, args: &FileTreeArgs) -> (Vec<String>, bool) {
+    let max_depth = args.max_depth.clamp(1, MAX_DEPTH_LIMIT);
+    let max_entries = args.max_entries.clamp(1, MAX_ENTRIES_LIMIT);
+
+    let mut walker = project_walker(root, args.hidden, args.sort);
+    walker.max_depth(Some(max_depth));
+
+    let mut lines = Vec::new();
+    for entry in walker.build().flatten() {
+        if entry.depth() == 0 {
+            continue;
+        }
+        if lines.len() == max_entries {
+            return (lines, true);
+        }
+        let mut line = format!(
+            "{}{}",
+            "  ".repeat(entry.depth() - 1),
+            entry.file_name().to_string_lossy()
+        );
+        if entry.file_type().is_some_and(|t| t.is_dir()) {
+            line.push('/');
+        }
+        lines.push(line);
+    }
+    (lines, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::args::SortOrder;
+
+    fn tree_args(max_depth: usize, max_entries: usize, hidden: bool) -> FileTreeArgs {
+        FileTreeArgs {
+            path: None,
+            max_depth,
+            hidden,
+            sort: SortOrder::Path,
+            max_entries,
+        }
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let dir = std::env::temp_dir().join(format!("friendev_file_tree_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src/tools/deep")).unwrap();
+        std::fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "node_modules/\n").unwrap();
+        for file in [
+            "Cargo.toml",
+            "src/main.rs",
+            "src/tools/mod.rs",
+            "src/tools/deep/x.rs",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+
+        let (lines, truncated) = render_tree(&dir, &tree_args(2, 100, false));
+        assert_eq!(lines, ["Cargo.toml", "src/", "  main.rs", "  tools/"]);
+        assert!(!truncated);
+
+        let (lines, _) = render_tree(&dir, &tree_args(1, 100, true));
+        assert_eq!(lines, [".gitignore", "Cargo.toml", "src/"]);
+
+        let (lines, truncated) = render_tree(&dir, &tree_args(5, 3, false));
+        assert_eq!(lines.len(), 3);
+        assert!(truncated);
+    }
+}
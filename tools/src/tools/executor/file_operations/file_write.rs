@@ -4,7 +4,7 @@ use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::Path;
 
-use super::file_common::normalize_path;
+use super::file_common::{after_write, before_write, resolve_path, Access};
 use crate::tools::args::FileWriteArgs;
 use crate::types::ToolResult;
 use ui::get_i18n;
@@ -16,7 +16,10 @@ pub async fn execute_file_write(
 ) -> Result<ToolResult> {
     let args: FileWriteArgs = serde_json::from_str(arguments)?;
 
-    let target_path = normalize_path(&args.path, working_dir);
+    let target_path = match resolve_path(&args.path, working_dir, Access::Write) {
+        Ok(path) => path,
+        Err(rejected) => return Ok(rejected),
+    };
     let i18n = get_i18n();
 
     // 验证 mode 参数
@@ -38,6 +41,8 @@ pub async fn execute_file_write(
         }
     }
 
+    before_write(&target_path)?;
+
     // 创建父目录（如果不存在）
     if let Some(parent) = target_path.parent() {
         fs::create_dir_all(parent)?;
@@ -58,6 +63,7 @@ fn execute_append_mode(target_path: &Path, content: &str) -> Result<ToolResult>
         .append(true)
         .open(target_path)?;
     file.write_all(content.as_bytes())?;
+    after_write(target_path)?;
 
     let file_size = target_path.metadata()?.len();
 
@@ -74,6 +80,7 @@ fn execute_append_mode(target_path: &Path, content: &str) -> Result<ToolResult>
 
 fn execute_overwrite_mode(target_path: &Path, content: &str) -> Result<ToolResult> {
     fs::write(target_path, content)?;
+    after_write(target_path)?;
 
     let i18n = ui::get_i18n();
 
@@ -95,15 +102,19 @@ fn generate_detailed_changes(target_path: &Path, args: &FileWriteArgs) -> String
         // If file exists, we might want to show the last few lines of the existing file for context?
         // For now, just show what's being added as +
         if target_path.exists() {
-             if let Ok(content) = fs::read_to_string(target_path) {
-                 let lines: Vec<&str> = content.lines().collect();
-                 if !lines.is_empty() {
-                      let context_lines = if lines.len() > 3 { &lines[lines.len()-3..] } else { &lines[..] };
-                      for line in context_lines {
-                          detailed_changes.push_str(&format!(" {}\n", line));
-                      }
-                 }
-             }
+            if let Ok(content) = fs::read_to_string(target_path) {
+                let lines: Vec<&str> = content.lines().collect();
+                if !lines.is_empty() {
+                    let context_lines = if lines.len() > 3 {
+                        &lines[lines.len() - 3..]
+                    } else {
+                        &lines[..]
+                    };
+                    for line in context_lines {
+                        detailed_changes.push_str(&format!(" {}\n", line));
+                    }
+                }
+            }
         }
 
         for line in args.content.lines() {
@@ -112,17 +123,17 @@ fn generate_detailed_changes(target_path: &Path, args: &FileWriteArgs) -> String
     } else {
         // Overwrite mode
         detailed_changes.push_str("@@ Overwrite file @@\n");
-        
+
         if target_path.exists() {
-             if let Ok(content) = fs::read_to_string(target_path) {
-                 for line in content.lines() {
-                     detailed_changes.push_str(&format!("-{}\n", line));
-                 }
-             }
+            if let Ok(content) = fs::read_to_string(target_path) {
+                for line in content.lines() {
+                    detailed_changes.push_str(&format!("-{}\n", line));
+                }
+            }
         } else {
-             detailed_changes.push_str("--- /dev/null\n");
+            detailed_changes.push_str("--- /dev/null\n");
         }
-        
+
         detailed_changes.push_str(&format!("+++ {}\n", target_path.display()));
         for line in args.content.lines() {
             detailed_changes.push_str(&format!("+{}\n", line));
@@ -3,21 +3,50 @@ use std::path::Path;
 
 use crate::types::ToolResult;
 
+mod apply_patch;
+mod dir_create;
 mod file_common;
+mod file_copy;
+mod file_delete;
 mod file_diff_edit;
+mod file_glob;
 mod file_list;
+mod file_move;
 mod file_read;
+mod file_read_image;
 mod file_replace;
+mod file_search;
+mod file_tree;
 mod file_write;
+mod patch;
+mod read_tracker;
+
+pub(crate) use file_common::{resolve_path, Access};
 
 pub async fn execute_file_list(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
     file_list::execute_file_list(arguments, working_dir).await
 }
 
+pub async fn execute_file_glob(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    file_glob::execute_file_glob(arguments, working_dir).await
+}
+
+pub async fn execute_file_tree(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    file_tree::execute_file_tree(arguments, working_dir).await
+}
+
 pub async fn execute_file_read(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
     file_read::execute_file_read(arguments, working_dir).await
 }
 
+pub async fn execute_file_search(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    file_search::execute_file_search(arguments, working_dir).await
+}
+
+pub async fn execute_file_read_image(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    file_read_image::execute_file_read_image(arguments, working_dir).await
+}
+
 pub async fn execute_file_write(
     arguments: &str,
     working_dir: &Path,
@@ -41,3 +70,43 @@ pub async fn execute_file_diff_edit(
 ) -> Result<ToolResult> {
     file_diff_edit::execute_file_diff_edit(arguments, working_dir, require_approval).await
 }
+
+pub async fn execute_apply_patch(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    apply_patch::execute_apply_patch(arguments, working_dir, require_approval).await
+}
+
+pub async fn execute_file_move(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    file_move::execute_file_move(arguments, working_dir, require_approval).await
+}
+
+pub async fn execute_file_copy(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    file_copy::execute_file_copy(arguments, working_dir, require_approval).await
+}
+
+pub async fn execute_file_delete(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    file_delete::execute_file_delete(arguments, working_dir, require_approval).await
+}
+
+pub async fn execute_dir_create(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    dir_create::execute_dir_create(arguments, working_dir, require_approval).await
+}
@@ -0,0 +1,534 @@
+//! Unified diff 的解析与按上下文定位应用
+
+use anyhow::{bail, Result};
+
+/// 一个文件的补丁
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilePatch {
+    /// 原路径，新建文件时为 None（`/dev/null`）
+    pub old_path: Option<String>,
+    /// 新路径，删除文件时为 None（`/dev/null`）
+    pub new_path: Option<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// 补丁作用的路径（删除时为原路径）
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// 新增与删除的行数
+    pub fn line_counts(&self) -> (usize, usize) {
+        let lines = self.hunks.iter().flat_map(|hunk| &hunk.lines);
+        lines.fold((0, 0), |(added, removed), line| match line {
+            HunkLine::Add(_) => (added + 1, removed),
+            HunkLine::Remove(_) => (added, removed + 1),
+            HunkLine::Context(_) => (added, removed),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk {
+    /// `@@ -old_start,old_len +new_start,new_len @@` 中的原文件起始行
+    pub old_start: usize,
+    pub header: String,
+    pub lines: Vec<HunkLine>,
+    /// 原内容末尾没有换行（`\ No newline at end of file`）
+    pub old_no_newline: bool,
+    /// 新内容末尾没有换行
+    pub new_no_newline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+impl Hunk {
+    /// 原文件中应出现的行（上下文与删除行）
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+
+    /// 替换后的行（上下文与新增行）
+    fn new_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Add(text) => Some(text.as_str()),
+                HunkLine::Remove(_) => None,
+            })
+            .collect()
+    }
+
+    /// 开头与结尾的上下文行数
+    fn context_counts(&self) -> (usize, usize) {
+        let is_context = |line: &&HunkLine| matches!(line, HunkLine::Context(_));
+        let leading = self.lines.iter().take_while(is_context).count();
+        let trailing = if leading == self.lines.len() {
+            0
+        } else {
+            self.lines.iter().rev().take_while(is_context).count()
+        };
+        (leading, trailing)
+    }
+}
+
+/// 解析（可能包含多个文件的）unified diff，忽略 `index`、说明文字等无关行
+pub fn parse_patch(text: &str) -> Result<Vec<FilePatch>> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut files: Vec<FilePatch> = Vec::new();
+    // 当前文件是否只有 `diff --git` 头，尚未遇到 `---`/`+++`
+    let mut git_header_only = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let (old, new) = match rest.split_once(" b/") {
+                Some((old, new)) => (old.trim_start_matches("a/").to_string(), new.to_string()),
+                None => bail!("cannot read file names from `{}`", line),
+            };
+            files.push(FilePatch {
+                old_path: Some(old),
+                new_path: Some(new),
+                hunks: Vec::new(),
+            });
+            git_header_only = true;
+            i += 1;
+        } else if is_file_header(&lines, i) {
+            let old = header_path(&lines[i][4..]);
+            let new = header_path(&lines[i + 1][4..]);
+            let (old, new) = strip_git_prefixes(old, new);
+            match files.last_mut() {
+                Some(file) if git_header_only => {
+                    file.old_path = old;
+                    file.new_path = new;
+                }
+                _ => files.push(FilePatch {
+                    old_path: old,
+                    new_path: new,
+                    hunks: Vec::new(),
+                }),
+            }
+            git_header_only = false;
+            i += 2;
+        } else if line.starts_with("@@") {
+            let Some(file) = files.last_mut() else {
+                bail!("hunk `{}` has no file header (--- / +++)", line);
+            };
+            let (hunk, next) = parse_hunk(&lines, i)?;
+            file.hunks.push(hunk);
+            git_header_only = false;
+            i = next;
+        } else {
+            if git_header_only {
+                if let Some(file) = files.last_mut() {
+                    if line.starts_with("new file mode") {
+                        file.old_path = None;
+                    } else if line.starts_with("deleted file mode") {
+                        file.new_path = None;
+                    } else if let Some(path) = line.strip_prefix("rename from ") {
+                        file.old_path = Some(path.to_string());
+                    } else if let Some(path) = line.strip_prefix("rename to ") {
+                        file.new_path = Some(path.to_string());
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    for file in &files {
+        if file.old_path.is_none() && file.new_path.is_none() {
+            bail!("a file patch has /dev/null on both sides");
+        }
+        let modifies = file.old_path.is_some() && file.new_path.is_some();
+        if modifies && file.hunks.is_empty() && file.old_path == file.new_path {
+            bail!("{} has no hunks", file.path());
+        }
+    }
+    Ok(files)
+}
+
+fn is_file_header(lines: &[&str], i: usize) -> bool {
+    lines[i].starts_with("--- ") && lines.get(i + 1).is_some_and(|l| l.starts_with("+++ "))
+}
+
+/// `--- a/src/x.rs\t2024-01-01 ...` 中的路径，`/dev/null` 为 None
+fn header_path(value: &str) -> Option<String> {
+    let path = value.split('\t').next().unwrap_or_default().trim();
+    let path = path.trim_matches('"');
+    (path != "/dev/null").then(|| path.to_string())
+}
+
+/// 去掉 git 风格的 `a/` 与 `b/` 前缀
+fn strip_git_prefixes(
+    old: Option<String>,
+    new: Option<String>,
+) -> (Option<String>, Option<String>) {
+    let old_git = old.as_deref().is_none_or(|p| p.starts_with("a/"));
+    let new_git = new.as_deref().is_none_or(|p| p.starts_with("b/"));
+    if old_git && new_git {
+        (
+            old.map(|p| p[2..].to_string()),
+            new.map(|p| p[2..].to_string()),
+        )
+    } else {
+        (old, new)
+    }
+}
+
+/// 从 `@@` 行开始解析一个 hunk，返回 hunk 与下一行的位置
+fn parse_hunk(lines: &[&str], start: usize) -> Result<(Hunk, usize)> {
+    let header = lines[start];
+    let (old_start, old_len) = parse_range(header)?;
+    let mut hunk = Hunk {
+        old_start,
+        header: header.to_string(),
+        lines: Vec::new(),
+        old_no_newline: false,
+        new_no_newline: false,
+    };
+
+    let mut i = start + 1;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.starts_with("@@") || line.starts_with("diff --git ") || is_file_header(lines, i) {
+            break;
+        }
+        let parsed = if line.is_empty() {
+            // 编辑器或模型常会去掉空上下文行开头的空格
+            HunkLine::Context(String::new())
+        } else if line.starts_with('\\') {
+            match hunk.lines.last() {
+                Some(HunkLine::Remove(_)) => hunk.old_no_newline = true,
+                Some(HunkLine::Add(_)) => hunk.new_no_newline = true,
+                _ => {
+                    hunk.old_no_newline = true;
+                    hunk.new_no_newline = true;
+                }
+            }
+            i += 1;
+            continue;
+        } else {
+            let (kind, text) = line.split_at(1);
+            match kind {
+                " " => HunkLine::Context(text.to_string()),
+                "-" => HunkLine::Remove(text.to_string()),
+                "+" => HunkLine::Add(text.to_string()),
+                _ => break,
+            }
+        };
+        hunk.lines.push(parsed);
+        i += 1;
+    }
+
+    // 补丁末尾或文件之间的空行不属于 hunk
+    while hunk.old_lines().len() > old_len
+        && matches!(hunk.lines.last(), Some(HunkLine::Context(text)) if text.is_empty())
+    {
+        hunk.lines.pop();
+    }
+    if hunk.lines.is_empty() {
+        bail!("hunk `{}` is empty", header);
+    }
+    Ok((hunk, i))
+}
+
+/// `@@ -12,5 +12,6 @@` 中原文件的起始行与行数；只有 `@@` 没有行号时为 (0, 0)，
+/// 此时只按上下文定位
+fn parse_range(header: &str) -> Result<(usize, usize)> {
+    let invalid = || anyhow::anyhow!("invalid hunk header `{}`", header);
+    let Some(range) = header
+        .trim_start_matches('@')
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('-'))
+    else {
+        return Ok((0, 0));
+    };
+    let (start, len) = match range.split_once(',') {
+        Some((start, len)) => (start, len.parse().map_err(|_| invalid())?),
+        None => (range, 1),
+    };
+    Ok((start.parse().map_err(|_| invalid())?, len))
+}
+
+/// 应用到文件内容后的结果
+#[derive(Debug)]
+pub struct Patched {
+    pub content: String,
+    /// 未按原位置精确应用的 hunk：(hunk 序号, 实际起始行, 偏移, fuzz)
+    pub adjusted: Vec<(usize, usize, isize, usize)>,
+}
+
+/// 无法应用的 hunk
+#[derive(Debug)]
+pub struct HunkFailure {
+    /// 从 1 开始的 hunk 序号
+    pub index: usize,
+    pub header: String,
+    pub closest: Option<ClosestMatch>,
+}
+
+/// 与 hunk 原内容最相似的区域
+#[derive(Debug)]
+pub struct ClosestMatch {
+    /// 起始行（从 1 开始）
+    pub line: usize,
+    pub lines: Vec<String>,
+    pub equal: usize,
+    pub total: usize,
+}
+
+/// 按上下文定位并应用所有 hunk；fuzz 为允许从 hunk 两端忽略的上下文行数。
+/// 任一 hunk 失败时返回全部失败的 hunk，内容不做任何修改。
+pub fn apply_hunks(
+    content: &str,
+    hunks: &[Hunk],
+    fuzz: usize,
+) -> std::result::Result<Patched, Vec<HunkFailure>> {
+    let crlf = content.contains("\r\n");
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let mut trailing_newline = content.is_empty() || content.ends_with('\n');
+    let mut adjusted = Vec::new();
+    let mut failures = Vec::new();
+    // 下一个 hunk 最早可以开始的位置，以及已应用 hunk 造成的行号偏移
+    let mut cursor = 0;
+    let mut shift: isize = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let old = hunk.old_lines();
+        let new = hunk.new_lines();
+        let (leading, trailing) = hunk.context_counts();
+        let expected = (hunk.old_start.saturating_sub(1) as isize + shift).max(0) as usize;
+
+        let mut found = None;
+        for level in 0..=fuzz {
+            let head = level.min(leading);
+            let tail = level.min(trailing).min(old.len() - head);
+            if level > 0 && head + tail == 0 {
+                break;
+            }
+            let pattern = &old[head..old.len() - tail];
+            if pattern.is_empty() && !old.is_empty() {
+                break;
+            }
+            if let Some(pos) = find_block(&lines, pattern, cursor, expected + head) {
+                found = Some((pos, head, tail, level));
+                break;
+            }
+        }
+
+        let Some((pos, head, tail, level)) = found else {
+            failures.push(HunkFailure {
+                index: index + 1,
+                header: hunk.header.clone(),
+                closest: closest_match(&lines, &old, expected),
+            });
+            continue;
+        };
+
+        let old_len = old.len() - head - tail;
+        let replacement: Vec<String> = new[head..new.len() - tail]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let offset = pos as isize - (expected + head) as isize;
+        if offset != 0 || level > 0 {
+            adjusted.push((index + 1, pos + 1, offset, level));
+        }
+        if pos + old_len == lines.len() && tail == 0 {
+            if hunk.new_no_newline {
+                trailing_newline = false;
+            } else if hunk.old_no_newline {
+                trailing_newline = true;
+            }
+        }
+
+        let added = replacement.len();
+        lines.splice(pos..pos + old_len, replacement);
+        cursor = pos + added;
+        shift += offset + added as isize - old_len as isize;
+    }
+
+    if !failures.is_empty() {
+        return Err(failures);
+    }
+
+    let separator = if crlf { "\r\n" } else { "\n" };
+    let mut content = lines.join(separator);
+    if trailing_newline && !lines.is_empty() {
+        content.push_str(separator);
+    }
+    Ok(Patched { content, adjusted })
+}
+
+/// 在 `from` 之后查找与 pattern 相同的连续行，优先离 `near` 最近的位置；
+/// 找不到时再忽略行尾空白比较
+fn find_block(lines: &[String], pattern: &[&str], from: usize, near: usize) -> Option<usize> {
+    if pattern.is_empty() {
+        return Some(near.clamp(from, lines.len()));
+    }
+    if lines.len() < pattern.len() || from > lines.len() - pattern.len() {
+        return None;
+    }
+    let last = lines.len() - pattern.len();
+    let near = near.clamp(from, last);
+    let mut candidates = (from..=last).collect::<Vec<_>>();
+    candidates.sort_by_key(|pos| pos.abs_diff(near));
+
+    let exact = |pos: &usize| {
+        pattern
+            .iter()
+            .enumerate()
+            .all(|(k, line)| lines[pos + k] == *line)
+    };
+    let loose = |pos: &usize| {
+        pattern
+            .iter()
+            .enumerate()
+            .all(|(k, line)| lines[pos + k].trim_end() == line.trim_end())
+    };
+    candidates
+        .iter()
+        .copied()
+        .find(exact)
+        .or_else(|| candidates.iter().copied().find(loose))
+}
+
+/// 与 hunk 原内容逐行比较相同行数最多的区域
+fn closest_match(lines: &[String], old: &[&str], near: usize) -> Option<ClosestMatch> {
+    if old.is_empty() || lines.is_empty() {
+        return None;
+    }
+    let window = old.len().min(lines.len());
+    let mut best: Option<(usize, usize)> = None;
+    for pos in 0..=lines.len() - window {
+        let equal = (0..window)
+            .filter(|&k| lines[pos + k].trim() == old[k].trim())
+            .count();
+        let better = match best {
+            None => equal > 0,
+            Some((best_pos, best_equal)) => {
+                equal > best_equal
+                    || (equal == best_equal && pos.abs_diff(near) < best_pos.abs_diff(near))
+            }
+        };
+        if better {
+            best = Some((pos, equal));
+        }
+    }
+    best.map(|(pos, equal)| ClosestMatch {
+        line: pos + 1,
+        lines: lines[pos..pos + window].to_vec(),
+        equal,
+        total: old.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTI_FILE: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 3b18e51..a5c1f2e 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    println!(\"hi\");
++    println!(\"hello\");
+ }
+
+diff --git a/notes.txt b/notes.txt
+new file mode 100644
+--- /dev/null
++++ b/notes.txt
+@@ -0,0 +1,2 @@
++first
++second
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+";
+
+    #[test]
+    fn test_parse_multi_file_patch() {
+        let files = parse_patch(MULTI_FILE).unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].path(), "src/lib.rs");
+        assert_eq!(files[0].hunks[0].lines.len(), 4);
+        assert_eq!(files[0].line_counts(), (1, 1));
+        assert_eq!(files[1].old_path, None);
+        assert_eq!(files[1].path(), "notes.txt");
+        assert_eq!(files[2].new_path, None);
+        assert_eq!(files[2].path(), "old.txt");
+
+        // 不带 a/ b/ 前缀、没有 diff 头的普通 diff
+        let plain = parse_patch("--- x.txt\n+++ x.txt\n@@ -1 +1 @@\n-a\n+b\n").unwrap();
+        assert_eq!(plain[0].path(), "x.txt");
+
+        assert!(parse_patch("@@ -1 +1 @@\n-a\n+b\n").is_err());
+    }
+
+    #[test]
+    fn test_apply_with_offset_and_fuzz() {
+        let content = "header\nextra\nfn main() {\n    println!(\"hi\");\n}\n";
+        let hunks = &parse_patch(MULTI_FILE).unwrap()[0].hunks;
+
+        // 文件开头多了一行，hunk 向下偏移一行
+        let patched = apply_hunks(content, hunks, 0).unwrap();
+        assert_eq!(
+            patched.content,
+            "header\nextra\nfn main() {\n    println!(\"hello\");\n}\n"
+        );
+        assert_eq!(patched.adjusted, vec![(1, 3, 2, 0)]);
+
+        // 上下文首行不一致，需要 fuzz 才能应用
+        let drifted = "fn main() -> Result<()> {\n    println!(\"hi\");\n}\n";
+        assert!(apply_hunks(drifted, hunks, 0).is_err());
+        let patched = apply_hunks(drifted, hunks, 1).unwrap();
+        assert!(patched.content.contains("hello"));
+        assert_eq!(patched.adjusted[0].3, 1);
+
+        let created = apply_hunks("", &parse_patch(MULTI_FILE).unwrap()[1].hunks, 0).unwrap();
+        assert_eq!(created.content, "first\nsecond\n");
+    }
+
+    #[test]
+    fn test_failed_hunk_reports_closest_region() {
+        let hunks = &parse_patch(MULTI_FILE).unwrap()[0].hunks;
+        let content = "fn main() {\n    println!(\"bye\");\n}\n";
+        let failures = apply_hunks(content, hunks, 0).unwrap_err();
+        assert_eq!(failures.len(), 1);
+        let closest = failures[0].closest.as_ref().unwrap();
+        assert_eq!(closest.line, 1);
+        assert_eq!((closest.equal, closest.total), (2, 3));
+    }
+
+    #[test]
+    fn test_crlf_and_missing_newline() {
+        let patch = "--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+c\n";
+        let hunks = &parse_patch(patch).unwrap()[0].hunks;
+        let patched = apply_hunks("a\r\nb", hunks, 0).unwrap();
+        assert_eq!(patched.content, "a\r\nc\r\n");
+    }
+}
@@ -0,0 +1,63 @@
+//! 记录模型最后一次看到的文件内容，用于在编辑前发现文件已被外部修改
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::tools::utils::content_hash;
+
+/// 路径 -> 模型最后一次读取或写入时的内容哈希
+fn seen() -> &'static Mutex<HashMap<PathBuf, u64>> {
+    static SEEN: OnceLock<Mutex<HashMap<PathBuf, u64>>> = OnceLock::new();
+    SEEN.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 同一文件的不同写法（`./a.rs`、`a.rs`）使用同一条记录
+fn key(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// 记录模型已看到的内容哈希
+pub fn record_hash(path: &Path, hash: u64) {
+    seen().lock().unwrap().insert(key(path), hash);
+}
+
+/// 工具写入文件后记录其当前内容，之后的编辑不会误报
+pub fn record_file(path: &Path) {
+    match std::fs::read(path) {
+        Ok(content) => record_hash(path, content_hash(&content)),
+        Err(_) => {
+            seen().lock().unwrap().remove(&key(path));
+        }
+    }
+}
+
+/// 文件在模型上次读取后是否被修改过；从未读取过的文件返回 false
+pub fn changed_since_read(path: &Path, current: &[u8]) -> bool {
+    seen()
+        .lock()
+        .unwrap()
+        .get(&key(path))
+        .is_some_and(|hash| *hash != content_hash(current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::utils::ContentHasher;
+
+    #[test]
+    fn test_changed_since_read() {
+        let path = Path::new("/friendev/read_tracker/a.rs");
+        assert!(!changed_since_read(path, b"anything"));
+
+        // file_read 逐行累加哈希
+        let mut hasher = ContentHasher::new();
+        hasher.update(b"fn a() {}\n");
+        hasher.update(b"fn b() {}\n");
+        record_hash(path, hasher.finish());
+
+        assert!(!changed_since_read(path, b"fn a() {}\nfn b() {}\n"));
+        assert!(changed_since_read(path, b"fn a() {}\n"));
+    }
+}
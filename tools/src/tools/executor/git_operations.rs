@@ -0,0 +1,162 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::tools::args::{GitDiffArgs, GitLogArgs, GitStatusArgs};
+use crate::tools::git::{check_revision, repo_root, run_git};
+use crate::types::ToolResult;
+use ui::get_i18n;
+
+/// 输出超过该大小时截断
+const MAX_OUTPUT_BYTES: usize = 100 * 1024;
+
+/// max_count 的上限
+const MAX_LOG_COUNT: usize = 200;
+
+pub async fn execute_git_status(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    let args: GitStatusArgs = serde_json::from_str(arguments)?;
+    let mut git_args = vec!["status", "--short", "--branch"];
+    push_path(&mut git_args, args.path.as_deref());
+
+    run(working_dir, git_args, |output| {
+        let i18n = get_i18n();
+        let changes = output
+            .lines()
+            .filter(|line| !line.starts_with("##"))
+            .count();
+        if changes == 0 {
+            let mut output = output.to_string();
+            output.push_str(&i18n.get("git_status_clean"));
+            (i18n.get("git_status_clean"), output)
+        } else {
+            let brief = i18n
+                .get("git_status_brief")
+                .replace("{}", &changes.to_string());
+            (brief, output.to_string())
+        }
+    })
+    .await
+}
+
+pub async fn execute_git_diff(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    let args: GitDiffArgs = serde_json::from_str(arguments)?;
+    let mut git_args = vec!["diff"];
+    if args.staged {
+        git_args.push("--cached");
+    }
+    if args.stat {
+        git_args.push("--stat");
+    }
+    if let Some(base) = args.base.as_deref() {
+        if let Err(e) = check_revision(base) {
+            return Ok(ToolResult::error(e.to_string()));
+        }
+        git_args.push(base);
+    }
+    push_path(&mut git_args, args.path.as_deref());
+
+    run(working_dir, git_args, |output| {
+        let i18n = get_i18n();
+        if output.trim().is_empty() {
+            return (i18n.get("git_diff_empty"), i18n.get("git_diff_empty"));
+        }
+        let files = if args.stat {
+            output.lines().count().saturating_sub(1)
+        } else {
+            output
+                .lines()
+                .filter(|line| line.starts_with("diff --git "))
+                .count()
+        };
+        let brief = i18n.get("git_diff_brief").replace("{}", &files.to_string());
+        (brief, output.to_string())
+    })
+    .await
+}
+
+pub async fn execute_git_log(arguments: &str, working_dir: &Path) -> Result<ToolResult> {
+    let args: GitLogArgs = serde_json::from_str(arguments)?;
+    let max_count = format!("--max-count={}", args.max_count.clamp(1, MAX_LOG_COUNT));
+    let mut git_args = vec![
+        "log",
+        max_count.as_str(),
+        "--date=short",
+        "--format=%h %ad %an%d%n    %s",
+    ];
+    if let Some(revision) = args.revision.as_deref() {
+        if let Err(e) = check_revision(revision) {
+            return Ok(ToolResult::error(e.to_string()));
+        }
+        git_args.push(revision);
+    }
+    push_path(&mut git_args, args.path.as_deref());
+
+    run(working_dir, git_args, |output| {
+        let i18n = get_i18n();
+        if output.trim().is_empty() {
+            return (i18n.get("git_log_empty"), i18n.get("git_log_empty"));
+        }
+        let commits = output.lines().filter(|line| !line.starts_with(' ')).count();
+        let brief = i18n
+            .get("git_log_brief")
+            .replace("{}", &commits.to_string());
+        (brief, output.to_string())
+    })
+    .await
+}
+
+/// 路径放在 `--` 之后，避免被当作选项或提交
+fn push_path<'a>(git_args: &mut Vec<&'a str>, path: Option<&'a str>) {
+    if let Some(path) = path.filter(|path| !path.is_empty()) {
+        git_args.push("--");
+        git_args.push(path);
+    }
+}
+
+/// 在工作目录运行 git，把输出交给 `summarize` 生成 (brief, message)
+async fn run(
+    working_dir: &Path,
+    git_args: Vec<&str>,
+    summarize: impl FnOnce(&str) -> (String, String),
+) -> Result<ToolResult> {
+    let i18n = get_i18n();
+    if repo_root(working_dir).is_none() {
+        let tmpl = i18n.get("git_not_repository");
+        return Ok(ToolResult::error(
+            tmpl.replace("{}", &working_dir.display().to_string()),
+        ));
+    }
+
+    let dir = working_dir.to_path_buf();
+    let git_args: Vec<String> = git_args.into_iter().map(String::from).collect();
+    let output = tokio::task::spawn_blocking(move || {
+        let git_args: Vec<&str> = git_args.iter().map(String::as_str).collect();
+        run_git(&dir, &git_args)
+    })
+    .await?;
+    let mut output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            let tmpl = i18n.get("git_failed");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
+
+    let truncated = output.len() > MAX_OUTPUT_BYTES;
+    if truncated {
+        let mut end = MAX_OUTPUT_BYTES;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+    }
+    let (brief, mut message) = summarize(&output);
+    if truncated {
+        message.push_str("\n\n");
+        message.push_str(
+            &i18n
+                .get("git_output_truncated")
+                .replace("{}", &(MAX_OUTPUT_BYTES / 1024).to_string()),
+        );
+    }
+    Ok(ToolResult::ok(brief, message))
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use super::command_operations::{approve_command, command_cwd, CommandRequest};
+use super::command_runner::{OutputCap, OUTPUT_HEAD_BYTES, OUTPUT_TAIL_BYTES};
+use crate::tools::args::{PtyCloseArgs, PtySendArgs, PtyStartArgs};
+use crate::tools::command_manager::CommandConfig;
+use crate::tools::pty::{self, PtyOutput};
+use crate::types::ToolResult;
+use ui::{get_i18n, ToolProgress};
+
+/// pty_start 等待程序首次输出静止的时间
+const START_QUIET: Duration = Duration::from_millis(500);
+const START_MAX_WAIT: Duration = Duration::from_secs(10);
+
+/// pty_send 最长等待时间上限（秒）
+const MAX_SEND_TIMEOUT_SECS: u64 = 120;
+
+pub async fn execute_pty_start(
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    let args: PtyStartArgs = serde_json::from_str(arguments)?;
+    pty::close_idle();
+    let cwd = match command_cwd(args.cwd.as_deref(), working_dir) {
+        Ok(cwd) => cwd,
+        Err(rejected) => return Ok(rejected),
+    };
+
+    let config = CommandConfig::load()?;
+    let request = CommandRequest {
+        command: &args.command,
+        mode: "pty",
+        cwd: Some(&cwd),
+        env: &args.env,
+        timeout_secs: None,
+    };
+    if let Some(rejected) = approve_command(&config, &request, require_approval)? {
+        return Ok(rejected);
+    }
+
+    let i18n = get_i18n();
+    let rows = args.rows.unwrap_or(pty::DEFAULT_ROWS).clamp(10, 200);
+    let cols = args.cols.unwrap_or(pty::DEFAULT_COLS).clamp(40, 400);
+    let id = match pty::start(&args.command, &cwd, &args.env, rows, cols) {
+        Ok(id) => id,
+        Err(e) => {
+            let tmpl = i18n.get("pty_start_failed");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    };
+
+    let output = pty::read_until_quiet(id, START_QUIET, START_MAX_WAIT).await?;
+    let brief = i18n.get("pty_started").replace("{}", &id.to_string());
+    let message = format!(
+        "{}\n\n{}",
+        i18n.get("pty_started_details")
+            .replacen("{}", &id.to_string(), 1)
+            .replacen("{}", &args.command, 1),
+        describe_output(id, &output, false)
+    );
+    Ok(ToolResult::ok(brief, message))
+}
+
+pub async fn execute_pty_send(arguments: &str, require_approval: bool) -> Result<ToolResult> {
+    let args: PtySendArgs = serde_json::from_str(arguments)?;
+    pty::close_idle();
+    let i18n = get_i18n();
+    if !pty::exists(args.id) {
+        return Ok(not_found(args.id));
+    }
+
+    // 文本中的换行按回车键发送，终端程序据此提交输入
+    let mut input = args
+        .input
+        .replace("\r\n", "\n")
+        .replace('\n', "\r")
+        .into_bytes();
+    for key in &args.keys {
+        match pty::key_bytes(key) {
+            Some(bytes) => input.extend(bytes),
+            None => {
+                return Ok(ToolResult::error(
+                    i18n.get("pty_unknown_key").replace("{}", key),
+                ))
+            }
+        }
+    }
+
+    // 输入可能是交给 shell 或数据库执行的命令，与 run_command 同样审批
+    let typed = args.input.trim();
+    if !typed.is_empty() {
+        let config = CommandConfig::load()?;
+        let request = CommandRequest {
+            command: typed,
+            mode: &format!("pty session {}", args.id),
+            cwd: None,
+            env: &HashMap::new(),
+            timeout_secs: None,
+        };
+        if let Some(rejected) = approve_command(&config, &request, require_approval)? {
+            return Ok(rejected);
+        }
+    }
+
+    if !input.is_empty() {
+        if let Err(e) = pty::write(args.id, &input) {
+            let tmpl = i18n.get("pty_write_failed");
+            return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+        }
+    }
+
+    let quiet = Duration::from_millis(args.quiet_ms.clamp(50, 10_000));
+    let max_wait = Duration::from_secs(args.timeout_secs.clamp(1, MAX_SEND_TIMEOUT_SECS));
+    let output = pty::read_until_quiet(args.id, quiet, max_wait).await?;
+    for line in output.text.lines().filter(|line| !line.trim().is_empty()) {
+        let _ = ToolProgress::output_line(line);
+    }
+
+    let brief = match output.exit_code {
+        Some(code) => i18n
+            .get("pty_exited")
+            .replacen("{}", &args.id.to_string(), 1)
+            .replacen("{}", &code.to_string(), 1),
+        None => i18n
+            .get("pty_send_brief")
+            .replacen("{}", &args.id.to_string(), 1)
+            .replacen("{}", &output.text.lines().count().to_string(), 1),
+    };
+    Ok(ToolResult::ok(
+        brief,
+        describe_output(args.id, &output, args.screen),
+    ))
+}
+
+pub fn execute_pty_close(arguments: &str) -> Result<ToolResult> {
+    let args: PtyCloseArgs = serde_json::from_str(arguments)?;
+    pty::close_idle();
+    let Ok(output) = pty::close(args.id) else {
+        return Ok(not_found(args.id));
+    };
+    let brief = get_i18n()
+        .get("pty_closed")
+        .replace("{}", &args.id.to_string());
+    let message = format!("{}\n\n{}", brief, describe_output(args.id, &output, false));
+    Ok(ToolResult::ok(brief, message))
+}
+
+fn not_found(id: u32) -> ToolResult {
+    let minutes = pty::IDLE_TIMEOUT.as_secs() / 60;
+    ToolResult::error(
+        get_i18n()
+            .get("pty_not_found")
+            .replacen("{}", &id.to_string(), 1)
+            .replacen("{}", &minutes.to_string(), 1),
+    )
+}
+
+/// 返回给模型的输出：新输出（过长时截去中间），全屏程序或按需附带屏幕内容
+fn describe_output(id: u32, output: &PtyOutput, screen: bool) -> String {
+    let i18n = get_i18n();
+    let mut cap = OutputCap::new(OUTPUT_HEAD_BYTES, OUTPUT_TAIL_BYTES);
+    for line in output.text.lines() {
+        cap.push_line(line);
+    }
+    let text = cap.finish();
+    let text = if text.trim().is_empty() {
+        i18n.get("pty_no_output")
+    } else {
+        text
+    };
+
+    let mut sections = vec![i18n.get("pty_output").replace("{}", text.trim_end())];
+    if screen || output.alternate_screen {
+        sections.push(
+            i18n.get("pty_screen")
+                .replace("{}", output.screen.trim_end()),
+        );
+    }
+    if let Some(code) = output.exit_code {
+        sections.push(
+            i18n.get("pty_exited")
+                .replacen("{}", &id.to_string(), 1)
+                .replacen("{}", &code.to_string(), 1),
+        );
+    }
+    sections.join("\n\n")
+}
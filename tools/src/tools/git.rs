@@ -0,0 +1,134 @@
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run git in `dir` and return its stdout; fails with git's stderr on a non-zero exit
+pub fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        // Plain output regardless of the user's color and pager settings
+        .args(["-c", "color.ui=false", "--no-pager"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Cannot run git")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Top-level directory of the repository containing `dir`, `None` outside a repository
+pub fn repo_root(dir: &Path) -> Option<PathBuf> {
+    run_git(dir, &["rev-parse", "--show-toplevel"])
+        .ok()
+        .map(|root| PathBuf::from(root.trim()))
+}
+
+/// Reject revisions that git would parse as an option
+pub fn check_revision(revision: &str) -> Result<()> {
+    if revision.is_empty() || revision.starts_with('-') {
+        bail!("Invalid revision: {}", revision);
+    }
+    Ok(())
+}
+
+/// Diff of the staged changes
+pub fn staged_diff(dir: &Path) -> Result<String> {
+    run_git(dir, &["diff", "--cached"])
+}
+
+/// Commit the staged changes and return the short hash of the new commit
+pub fn commit(dir: &Path, message: &str) -> Result<String> {
+    run_git(dir, &["commit", "--quiet", "-m", message])?;
+    head_commit(dir)
+}
+
+/// Stage `paths` (including deletions), skipping ignored files and files outside the
+/// repository, and return the diff of what was staged for them
+pub fn stage_paths(root: &Path, paths: &[PathBuf]) -> Result<(Vec<String>, String)> {
+    let mut relative: Vec<String> = paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(root).ok())
+        .filter(|path| !path.starts_with(".git"))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    if relative.is_empty() {
+        return Ok((relative, String::new()));
+    }
+
+    // check-ignore exits with 1 when no path is ignored
+    let mut args = vec!["check-ignore", "--"];
+    args.extend(relative.iter().map(String::as_str));
+    let ignored = run_git(root, &args).unwrap_or_default();
+    relative.retain(|path| !ignored.lines().any(|ignored| ignored == path));
+    if relative.is_empty() {
+        return Ok((relative, String::new()));
+    }
+
+    let mut add = vec!["add", "--all", "--"];
+    add.extend(relative.iter().map(String::as_str));
+    run_git(root, &add)?;
+
+    let mut diff = vec!["diff", "--cached", "--"];
+    diff.extend(relative.iter().map(String::as_str));
+    let diff = run_git(root, &diff)?;
+    Ok((relative, diff))
+}
+
+/// Commit only `paths` (already staged with `stage_paths`), leaving other staged changes
+/// for the user, and return the short hash of the new commit
+pub fn commit_paths(root: &Path, paths: &[String], message: &str) -> Result<String> {
+    let mut args = vec!["commit", "--quiet", "-m", message, "--"];
+    args.extend(paths.iter().map(String::as_str));
+    run_git(root, &args)?;
+    head_commit(root)
+}
+
+fn head_commit(dir: &Path) -> Result<String> {
+    Ok(run_git(dir, &["rev-parse", "--short", "HEAD"])?
+        .trim()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_stage_and_commit_paths() {
+        let dir = std::env::temp_dir().join(format!("friendev_git_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str]| run_git(&dir, args).unwrap();
+        git(&["init", "--quiet"]);
+        git(&["config", "user.name", "Friendev"]);
+        git(&["config", "user.email", "friendev@example.com"]);
+        fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.join("a.txt"), "a\n").unwrap();
+        fs::write(dir.join("other.txt"), "other\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "init"]);
+
+        // The user's own staged change stays staged
+        fs::write(dir.join("other.txt"), "changed by the user\n").unwrap();
+        git(&["add", "other.txt"]);
+        fs::write(dir.join("a.txt"), "b\n").unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("target/out"), "build\n").unwrap();
+
+        let root = repo_root(&dir).unwrap();
+        let paths = [root.join("a.txt"), root.join("target/out")];
+        let (staged, diff) = stage_paths(&root, &paths).unwrap();
+        assert_eq!(staged, vec!["a.txt".to_string()]);
+        assert!(diff.contains("+b"));
+
+        commit_paths(&root, &staged, "Update a").unwrap();
+        assert_eq!(git(&["log", "-1", "--format=%s"]).trim(), "Update a");
+        assert_eq!(
+            git(&["diff", "--cached", "--name-only"]).trim(),
+            "other.txt"
+        );
+        assert!(check_revision("--output=x").is_err());
+    }
+}
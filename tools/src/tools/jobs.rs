@@ -0,0 +1,289 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use config::Config;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use ui::get_i18n;
+
+use super::executor::command_operations::shell_command;
+use super::executor::command_runner::kill_process_tree;
+
+/// Background jobs started in this process
+static JOBS: Mutex<Vec<Job>> = Mutex::new(Vec::new());
+
+/// Notices about finished jobs, not yet shown to the model
+static NOTICES: Mutex<Vec<JobNotice>> = Mutex::new(Vec::new());
+
+/// Log files older than this are removed when a job starts
+const LOG_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Most bytes read from the end of a log when tailing it
+const MAX_TAIL_BYTES: u64 = 64 * 1024;
+
+/// Log lines included in a completion notice
+const NOTICE_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Running,
+    /// Exited on its own; no code when ended by a signal
+    Exited(Option<i32>),
+    /// Stopped with `/jobs kill` or `command_kill`
+    Killed,
+}
+
+/// A command running detached from the conversation, with its output in a log file
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: u32,
+    pub command: String,
+    pub cwd: PathBuf,
+    pub pid: Option<u32>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub status: JobStatus,
+    /// stdout and stderr, written as the command runs
+    pub log_path: PathBuf,
+}
+
+impl Job {
+    pub fn is_running(&self) -> bool {
+        self.status == JobStatus::Running
+    }
+
+    pub fn status_text(&self) -> String {
+        let i18n = get_i18n();
+        match self.status {
+            JobStatus::Running => i18n.get("job_status_running"),
+            JobStatus::Exited(Some(code)) => i18n
+                .get("job_status_exited")
+                .replace("{}", &code.to_string()),
+            JobStatus::Exited(None) => i18n.get("job_status_signaled"),
+            JobStatus::Killed => i18n.get("job_status_killed"),
+        }
+    }
+}
+
+/// A job that finished, with the message telling the model about it
+#[derive(Debug, Clone)]
+pub struct JobNotice {
+    pub job: Job,
+    pub message: String,
+}
+
+/// Result of `kill`
+#[derive(Debug)]
+pub enum KillOutcome {
+    Killed(Job),
+    /// The job had already finished
+    NotRunning(Job),
+    NotFound,
+}
+
+/// Start a shell command in the background; its output goes to a new log file
+pub fn spawn(command: &str, cwd: &Path, env: &HashMap<String, String>) -> Result<Job> {
+    let dir = logs_dir()?;
+    fs::create_dir_all(&dir)?;
+    prune_logs(&dir);
+
+    let mut jobs = JOBS.lock().unwrap();
+    let id = jobs.last().map_or(1, |job| job.id + 1);
+    let log_path = dir.join(format!("{}-{}.log", std::process::id(), id));
+    let log = File::create(&log_path)?;
+
+    let mut cmd = shell_command(command);
+    cmd.current_dir(cwd)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .kill_on_drop(true);
+    // Own process group, so killing the job also stops what it started
+    #[cfg(unix)]
+    cmd.process_group(0);
+    let mut child = cmd.spawn()?;
+
+    let job = Job {
+        id,
+        command: command.to_string(),
+        cwd: cwd.to_path_buf(),
+        pid: child.id(),
+        started_at: Utc::now(),
+        finished_at: None,
+        status: JobStatus::Running,
+        log_path,
+    };
+    jobs.push(job.clone());
+    drop(jobs);
+
+    tokio::spawn(async move {
+        let code = child.wait().await.ok().and_then(|status| status.code());
+        finish(id, code);
+    });
+    Ok(job)
+}
+
+/// Record the exit of a job and queue a notice unless it was killed
+fn finish(id: u32, code: Option<i32>) {
+    let finished = {
+        let mut jobs = JOBS.lock().unwrap();
+        let Some(job) = jobs.iter_mut().find(|job| job.id == id) else {
+            return;
+        };
+        if !job.is_running() {
+            return;
+        }
+        job.status = JobStatus::Exited(code);
+        job.finished_at = Some(Utc::now());
+        job.clone()
+    };
+    push_notice(&finished);
+}
+
+fn push_notice(job: &Job) {
+    let tail = tail(job, NOTICE_TAIL_LINES).unwrap_or_default();
+    let message = get_i18n()
+        .get("job_notice")
+        .replacen("{}", &job.id.to_string(), 1)
+        .replacen("{}", &job.command, 1)
+        .replacen("{}", &job.status_text(), 1)
+        .replacen("{}", tail.trim_end(), 1);
+    NOTICES.lock().unwrap().push(JobNotice {
+        job: job.clone(),
+        message,
+    });
+}
+
+/// All jobs of this process, oldest first
+pub fn list() -> Vec<Job> {
+    JOBS.lock().unwrap().clone()
+}
+
+pub fn get(id: u32) -> Option<Job> {
+    JOBS.lock()
+        .unwrap()
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+}
+
+/// Stop a running job and everything it started. With `notify` the model is told about
+/// it on its next request, as for jobs that exit on their own
+pub fn kill(id: u32, notify: bool) -> KillOutcome {
+    let killed = {
+        let mut jobs = JOBS.lock().unwrap();
+        let Some(job) = jobs.iter_mut().find(|job| job.id == id) else {
+            return KillOutcome::NotFound;
+        };
+        if !job.is_running() {
+            return KillOutcome::NotRunning(job.clone());
+        }
+        if let Some(pid) = job.pid {
+            kill_process_tree(pid);
+        }
+        job.status = JobStatus::Killed;
+        job.finished_at = Some(Utc::now());
+        job.clone()
+    };
+    if notify {
+        push_notice(&killed);
+    }
+    KillOutcome::Killed(killed)
+}
+
+/// Stop every running job, e.g. when the app exits
+pub fn kill_all() {
+    for job in list().iter().filter(|job| job.is_running()) {
+        kill(job.id, false);
+    }
+}
+
+/// Take the queued notices about finished jobs
+pub fn take_notices() -> Vec<JobNotice> {
+    std::mem::take(&mut *NOTICES.lock().unwrap())
+}
+
+/// The last `lines` lines of a job's log
+pub fn tail(job: &Job, lines: usize) -> Result<String> {
+    let mut file = File::open(&job.log_path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(MAX_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let mut all: Vec<&str> = text.lines().collect();
+    // The first line may be cut when only the end of the file was read
+    if start > 0 && !all.is_empty() {
+        all.remove(0);
+    }
+    let skip = all.len().saturating_sub(lines);
+    let mut tail = all[skip..].join("\n");
+    if !tail.is_empty() {
+        tail.push('\n');
+    }
+    Ok(tail)
+}
+
+fn logs_dir() -> Result<PathBuf> {
+    Ok(Config::config_dir()?.join("jobs"))
+}
+
+/// Remove logs of jobs from earlier runs once they are old
+fn prune_logs(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let now = SystemTime::now();
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| {
+                now.duration_since(modified)
+                    .is_ok_and(|age| age > LOG_RETENTION)
+            });
+        if expired {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_reads_last_lines() {
+        let path =
+            std::env::temp_dir().join(format!("friendev_job_tail_{}.log", std::process::id()));
+        let lines: Vec<String> = (0..20_000).map(|i| format!("line {}", i)).collect();
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        let job = Job {
+            id: 1,
+            command: "build".to_string(),
+            cwd: std::env::temp_dir(),
+            pid: None,
+            started_at: Utc::now(),
+            finished_at: None,
+            status: JobStatus::Running,
+            log_path: path.clone(),
+        };
+
+        assert_eq!(tail(&job, 2).unwrap(), "line 19998\nline 19999\n");
+        // Only the end of a large log is read, starting at a whole line
+        let all = tail(&job, usize::MAX).unwrap();
+        assert!(all.len() as u64 <= MAX_TAIL_BYTES);
+        assert!(all.starts_with("line "));
+
+        fs::write(&path, "").unwrap();
+        assert_eq!(tail(&job, 5).unwrap(), "");
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -1,12 +1,21 @@
 pub mod args;
+pub mod builtin;
+pub mod checkpoint;
 pub mod command_manager;
-pub mod definitions;
 pub mod executor;
+pub mod git;
+pub mod jobs;
+pub mod pty;
+pub mod registry;
+pub mod script;
 pub mod types;
 pub mod utils;
 
-pub use self::definitions::get_available_tools;
 pub use command_manager::CommandConfig;
-pub use executor::execute_tool;
-pub use types::{Tool, ToolFunction, ToolResult};
-pub use utils::get_tools_description;
+pub use registry::{
+    can_run_concurrently, execute_tool, get_available_tools, get_tools_description,
+    is_arguments_complete, is_read_only_tool, register_tool, registry, unregister_tool,
+    ApprovalPolicy, Tool, ToolContext, ToolRegistry,
+};
+pub use script::{load_script_tools, register_script_tools, ScriptTool, SCRIPT_TOOLS_FILE};
+pub use types::{ToolDefinition, ToolFunction, ToolResult};
@@ -0,0 +1,461 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use ui::get_i18n;
+
+use super::executor::command_runner::{OUTPUT_HEAD_BYTES, OUTPUT_TAIL_BYTES};
+
+/// Interactive sessions started in this process
+static SESSIONS: Mutex<Vec<PtySession>> = Mutex::new(Vec::new());
+
+/// Sessions without any input for this long are closed
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+pub const DEFAULT_ROWS: u16 = 24;
+pub const DEFAULT_COLS: u16 = 120;
+
+/// How often the output is checked while waiting for it to settle
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A program running in a pseudo terminal, driven through `pty_send`
+struct PtySession {
+    id: u32,
+    command: String,
+    started_at: DateTime<Utc>,
+    last_used: Instant,
+    // Kept open for the lifetime of the session; dropping it hangs up the terminal
+    _master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn portable_pty::Child + Send + Sync>,
+    output: Arc<Mutex<SessionOutput>>,
+}
+
+/// Output collected by the reader thread
+struct SessionOutput {
+    /// Bytes not yet returned to the model
+    pending: PendingOutput,
+    /// Terminal emulator holding the current screen
+    parser: vt100::Parser,
+    last_output: Instant,
+    /// The terminal was closed, no more output will come
+    eof: bool,
+}
+
+/// Summary of a session for listings
+#[derive(Debug, Clone)]
+pub struct PtyInfo {
+    pub id: u32,
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// What a session printed since the last read
+#[derive(Debug, Clone)]
+pub struct PtyOutput {
+    /// New output as plain text, without escape sequences
+    pub text: String,
+    /// The visible screen, as a terminal would show it
+    pub screen: String,
+    /// Full-screen programs (editors, pagers, debugger UIs) draw on the alternate screen,
+    /// where the new output alone is hard to follow
+    pub alternate_screen: bool,
+    /// Exit code once the program has ended; the session is closed then
+    pub exit_code: Option<u32>,
+}
+
+/// Start a command in a new pseudo terminal and return the session id
+pub fn start(
+    command: &str,
+    cwd: &Path,
+    env: &HashMap<String, String>,
+    rows: u16,
+    cols: u16,
+) -> Result<u32> {
+    let pair = native_pty_system().openpty(PtySize {
+        rows,
+        cols,
+        pixel_width: 0,
+        pixel_height: 0,
+    })?;
+
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = CommandBuilder::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    cmd.cwd(cwd);
+    cmd.env("TERM", "xterm-256color");
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
+    let child = pair.slave.spawn_command(cmd)?;
+    // Only the child keeps the terminal open, so its exit ends the output
+    drop(pair.slave);
+
+    let mut reader = pair.master.try_clone_reader()?;
+    let writer = pair.master.take_writer()?;
+    let output = Arc::new(Mutex::new(SessionOutput {
+        pending: PendingOutput::new(OUTPUT_HEAD_BYTES, OUTPUT_TAIL_BYTES),
+        parser: vt100::Parser::new(rows, cols, 0),
+        last_output: Instant::now(),
+        eof: false,
+    }));
+
+    let shared = Arc::clone(&output);
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        loop {
+            let read = reader.read(&mut buf).unwrap_or(0);
+            let mut output = shared.lock().unwrap();
+            if read == 0 {
+                output.eof = true;
+                break;
+            }
+            output.pending.push(&buf[..read]);
+            output.parser.process(&buf[..read]);
+            output.last_output = Instant::now();
+        }
+    });
+
+    let mut sessions = SESSIONS.lock().unwrap();
+    let id = sessions.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+    sessions.push(PtySession {
+        id,
+        command: command.to_string(),
+        started_at: Utc::now(),
+        last_used: Instant::now(),
+        _master: pair.master,
+        writer,
+        child,
+        output,
+    });
+    Ok(id)
+}
+
+pub fn exists(id: u32) -> bool {
+    SESSIONS.lock().unwrap().iter().any(|s| s.id == id)
+}
+
+/// Open sessions, oldest first
+pub fn list() -> Vec<PtyInfo> {
+    SESSIONS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|s| PtyInfo {
+            id: s.id,
+            command: s.command.clone(),
+            started_at: s.started_at,
+        })
+        .collect()
+}
+
+/// Type into a session
+pub fn write(id: u32, input: &[u8]) -> Result<()> {
+    let mut sessions = SESSIONS.lock().unwrap();
+    let session = sessions
+        .iter_mut()
+        .find(|s| s.id == id)
+        .ok_or_else(|| anyhow!("No PTY session {}", id))?;
+    session.last_used = Instant::now();
+    session.writer.write_all(input)?;
+    session.writer.flush()?;
+    Ok(())
+}
+
+/// Wait until the session has printed nothing for `quiet` (or `max_wait` passed, or the
+/// program ended) and return what it printed. A session whose program ended is closed
+pub async fn read_until_quiet(id: u32, quiet: Duration, max_wait: Duration) -> Result<PtyOutput> {
+    let started = Instant::now();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let (settled, exited) = {
+            let mut sessions = SESSIONS.lock().unwrap();
+            let session = sessions
+                .iter_mut()
+                .find(|s| s.id == id)
+                .ok_or_else(|| anyhow!("No PTY session {}", id))?;
+            let exited = session.child.try_wait()?.is_some();
+            let output = session.output.lock().unwrap();
+            let now = Instant::now();
+            let settled = now.duration_since(started) >= quiet
+                && now.duration_since(output.last_output) >= quiet;
+            (settled, exited && output.eof)
+        };
+        if settled || exited || started.elapsed() >= max_wait {
+            break;
+        }
+    }
+    take_output(id, false)
+}
+
+/// End a session and return its last output
+pub fn close(id: u32) -> Result<PtyOutput> {
+    take_output(id, true)
+}
+
+/// Close every session, e.g. when the app exits
+pub fn close_all() {
+    for info in list() {
+        let _ = close(info.id);
+    }
+}
+
+/// Close sessions nobody typed into for `IDLE_TIMEOUT`, returning their ids
+pub fn close_idle() -> Vec<u32> {
+    let idle: Vec<u32> = SESSIONS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|s| s.last_used.elapsed() >= IDLE_TIMEOUT)
+        .map(|s| s.id)
+        .collect();
+    for id in &idle {
+        let _ = close(*id);
+    }
+    idle
+}
+
+/// Collect the pending output; the session is removed when `kill` is set or its program ended
+fn take_output(id: u32, kill: bool) -> Result<PtyOutput> {
+    let mut sessions = SESSIONS.lock().unwrap();
+    let index = sessions
+        .iter()
+        .position(|s| s.id == id)
+        .ok_or_else(|| anyhow!("No PTY session {}", id))?;
+    if kill {
+        // Waiting for the program to go away must not block the other sessions
+        let mut session = sessions.remove(index);
+        drop(sessions);
+        let _ = session.child.kill();
+        let exit_code = session.child.wait().ok().map(|status| status.exit_code());
+        return Ok(session.collect(exit_code));
+    }
+
+    let session = &mut sessions[index];
+    let exit_code = session.child.try_wait()?.map(|status| status.exit_code());
+    let output = session.collect(exit_code);
+    if exit_code.is_some() {
+        sessions.remove(index);
+    }
+    Ok(output)
+}
+
+impl PtySession {
+    /// Take the pending output along with the current screen
+    fn collect(&self, exit_code: Option<u32>) -> PtyOutput {
+        let mut shared = self.output.lock().unwrap();
+        let text = shared.pending.take_text();
+        let screen = shared.parser.screen();
+        PtyOutput {
+            text,
+            screen: screen.contents(),
+            alternate_screen: screen.alternate_screen(),
+            exit_code,
+        }
+    }
+}
+
+/// Raw output waiting to be read, limited like command output: the beginning and the
+/// end are kept, whole lines in between are dropped
+struct PendingOutput {
+    head: Vec<u8>,
+    head_limit: usize,
+    tail: VecDeque<u8>,
+    tail_limit: usize,
+    omitted_lines: usize,
+}
+
+impl PendingOutput {
+    fn new(head_limit: usize, tail_limit: usize) -> Self {
+        Self {
+            head: Vec::new(),
+            head_limit,
+            tail: VecDeque::new(),
+            tail_limit,
+            omitted_lines: 0,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        if self.tail.is_empty() && self.head.len() + bytes.len() <= self.head_limit {
+            self.head.extend_from_slice(bytes);
+            return;
+        }
+        self.tail.extend(bytes);
+        while self.tail.len() > self.tail_limit {
+            // Drop up to the next line break, or just the excess when there is none
+            let excess = self.tail.len() - self.tail_limit;
+            let cut = self
+                .tail
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(excess, |pos| pos + 1);
+            self.tail.drain(..cut);
+            self.omitted_lines += 1;
+        }
+    }
+
+    /// Everything pending as plain text, emptying the buffer
+    fn take_text(&mut self) -> String {
+        let mut text = plain_text(&std::mem::take(&mut self.head));
+        if self.omitted_lines > 0 {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(
+                &get_i18n()
+                    .get("run_command_output_truncated")
+                    .replace("{}", &self.omitted_lines.to_string()),
+            );
+            text.push('\n');
+        }
+        let tail: Vec<u8> = std::mem::take(&mut self.tail).into();
+        text.push_str(&plain_text(&tail));
+        self.omitted_lines = 0;
+        text
+    }
+}
+
+/// Bytes for named keys such as `enter`, `up` or `ctrl-c`
+pub fn key_bytes(name: &str) -> Option<Vec<u8>> {
+    let name = name.trim().to_lowercase();
+    let bytes: &[u8] = match name.as_str() {
+        "enter" | "return" => b"\r",
+        "tab" => b"\t",
+        "esc" | "escape" => b"\x1b",
+        "backspace" => b"\x7f",
+        "space" => b" ",
+        "up" => b"\x1b[A",
+        "down" => b"\x1b[B",
+        "right" => b"\x1b[C",
+        "left" => b"\x1b[D",
+        "home" => b"\x1b[H",
+        "end" => b"\x1b[F",
+        "pageup" => b"\x1b[5~",
+        "pagedown" => b"\x1b[6~",
+        "delete" => b"\x1b[3~",
+        _ => {
+            // ctrl-a .. ctrl-z
+            let letter = name.strip_prefix("ctrl-").or(name.strip_prefix("ctrl+"))?;
+            let &[c] = letter.as_bytes() else {
+                return None;
+            };
+            return c.is_ascii_lowercase().then(|| vec![c & 0x1f]);
+        }
+    };
+    Some(bytes.to_vec())
+}
+
+/// Terminal output as plain text: escape sequences removed, lines redrawn with `\r`
+/// reduced to their last state and backspaces applied
+pub fn plain_text(bytes: &[u8]) -> String {
+    static ESCAPES: OnceLock<Regex> = OnceLock::new();
+    let escapes = ESCAPES.get_or_init(|| {
+        Regex::new(
+            r"\x1b(?:\[[0-?]*[ -/]*[@-~]|\][^\x07\x1b]*(?:\x07|\x1b\\)|[PX^_][^\x1b]*\x1b\\|[()*+][0-9A-Za-z]|[@-Z\\-_=>78])",
+        )
+        .expect("valid escape pattern")
+    });
+    let text = String::from_utf8_lossy(bytes);
+    let text = escapes.replace_all(&text, "");
+
+    let mut lines = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let line = line.rsplit('\r').next().unwrap_or_default();
+        let mut plain = String::new();
+        for c in line.chars() {
+            match c {
+                '\x08' => {
+                    plain.pop();
+                }
+                '\t' => plain.push(c),
+                c if c.is_control() => {}
+                c => plain.push(c),
+            }
+        }
+        lines.push(plain);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text() {
+        let raw = b"\x1b[1;32mok\x1b[0m done\r\n50%\r100%\r\nab\x08c\x1b]0;title\x07!\n";
+        assert_eq!(plain_text(raw), "ok done\n100%\nac!\n");
+        assert_eq!(key_bytes("ctrl-c"), Some(vec![3]));
+        assert_eq!(key_bytes("Up"), Some(b"\x1b[A".to_vec()));
+        assert_eq!(key_bytes("ctrl-1"), None);
+    }
+
+    #[test]
+    fn test_pending_output_keeps_head_and_tail() {
+        let mut pending = PendingOutput::new(8, 8);
+        pending.push(b"one\n");
+        for i in 0..100 {
+            pending.push(format!("line {}\n", i).as_bytes());
+        }
+        let text = pending.take_text();
+        assert!(text.starts_with("one\n"), "{:?}", text);
+        assert!(text.ends_with("line 99\n"), "{:?}", text);
+        assert!(!text.contains("line 50"));
+        assert!(pending.head.is_empty() && pending.tail.is_empty());
+        assert_eq!(pending.take_text(), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_close_kills_running_program() {
+        let id = start(
+            "sleep 30",
+            &std::env::temp_dir(),
+            &HashMap::new(),
+            DEFAULT_ROWS,
+            DEFAULT_COLS,
+        )
+        .unwrap();
+        assert!(close(id).is_ok());
+        assert!(!exists(id));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pty_session() {
+        let id = start(
+            "printf 'name? '; read name; echo \"hi $name\"",
+            &std::env::temp_dir(),
+            &HashMap::new(),
+            DEFAULT_ROWS,
+            DEFAULT_COLS,
+        )
+        .unwrap();
+        let quiet = Duration::from_millis(200);
+        let max_wait = Duration::from_secs(5);
+
+        let output = read_until_quiet(id, quiet, max_wait).await.unwrap();
+        assert!(output.text.contains("name?"), "{:?}", output.text);
+        assert_eq!(output.exit_code, None);
+
+        write(id, b"bob\r").unwrap();
+        let output = read_until_quiet(id, quiet, max_wait).await.unwrap();
+        assert!(output.text.contains("hi bob"), "{:?}", output.text);
+        assert!(output.screen.contains("name? bob"));
+        assert_eq!(output.exit_code, Some(0));
+        assert!(!exists(id));
+    }
+}
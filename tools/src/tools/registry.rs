@@ -0,0 +1,374 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use super::builtin::builtin_tools;
+use crate::types::{approve_action_for_session, is_action_approved, ToolDefinition, ToolResult};
+use ui::{get_i18n, prompt_approval};
+
+/// How a tool call is approved before it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalPolicy {
+    /// Runs without asking
+    Never,
+    /// The registry asks before every call (unless --ally or approved for the session)
+    Prompt,
+    /// The tool asks itself, e.g. to show a diff or consult the command allow list
+    Custom,
+}
+
+/// Per-call execution context
+pub struct ToolContext<'a> {
+    pub working_dir: &'a Path,
+    /// False under --ally: run without asking
+    pub require_approval: bool,
+}
+
+/// A tool the model can call
+#[async_trait]
+pub trait Tool: Send + Sync {
+    /// Function name sent to the model; must be unique in the registry
+    fn name(&self) -> &str;
+
+    fn description(&self) -> &str;
+
+    /// JSON schema of the arguments object
+    fn parameters(&self) -> Value;
+
+    /// Tools without side effects may run concurrently with each other
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    /// Read-only tools run freely, everything else is confirmed by default
+    fn approval(&self) -> ApprovalPolicy {
+        if self.is_read_only() {
+            ApprovalPolicy::Never
+        } else {
+            ApprovalPolicy::Prompt
+        }
+    }
+
+    /// Whether streamed arguments carry everything needed to run the call.
+    /// Calls with incomplete arguments are dropped instead of executed.
+    fn arguments_complete(&self, _arguments: &Value) -> bool {
+        true
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult>;
+
+    /// Definition for the `tools` array of a chat request
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::function(self.name(), self.description(), self.parameters())
+    }
+}
+
+/// Set of tools offered to the model, dispatches calls by name
+#[derive(Default, Clone)]
+pub struct ToolRegistry {
+    tools: Vec<Arc<dyn Tool>>,
+}
+
+impl ToolRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry holding the built-in tools
+    pub fn with_builtin_tools() -> Self {
+        let mut registry = Self::new();
+        for tool in builtin_tools() {
+            registry.register_boxed(tool);
+        }
+        registry
+    }
+
+    /// Add a tool, replacing any tool with the same name
+    pub fn register<T: Tool + 'static>(&mut self, tool: T) {
+        self.register_boxed(Box::new(tool));
+    }
+
+    pub fn register_boxed(&mut self, tool: Box<dyn Tool>) {
+        let tool: Arc<dyn Tool> = Arc::from(tool);
+        match self.tools.iter_mut().find(|t| t.name() == tool.name()) {
+            Some(existing) => *existing = tool,
+            None => self.tools.push(tool),
+        }
+    }
+
+    /// Remove a tool by name, returns whether it was registered
+    pub fn unregister(&mut self, name: &str) -> bool {
+        let before = self.tools.len();
+        self.tools.retain(|t| t.name() != name);
+        self.tools.len() != before
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Tool>> {
+        self.tools.iter().find(|t| t.name() == name).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.tools.iter().map(|t| t.name().to_string()).collect()
+    }
+
+    /// The `tools` array of a chat request
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools.iter().map(|t| t.definition()).collect()
+    }
+
+    /// Unknown tools are treated as having side effects
+    pub fn is_read_only(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|t| t.is_read_only())
+    }
+
+    /// Read-only tools that will not stop to ask the user, so they can share a batch.
+    /// `Custom` tools may prompt even under --ally (e.g. the command approval list)
+    pub fn can_run_concurrently(&self, name: &str, require_approval: bool) -> bool {
+        self.get(name).is_some_and(|t| {
+            t.is_read_only()
+                && match t.approval() {
+                    ApprovalPolicy::Never => true,
+                    ApprovalPolicy::Prompt => !require_approval,
+                    ApprovalPolicy::Custom => false,
+                }
+        })
+    }
+
+    /// Unknown tools only need structurally complete arguments
+    pub fn arguments_complete(&self, name: &str, arguments: &Value) -> bool {
+        self.get(name)
+            .is_none_or(|t| t.arguments_complete(arguments))
+    }
+
+    /// Tool list for the system prompt
+    pub fn descriptions(&self) -> String {
+        self.tools
+            .iter()
+            .map(|t| format!("- {}: {}", t.name(), t.description()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Run a tool call, asking for approval first when its policy requires it
+    pub async fn execute(
+        &self,
+        name: &str,
+        arguments: &str,
+        ctx: &ToolContext<'_>,
+    ) -> Result<ToolResult> {
+        let i18n = get_i18n();
+        let Some(tool) = self.get(name) else {
+            let tmpl = i18n.get("tool_unknown");
+            return Ok(ToolResult::error(tmpl.replace("{}", name)));
+        };
+
+        if tool.approval() == ApprovalPolicy::Prompt
+            && ctx.require_approval
+            && !approve_call(name, arguments)?
+        {
+            let tmpl = i18n.get("tool_user_rejected");
+            return Ok(ToolResult::error(tmpl.replace("{}", name)));
+        }
+
+        tool.execute(arguments, ctx).await
+    }
+}
+
+/// Ask the user to approve a call of a `Prompt` tool
+fn approve_call(name: &str, arguments: &str) -> Result<bool> {
+    if is_action_approved(name) {
+        return Ok(true);
+    }
+
+    let (approved, always, view_details) = prompt_approval(name, name, Some(arguments))?;
+    if view_details {
+        return Ok(ui::show_detailed_content(name, name, arguments)?);
+    }
+    if approved && always {
+        approve_action_for_session(name);
+    }
+    Ok(approved)
+}
+
+/// Process-wide registry used by the chat loop
+fn global() -> &'static RwLock<ToolRegistry> {
+    static REGISTRY: OnceLock<RwLock<ToolRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(ToolRegistry::with_builtin_tools()))
+}
+
+/// Snapshot of the process-wide registry
+pub fn registry() -> ToolRegistry {
+    global().read().unwrap().clone()
+}
+
+/// Register a project-specific tool with the process-wide registry
+pub fn register_tool<T: Tool + 'static>(tool: T) {
+    global().write().unwrap().register(tool);
+}
+
+/// Remove a tool from the process-wide registry
+pub fn unregister_tool(name: &str) -> bool {
+    global().write().unwrap().unregister(name)
+}
+
+/// Definitions of every registered tool
+pub fn get_available_tools() -> Vec<ToolDefinition> {
+    global().read().unwrap().definitions()
+}
+
+/// Tools without side effects, safe to run concurrently with each other
+pub fn is_read_only_tool(name: &str) -> bool {
+    global().read().unwrap().is_read_only(name)
+}
+
+/// Read-only tools that run without asking, safe to batch with each other
+pub fn can_run_concurrently(name: &str, require_approval: bool) -> bool {
+    global()
+        .read()
+        .unwrap()
+        .can_run_concurrently(name, require_approval)
+}
+
+/// Whether streamed arguments are complete enough to run the call
+pub fn is_arguments_complete(name: &str, arguments: &Value) -> bool {
+    global().read().unwrap().arguments_complete(name, arguments)
+}
+
+/// Tool list for the system prompt
+pub fn get_tools_description() -> String {
+    global().read().unwrap().descriptions()
+}
+
+/// Run a tool call through the process-wide registry
+pub async fn execute_tool(
+    name: &str,
+    arguments: &str,
+    working_dir: &Path,
+    require_approval: bool,
+) -> Result<ToolResult> {
+    let ctx = ToolContext {
+        working_dir,
+        require_approval,
+    };
+    registry().execute(name, arguments, &ctx).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Echo;
+
+    #[async_trait]
+    impl Tool for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the text back"
+        }
+
+        fn parameters(&self) -> Value {
+            json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            })
+        }
+
+        fn is_read_only(&self) -> bool {
+            true
+        }
+
+        async fn execute(&self, arguments: &str, _ctx: &ToolContext<'_>) -> Result<ToolResult> {
+            let args: Value = serde_json::from_str(arguments)?;
+            let text = args["text"].as_str().unwrap_or("").to_string();
+            Ok(ToolResult::ok(text.clone(), text))
+        }
+    }
+
+    #[test]
+    fn test_builtin_tools_have_unique_names() {
+        let registry = ToolRegistry::with_builtin_tools();
+        let mut names = registry.names();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+        assert!(registry.is_read_only("file_read"));
+        assert!(!registry.is_read_only("run_command"));
+        assert!(!registry.is_read_only("no_such_tool"));
+    }
+
+    #[test]
+    fn test_can_run_concurrently() {
+        let mut registry = ToolRegistry::with_builtin_tools();
+        assert!(registry.can_run_concurrently("file_read", true));
+        assert!(!registry.can_run_concurrently("run_command", false));
+        assert!(!registry.can_run_concurrently("no_such_tool", false));
+
+        // A read-only tool the registry asks about only batches when nobody is asked
+        struct Lookup;
+        #[async_trait]
+        impl Tool for Lookup {
+            fn name(&self) -> &str {
+                "lookup"
+            }
+            fn description(&self) -> &str {
+                ""
+            }
+            fn parameters(&self) -> Value {
+                json!({})
+            }
+            fn is_read_only(&self) -> bool {
+                true
+            }
+            fn approval(&self) -> ApprovalPolicy {
+                ApprovalPolicy::Prompt
+            }
+            async fn execute(&self, _: &str, _: &ToolContext<'_>) -> Result<ToolResult> {
+                Ok(ToolResult::ok(String::new(), String::new()))
+            }
+        }
+        registry.register(Lookup);
+        assert!(!registry.can_run_concurrently("lookup", true));
+        assert!(registry.can_run_concurrently("lookup", false));
+    }
+
+    #[test]
+    fn test_arguments_complete() {
+        let registry = ToolRegistry::with_builtin_tools();
+        assert!(registry.arguments_complete("file_write", &json!({"path": "a", "content": ""})));
+        assert!(!registry.arguments_complete("file_read", &json!({"path": ""})));
+        assert!(!registry.arguments_complete("file_write", &json!({"path": "a"})));
+        assert!(!registry.arguments_complete("file_replace", &json!({"path": "a", "edits": []})));
+        assert!(registry.arguments_complete("file_list", &json!({})));
+        assert!(registry.arguments_complete("no_such_tool", &json!({})));
+    }
+
+    #[tokio::test]
+    async fn test_registered_tool_is_offered_and_dispatched() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Echo);
+        assert_eq!(registry.definitions()[0].function.name, "echo");
+
+        let ctx = ToolContext {
+            working_dir: Path::new("."),
+            require_approval: true,
+        };
+        let result = registry
+            .execute("echo", r#"{"text":"hi"}"#, &ctx)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.message, "hi");
+
+        let result = registry.execute("missing", "{}", &ctx).await.unwrap();
+        assert!(!result.success);
+    }
+}
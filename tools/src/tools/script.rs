@@ -0,0 +1,386 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::command_manager::CommandConfig;
+use super::executor::command_operations::{
+    approve_command, command_timeout_secs, shell_command, CommandRequest,
+};
+use super::executor::command_runner::{run_streaming, RunEnd};
+use super::registry::{self, ApprovalPolicy, Tool, ToolContext};
+use crate::types::ToolResult;
+use ui::{get_i18n, ToolProgress};
+
+/// Project file declaring script tools, relative to the working directory
+pub const SCRIPT_TOOLS_FILE: &str = ".friendev/tools.toml";
+
+/// Prefix of the per-argument environment variables (`input = "env"`)
+const ENV_PREFIX: &str = "FRIENDEV_ARG_";
+
+/// cmd.exe has no quoting that keeps `&`, `|` or `%VAR%` in a value literal, so
+/// `{{arg}}` placeholders are only filled in for POSIX shells
+const PLACEHOLDERS_SUPPORTED: bool = cfg!(not(target_os = "windows"));
+
+#[derive(Debug, Deserialize)]
+struct ScriptToolsFile {
+    #[serde(default, rename = "tool")]
+    tools: Vec<ScriptToolSpec>,
+}
+
+/// One `[[tool]]` entry of `.friendev/tools.toml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptToolSpec {
+    pub name: String,
+    pub description: String,
+    /// JSON schema of the arguments, as a TOML table or a JSON string
+    #[serde(default = "empty_schema", deserialize_with = "deserialize_schema")]
+    pub parameters: Value,
+    /// Shell command; `{{arg}}` placeholders are replaced with shell-quoted argument values
+    /// (not on Windows, where tools read their arguments from stdin or the environment)
+    pub command: String,
+    /// How the arguments reach the command besides the template
+    #[serde(default)]
+    pub input: ScriptInput,
+    /// Side-effect free tools may run concurrently with other reads
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptInput {
+    /// The arguments object as JSON on stdin
+    #[default]
+    Stdin,
+    /// One `FRIENDEV_ARG_<NAME>` variable per argument
+    Env,
+}
+
+fn empty_schema() -> Value {
+    json!({ "type": "object", "properties": {} })
+}
+
+fn deserialize_schema<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::String(text) => serde_json::from_str(&text).map_err(serde::de::Error::custom),
+        value => Ok(value),
+    }
+}
+
+/// Tool backed by a shell command from the project config
+pub struct ScriptTool {
+    spec: ScriptToolSpec,
+}
+
+impl ScriptTool {
+    pub fn new(spec: ScriptToolSpec) -> Self {
+        Self { spec }
+    }
+
+    /// The command with `{{arg}}` placeholders filled in
+    fn render_command(&self, arguments: &Value) -> String {
+        let mut rendered = String::new();
+        let mut rest = self.spec.command.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start..].find("}}") else {
+                break;
+            };
+            let key = rest[start + 2..start + len].trim();
+            rendered.push_str(&rest[..start]);
+            rendered.push_str(&shell_quote(&value_text(&arguments[key])));
+            rest = &rest[start + len + 2..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+}
+
+#[async_trait]
+impl Tool for ScriptTool {
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn description(&self) -> &str {
+        &self.spec.description
+    }
+
+    fn parameters(&self) -> Value {
+        self.spec.parameters.clone()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.spec.read_only
+    }
+
+    // Approved like run_command, including the session-wide approval
+    fn approval(&self) -> ApprovalPolicy {
+        ApprovalPolicy::Custom
+    }
+
+    async fn execute(&self, arguments: &str, ctx: &ToolContext<'_>) -> Result<ToolResult> {
+        let args: Value = serde_json::from_str(arguments)?;
+        let command = self.render_command(&args);
+
+        let env: HashMap<String, String> = match self.spec.input {
+            ScriptInput::Stdin => HashMap::new(),
+            ScriptInput::Env => args
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| (env_name(key), value_text(value)))
+                .collect(),
+        };
+        let timeout_secs = command_timeout_secs(None);
+
+        let config = CommandConfig::load()?;
+        let request = CommandRequest {
+            command: &command,
+            mode: "foreground",
+            cwd: Some(ctx.working_dir),
+            env: &env,
+            timeout_secs: Some(timeout_secs),
+        };
+        if let Some(rejected) = approve_command(&config, &request, ctx.require_approval)? {
+            return Ok(rejected);
+        }
+
+        let mut cmd = shell_command(&command);
+        cmd.current_dir(ctx.working_dir).envs(&env);
+        let input = (self.spec.input == ScriptInput::Stdin).then(|| args.to_string().into_bytes());
+
+        // Same limits and live output as a foreground run_command
+        let i18n = get_i18n();
+        let timeout = (timeout_secs > 0).then(|| Duration::from_secs(timeout_secs));
+        let result = run_streaming(cmd, input, timeout, |line| {
+            let _ = ToolProgress::output_line(line);
+        })
+        .await;
+        let run = match result {
+            Ok(run) => run,
+            Err(e) => {
+                let tmpl = i18n.get("run_command_execute_error");
+                return Ok(ToolResult::error(tmpl.replace("{}", &e.to_string())));
+            }
+        };
+
+        let brief = match run.end {
+            RunEnd::Exited(Some(0)) => {
+                let brief = i18n
+                    .get("script_tool_brief")
+                    .replace("{}", &run.output.lines().count().to_string());
+                return Ok(ToolResult::ok(brief, run.output));
+            }
+            RunEnd::Exited(code) => i18n
+                .get("script_tool_failed")
+                .replacen("{}", &self.spec.name, 1)
+                .replacen("{}", &code.unwrap_or(-1).to_string(), 1),
+            RunEnd::TimedOut => i18n
+                .get("run_command_timed_out")
+                .replace("{}", &timeout_secs.to_string()),
+            RunEnd::Interrupted => i18n.get("run_command_interrupted"),
+        };
+        let mut failed = ToolResult::error(brief);
+        failed.message = format!("{}\n{}", failed.brief, run.output);
+        Ok(failed)
+    }
+}
+
+/// `FRIENDEV_ARG_<NAME>` variable for an argument; characters not allowed in
+/// variable names become `_`
+fn env_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", ENV_PREFIX, name)
+}
+
+/// Argument as plain text: strings unquoted, missing values empty, the rest as JSON
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Quote a value as a single POSIX shell word
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Path of the script tools file for a working directory
+pub fn script_tools_path(working_dir: &Path) -> PathBuf {
+    working_dir.join(SCRIPT_TOOLS_FILE)
+}
+
+/// Parse the script tools declared for a working directory (none when the file is missing)
+pub fn load_script_tools(working_dir: &Path) -> Result<Vec<ScriptTool>> {
+    let path = script_tools_path(working_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    parse_script_tools(&content, PLACEHOLDERS_SUPPORTED)
+        .with_context(|| format!("Invalid {}", path.display()))
+}
+
+fn parse_script_tools(content: &str, placeholders_supported: bool) -> Result<Vec<ScriptTool>> {
+    let file: ScriptToolsFile = toml::from_str(content)?;
+    for spec in &file.tools {
+        let valid_name = !spec.name.is_empty()
+            && spec.name.len() <= 64
+            && spec
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_name {
+            bail!(
+                "tool name `{}` must be 1-64 letters, digits, `_` or `-`",
+                spec.name
+            );
+        }
+        if !placeholders_supported && spec.command.contains("{{") {
+            bail!(
+                "tool `{}`: `{{{{arg}}}}` placeholders are not supported on Windows, \
+                 read the arguments with input = \"stdin\" or \"env\" instead",
+                spec.name
+            );
+        }
+    }
+    Ok(file.tools.into_iter().map(ScriptTool::new).collect())
+}
+
+/// Register the project's script tools; names taken by other tools are skipped.
+/// Returns the registered and the skipped names.
+pub fn register_script_tools(working_dir: &Path) -> Result<(Vec<String>, Vec<String>)> {
+    let existing = registry::registry().names();
+    let mut registered = Vec::new();
+    let mut skipped = Vec::new();
+    for tool in load_script_tools(working_dir)? {
+        let name = tool.name().to_string();
+        if existing.contains(&name) || registered.contains(&name) {
+            skipped.push(name);
+        } else {
+            registry::register_tool(tool);
+            registered.push(name);
+        }
+    }
+    Ok((registered, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOOLS_TOML: &str = r#"
+[[tool]]
+name = "greet"
+description = "Greet someone"
+command = "echo hello {{who}}"
+read_only = true
+[tool.parameters]
+type = "object"
+required = ["who"]
+[tool.parameters.properties.who]
+type = "string"
+
+[[tool]]
+name = "count_args"
+description = "Echo arguments"
+parameters = '{"type":"object","properties":{"n":{"type":"integer"}}}'
+command = "cat"
+"#;
+
+    fn ctx(dir: &Path) -> ToolContext<'_> {
+        ToolContext {
+            working_dir: dir,
+            require_approval: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_script_tools() {
+        let tools = parse_script_tools(TOOLS_TOML, true).unwrap();
+        assert_eq!(tools.len(), 2);
+        assert!(tools[0].is_read_only());
+        assert_eq!(tools[0].parameters()["required"], json!(["who"]));
+        assert_eq!(tools[1].parameters()["properties"]["n"]["type"], "integer");
+        assert_eq!(tools[1].spec.input, ScriptInput::Stdin);
+
+        assert!(parse_script_tools(
+            "[[tool]]\nname = \"bad name\"\ndescription = \"\"\ncommand = \"true\"",
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_placeholders_rejected_without_posix_shell() {
+        // As on Windows: templates are refused, stdin and env tools still load
+        let Err(error) = parse_script_tools(TOOLS_TOML, false) else {
+            panic!("placeholders accepted");
+        };
+        assert!(error.to_string().contains("greet"));
+
+        let stdin_only = TOOLS_TOML.split("[[tool]]").nth(2).unwrap();
+        let tools = parse_script_tools(&format!("[[tool]]{}", stdin_only), false).unwrap();
+        assert_eq!(tools[0].name(), "count_args");
+    }
+
+    #[test]
+    fn test_render_command_quotes_arguments() {
+        let tools = parse_script_tools(TOOLS_TOML, true).unwrap();
+        let command = tools[0].render_command(&json!({ "who": "it's me; rm -rf /" }));
+        assert_eq!(command, r"echo hello 'it'\''s me; rm -rf /'");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_passes_arguments() {
+        let dir = std::env::temp_dir();
+        let tools = parse_script_tools(TOOLS_TOML, true).unwrap();
+
+        let result = tools[0]
+            .execute(r#"{"who":"team"}"#, &ctx(&dir))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.message, "hello team\n");
+
+        let result = tools[1].execute(r#"{"n":3}"#, &ctx(&dir)).await.unwrap();
+        assert_eq!(result.message, "{\"n\":3}\n");
+
+        let env_tool = ScriptTool::new(ScriptToolSpec {
+            name: "env".to_string(),
+            description: String::new(),
+            parameters: empty_schema(),
+            command: "echo $FRIENDEV_ARG_TABLE $FRIENDEV_ARG_ROW_ID; exit 3".to_string(),
+            input: ScriptInput::Env,
+            read_only: false,
+        });
+        let result = env_tool
+            .execute(r#"{"table":"users","row-id":7}"#, &ctx(&dir))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.message.contains("users 7"));
+    }
+
+    #[test]
+    fn test_env_name() {
+        assert_eq!(env_name("table"), "FRIENDEV_ARG_TABLE");
+        assert_eq!(env_name("row-id.v2"), "FRIENDEV_ARG_ROW_ID_V2");
+    }
+}
@@ -1,3 +1,4 @@
+use history::ImageUrl;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Mutex;
@@ -30,6 +31,8 @@ pub struct ToolResult {
     pub message: String,
     pub verification_required: bool,
     pub verification_message: Option<String>,
+    /// 需要展示给模型的图片（作为随后的 user 消息发送）
+    pub images: Vec<ImageUrl>,
 }
 
 impl ToolResult {
@@ -40,9 +43,15 @@ impl ToolResult {
             message: output,
             verification_required: false,
             verification_message: None,
+            images: Vec::new(),
         }
     }
 
+    pub fn with_images(mut self, images: Vec<ImageUrl>) -> Self {
+        self.images = images;
+        self
+    }
+
     pub fn error(brief: String) -> Self {
         Self {
             success: false,
@@ -50,17 +59,32 @@ impl ToolResult {
             message: brief,
             verification_required: false,
             verification_message: None,
+            images: Vec::new(),
         }
     }
 }
 
+/// 发送给模型的工具定义（`tools` 数组的元素）
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Tool {
+pub struct ToolDefinition {
     #[serde(rename = "type")]
     pub tool_type: String,
     pub function: ToolFunction,
 }
 
+impl ToolDefinition {
+    pub fn function(name: &str, description: &str, parameters: serde_json::Value) -> Self {
+        Self {
+            tool_type: "function".to_string(),
+            function: ToolFunction {
+                name: name.to_string(),
+                description: description.to_string(),
+                parameters,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolFunction {
     pub name: String,
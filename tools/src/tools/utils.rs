@@ -1,3 +1,35 @@
+/// 可分段输入的内容哈希（64 位 FNV-1a）：逐字节计算，分段方式不影响结果
+pub struct ContentHasher(u64);
+
+impl ContentHasher {
+    pub fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 整段内容的哈希，与用 `ContentHasher` 分段计算的结果相同
+pub fn content_hash(content: &[u8]) -> u64 {
+    let mut hasher = ContentHasher::new();
+    hasher.update(content);
+    hasher.finish()
+}
+
 /// 格式化文件大小显示
 pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -14,18 +46,3 @@ pub fn format_size(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
-
-/// 自动生成工具列表描述，用于系统提示词
-pub fn get_tools_description() -> String {
-    let tools = crate::tools::definitions::get_available_tools();
-    let mut descriptions = Vec::new();
-
-    for tool in tools {
-        descriptions.push(format!(
-            "- {}: {}",
-            tool.function.name, tool.function.description
-        ));
-    }
-
-    descriptions.join("\n")
-}
@@ -1,7 +1,7 @@
 pub mod ui;
 
 pub use ui::{
-    enhanced_output, extract_key_argument, get_i18n, print_model_list, prompt_approval,
+    confirm_sensitive_access, enhanced_output, extract_key_argument, get_i18n, print_model_list, prompt_approval,
     select_model, set_review_handler, set_smart_approval_mode, show_detailed_content, ReviewRequest, Spinner,
     ToolCallDisplay, ToolProgress,
 };
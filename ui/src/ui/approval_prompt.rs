@@ -112,55 +112,79 @@ pub fn prompt_approval(
         i18n.get("approval_opt_reject"),
     ];
 
-    loop {
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt(i18n.get("approval_choice_prompt"))
-            .items(&choices)
-            .default(0)
-            .interact()
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-
-        match selection {
-            0 => return Ok((true, false, false)), // Approve
-            1 => {
-                println!("  {} {}", "✓".green(), i18n.get("approval_always_approved"));
-                return Ok((true, true, false)); // Always
-            }
-            2 => {
-                // Check if we have a review handler
-                if REVIEW_HANDLER.get().is_some() {
-                     let request = ReviewRequest {
-                        action,
-                        subject: file_path,
-                        preview: content_preview,
-                    };
-                    
-                    // Try to run review
-                    if let Some(handler) = REVIEW_HANDLER.get() {
-                        println!("{}", i18n.get("approval_review_wait").yellow());
-                        if let Err(e) = handler(&request) {
-                             println!("{} {}", "Error:".red(), e);
-                             // If review fails, fall back to showing raw content?
-                             // Or just let user decide again.
-                        }
-                    }
-
-                    // After review (or attempt), ask for final decision
-                    let approved = prompt_review_decision(&i18n)?;
-                    return Ok((approved, false, false));
-                } else {
-                     // No review handler, treat as "Show Raw Details" request
-                     return Ok((true, false, true));
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(i18n.get("approval_choice_prompt"))
+        .items(&choices)
+        .default(0)
+        .interact()
+        .map_err(io::Error::other)?;
+
+    match selection {
+        0 => Ok((true, false, false)), // Approve
+        1 => {
+            println!("  {} {}", "✓".green(), i18n.get("approval_always_approved"));
+            Ok((true, true, false)) // Always
+        }
+        2 => {
+            // Check if we have a review handler
+            if let Some(handler) = REVIEW_HANDLER.get() {
+                let request = ReviewRequest {
+                    action,
+                    subject: file_path,
+                    preview: content_preview,
+                };
+
+                // Try to run review
+                println!("{}", i18n.get("approval_review_wait").yellow());
+                if let Err(e) = handler(&request) {
+                    println!("{} {}", "Error:".red(), e);
+                    // If review fails, let the user decide below.
                 }
-            }
-            _ => {
-                println!("  {} {}", "✗".red(), i18n.get("approval_rejected"));
-                return Ok((false, false, false)); // Reject
+
+                // After review (or attempt), ask for final decision
+                let approved = prompt_review_decision(&i18n)?;
+                Ok((approved, false, false))
+            } else {
+                // No review handler, treat as "Show Raw Details" request
+                Ok((true, false, true))
             }
         }
+        _ => {
+            println!("  {} {}", "✗".red(), i18n.get("approval_rejected"));
+            Ok((false, false, false)) // Reject
+        }
     }
 }
 
+/// Explicit confirmation for sensitive paths: always asks the user, even in auto-approve
+/// or smart approval mode, and never offers "always approve"
+pub fn confirm_sensitive_access(action: &str, paths: &[(String, String)]) -> io::Result<bool> {
+    let i18n = get_i18n();
+    println!();
+    println!("{}", i18n.get("sensitive_title").red().bold());
+    println!(
+        "    {}",
+        i18n.get("sensitive_action_wants").replace("{}", &action.cyan().to_string())
+    );
+    for (path, pattern) in paths {
+        println!(
+            "      {} {}",
+            path.yellow().bold(),
+            i18n.get("sensitive_matches").replace("{}", pattern).bright_black()
+        );
+    }
+    println!();
+
+    let choices = vec![i18n.get("sensitive_opt_reject"), i18n.get("sensitive_opt_allow")];
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(i18n.get("approval_choice_prompt"))
+        .items(&choices)
+        .default(0)
+        .interact()
+        .map_err(io::Error::other)?;
+    Ok(selection == 1)
+}
+
 fn prompt_review_decision(i18n: &I18n) -> io::Result<bool> {
     println!();
     let choices = vec![
@@ -173,7 +197,7 @@ fn prompt_review_decision(i18n: &I18n) -> io::Result<bool> {
         .items(&choices)
         .default(0)
         .interact()
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        .map_err(io::Error::other)?;
         
     Ok(selection == 0)
 }
@@ -215,7 +239,7 @@ pub fn show_detailed_content(action: &str, file_path: &str, content: &str) -> io
         .items(&choices)
         .default(0)
         .interact()
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        .map_err(io::Error::other)?;
         
      Ok(selection == 0)
 }
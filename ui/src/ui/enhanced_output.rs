@@ -60,7 +60,7 @@ pub fn print_section_header(title: &str) -> io::Result<()> {
     }
     
     let remaining = term_width.saturating_sub(total_content + 2); // 2 for corners
-    let left_line = box_chars::HORIZONTAL.repeat(1);
+    let left_line = box_chars::HORIZONTAL.to_string();
     let right_line = box_chars::HORIZONTAL.repeat(remaining.saturating_sub(1));
     
     execute!(
@@ -268,6 +268,19 @@ impl ToolProgress {
         io::stdout().flush()
     }
 
+    /// Print a line of live tool output (e.g. command output) below the progress line
+    pub fn output_line(line: &str) -> io::Result<()> {
+        execute!(
+            io::stdout(),
+            Print("\n    "),
+            SetForegroundColor(Color::DarkGrey),
+            Print("│ "),
+            Print(line),
+            ResetColor
+        )?;
+        io::stdout().flush()
+    }
+
     /// Finish with success
     pub fn finish_success(&self, result: Option<&str>) -> io::Result<()> {
         let i18n = get_i18n();
@@ -41,7 +41,7 @@ pub fn select_model(
     };
     
     let selection = Select::with_theme(&theme)
-        .with_prompt(&i18n.get("model_selector_prompt"))
+        .with_prompt(i18n.get("model_selector_prompt"))
         .items(&items)
         .default(default_index)
         .interact_opt()?;
@@ -70,19 +70,62 @@ pub fn extract_key_argument(tool_name: &str, arguments: &str) -> Option<String>
     };
 
     let key = match tool_name {
-        "file_read" | "file_write" => json
+        "file_read" | "file_write" | "file_delete" | "dir_create" => json
             .get("path")
             .and_then(|v| v.as_str())
             .map(normalize_path),
-        "file_list" => json
+        "file_list" | "file_tree" => json
             .get("path")
             .and_then(|v| v.as_str())
             .map(normalize_path)
             .or_else(|| Some("./".to_string())),
+        "file_move" | "file_copy" => json
+            .get("from")
+            .and_then(|v| v.as_str())
+            .map(normalize_path),
+        "git_diff" | "git_log" => json
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(normalize_path),
+        "file_search" | "file_glob" => json
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        // 补丁涉及的第一个文件
+        "apply_patch" => json.get("patch").and_then(|v| v.as_str()).and_then(|patch| {
+            patch.lines().find_map(|line| {
+                let path = line.strip_prefix("+++ ").or(line.strip_prefix("--- "))?;
+                let path = path.split('\t').next()?.trim();
+                (path != "/dev/null").then(|| {
+                    path.strip_prefix("b/")
+                        .or(path.strip_prefix("a/"))
+                        .unwrap_or(path)
+                        .to_string()
+                })
+            })
+        }),
+        "command_status" | "command_kill" | "pty_close" => json
+            .get("id")
+            .and_then(|v| v.as_u64())
+            .map(|id| format!("#{}", id)),
+        "pty_start" => json
+            .get("command")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        // 会话编号和输入的文本
+        "pty_send" => json.get("id").and_then(|v| v.as_u64()).map(|id| {
+            let input = json.get("input").and_then(|v| v.as_str()).unwrap_or("");
+            format!("#{} {}", id, input.trim()).trim_end().to_string()
+        }),
         "network_search_auto" | "network_search_duckduckgo" | "network_search_bing" => json
             .get("keywords")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
+        // MCP tools: the first string argument
+        name if name.starts_with("mcp__") => json
+            .as_object()
+            .and_then(|args| args.values().find_map(|v| v.as_str()))
+            .map(|s| s.to_string()),
         _ => None,
     };
 